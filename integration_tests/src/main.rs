@@ -8,6 +8,13 @@ const HELLO_ID: &str = "HELLO";
 const PANIC_ID: &str = "PANIC";
 const BUTTON_ID: &str = "BUTTON";
 const RELOAD_ID: &str = "RELOAD";
+const NESTED_ID: &str = "NESTED";
+const SCOPED_INSIDE_ID: &str = "SCOPED_INSIDE";
+const SCOPED_OUTSIDE_ID: &str = "SCOPED_OUTSIDE";
+const EMIT_BUTTON_ID: &str = "EMIT_BUTTON";
+const EMIT_RESULT_ID: &str = "EMIT_RESULT";
+const HOVER_ID: &str = "HOVER";
+const HOVER_CHILD_ID: &str = "HOVER_CHILD";
 
 global_css!("
     h1 {
@@ -31,9 +38,67 @@ scoped_css!("
     .I_amNotUsed {
         height: 400px;
     }
+"
+mod nested "
+    .wrapper ${HELLO} {
+        width: 200px;
+    }
+");
+
+scoped_css!("
+    .hover_box {
+        height: 10px;
+    }
+    .hover_box:hover {
+        height: 20px;
+    }
+    .hover_box > .hover_child {
+        width: 30px;
+    }
+");
+
+scoped_css!(@component "
+    .scoped_item {
+        width: 50px;
+    }
 ");
 
 #[derive(Component)]
+struct Scoped;
+
+impl Component for Scoped {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    const STYLE_SCOPE: Option<&'static str> = Some(NATRIX_SCOPE);
+    fn render() -> impl Element<Self> {
+        e::div().child(e::div().id(SCOPED_INSIDE_ID).class(SCOPED_ITEM))
+    }
+}
+
+enum ChildMessage {
+    Clicked(usize),
+}
+
+#[derive(Component)]
+struct Child {
+    id: usize,
+}
+
+impl Component for Child {
+    type EmitMessage = ChildMessage;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(EMIT_BUTTON_ID)
+            .on::<events::Click>(|ctx: E<Self>, _| {
+                let id = *ctx.id;
+                ctx.emit(ChildMessage::Clicked(id));
+            })
+    }
+}
+
+#[derive(Component)]
+#[expect(dead_code, reason = "Used to test that unused css is tree-shaken")]
 struct NotUsed;
 
 impl Component for NotUsed {
@@ -47,6 +112,7 @@ impl Component for NotUsed {
 #[derive(Component)]
 struct HelloWorld {
     counter: usize,
+    clicked: Option<usize>,
 }
 
 impl Component for HelloWorld {
@@ -64,6 +130,7 @@ impl Component for HelloWorld {
                     .class(style!("margin: 1px 2px 3px 4px")),
             )
             .child(C::new(integration_tests_dependency::DepComp))
+            .child(C::new(integration_tests_dependency::DepBadge))
             .child(
                 e::button()
                     .id(PANIC_ID)
@@ -80,11 +147,37 @@ impl Component for HelloWorld {
                     .text(|ctx: R<Self>| *ctx.counter),
             )
             .child(e::div().id(RELOAD_ID).text(reload_tests::VALUE))
+            .child(
+                e::div()
+                    .class(nested::WRAPPER)
+                    .child(e::div().id(NESTED_ID).class(HELLO)),
+            )
+            .child(C::new(Scoped))
+            .child(
+                e::div()
+                    .id(HOVER_ID)
+                    .class(HOVER_BOX)
+                    .child(e::div().id(HOVER_CHILD_ID).class(HOVER_CHILD)),
+            )
+            .child(e::div().id(SCOPED_OUTSIDE_ID).class(SCOPED_ITEM))
+            .child(
+                C::new(Child { id: 7 }).on(|ctx: E<Self>, ChildMessage::Clicked(id)| {
+                    *ctx.clicked = Some(id);
+                }),
+            )
+            .child(
+                e::div()
+                    .id(EMIT_RESULT_ID)
+                    .text(|ctx: R<Self>| ctx.clicked.map(|id| id.to_string())),
+            )
     }
 }
 
 fn main() {
-    mount(HelloWorld { counter: 0 });
+    mount(HelloWorld {
+        counter: 0,
+        clicked: None,
+    });
 }
 
 #[cfg(test)]
@@ -94,7 +187,21 @@ mod tests {
     use thirtyfour::{By, ChromiumLikeCapabilities, DesiredCapabilities, WebDriver};
     use tokio::time::sleep;
 
-    use crate::{BUTTON_ID, HELLO_ID, HELLO_TEXT, PANIC_ID, RELOAD_ID, reload_tests};
+    use crate::{
+        BUTTON_ID,
+        EMIT_BUTTON_ID,
+        EMIT_RESULT_ID,
+        HELLO_ID,
+        HELLO_TEXT,
+        HOVER_CHILD_ID,
+        HOVER_ID,
+        NESTED_ID,
+        PANIC_ID,
+        RELOAD_ID,
+        SCOPED_INSIDE_ID,
+        SCOPED_OUTSIDE_ID,
+        reload_tests,
+    };
 
     async fn create_client() -> WebDriver {
         let mut caps = DesiredCapabilities::chrome();
@@ -162,6 +269,51 @@ mod tests {
         assert_eq!(text, "300px");
     }
 
+    #[tokio::test]
+    async fn scoped_css_reference() {
+        let client = create_client().await;
+        let element = client.find(By::Id(NESTED_ID)).await.unwrap();
+        let text = element.css_value("width").await.unwrap();
+        assert_eq!(text, "200px");
+    }
+
+    #[tokio::test]
+    async fn scoped_css_component() {
+        let client = create_client().await;
+        let element = client.find(By::Id(SCOPED_INSIDE_ID)).await.unwrap();
+        let text = element.css_value("width").await.unwrap();
+        assert_eq!(text, "50px");
+
+        let element = client.find(By::Id(SCOPED_OUTSIDE_ID)).await.unwrap();
+        let text = element.css_value("width").await.unwrap();
+        assert_ne!(text, "50px");
+    }
+
+    #[tokio::test]
+    async fn scoped_css_pseudo_class() {
+        let client = create_client().await;
+        let element = client.find(By::Id(HOVER_ID)).await.unwrap();
+        let text = element.css_value("height").await.unwrap();
+        assert_eq!(text, "10px");
+
+        client
+            .action_chain()
+            .move_to_element_center(&element)
+            .perform()
+            .await
+            .unwrap();
+        let text = element.css_value("height").await.unwrap();
+        assert_eq!(text, "20px");
+    }
+
+    #[tokio::test]
+    async fn scoped_css_combinator() {
+        let client = create_client().await;
+        let element = client.find(By::Id(HOVER_CHILD_ID)).await.unwrap();
+        let text = element.css_value("width").await.unwrap();
+        assert_eq!(text, "30px");
+    }
+
     #[tokio::test]
     async fn inline_style() {
         let client = create_client().await;
@@ -189,6 +341,29 @@ mod tests {
         assert_eq!(text, "rgba(9, 8, 7, 1)");
     }
 
+    #[tokio::test]
+    async fn dep_global_css_included_once() {
+        let client = create_client().await;
+        let element = client
+            .find(By::Id(integration_tests_dependency::DEP_BADGE_ID))
+            .await
+            .unwrap();
+        let text = element.css_value("color").await.unwrap();
+        assert_eq!(text, "rgba(9, 8, 7, 1)");
+
+        let count = client
+            .execute(
+                "return [...document.styleSheets]
+                    .flatMap(sheet => [...sheet.cssRules])
+                    .filter(rule => rule.cssText.includes('rgb(9, 8, 7)'))
+                    .length",
+                Vec::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(count.json().as_u64(), Some(1));
+    }
+
     #[tokio::test]
     async fn dep_scoped_css() {
         let client = create_client().await;
@@ -220,6 +395,30 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn child_emit_updates_parent() {
+        let client = create_client().await;
+        let result = client.find(By::Id(EMIT_RESULT_ID)).await.unwrap();
+        assert_eq!(result.text().await.unwrap(), "");
+
+        let button = client.find(By::Id(EMIT_BUTTON_ID)).await.unwrap();
+        button.click().await.unwrap();
+
+        let start = Instant::now();
+        loop {
+            sleep(Duration::from_millis(100)).await;
+            if let Ok(element) = client.find(By::Id(EMIT_RESULT_ID)).await
+                && let Ok(text) = element.text().await
+                && text == "7"
+            {
+                break;
+            }
+            if start.elapsed().as_secs() > 5 {
+                panic!("Message did not reach the parent");
+            }
+        }
+    }
+
     #[tokio::test]
     async fn dynamic_class() {
         let client = create_client().await;
@@ -248,12 +447,11 @@ mod tests {
         let start = Instant::now();
         loop {
             sleep(Duration::from_millis(100)).await;
-            if let Ok(element) = client.find(By::Id(RELOAD_ID)).await {
-                if let Ok(text) = element.text().await {
-                    if text == new_text {
-                        break;
-                    }
-                }
+            if let Ok(element) = client.find(By::Id(RELOAD_ID)).await
+                && let Ok(text) = element.text().await
+                && text == new_text
+            {
+                break;
             }
 
             if start.elapsed().as_secs() > 5 {
@@ -271,12 +469,11 @@ mod tests {
         let start = Instant::now();
         loop {
             sleep(Duration::from_millis(100)).await;
-            if let Ok(element) = client.find(By::Id(RELOAD_ID)).await {
-                if let Ok(text) = element.text().await {
-                    if text == reload_tests::VALUE {
-                        break;
-                    }
-                }
+            if let Ok(element) = client.find(By::Id(RELOAD_ID)).await
+                && let Ok(text) = element.text().await
+                && text == reload_tests::VALUE
+            {
+                break;
             }
             if start.elapsed().as_secs() > 5 {
                 panic!("Reloading took too long");
@@ -14,6 +14,7 @@ scoped_css!("
 
 pub const DEP_TEXT: &str = "NICE!";
 pub const DEP_ID: &str = "DEP_TEXT";
+pub const DEP_BADGE_ID: &str = "DEP_BADGE";
 
 #[derive(Component)]
 pub struct DepComp;
@@ -25,3 +26,14 @@ impl Component for DepComp {
         e::h1().text(DEP_TEXT).id(DEP_ID).class(HELLO)
     }
 }
+
+#[derive(Component)]
+pub struct DepBadge;
+
+impl Component for DepBadge {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::h1().text(DEP_TEXT).id(DEP_BADGE_ID)
+    }
+}
@@ -531,7 +531,7 @@ fn spawn_server(folder: PathBuf) {
 
 /// Build a project
 fn build(config: &BuildConfig) -> Result<()> {
-    println!("🧹 {}", "Cleaning dist".bright_black(),);
+    println!("🧹 {}", "Cleaning dist".bright_black());
     let _ = fs::remove_dir_all(&config.dist);
 
     println!(
@@ -877,8 +877,14 @@ fn collect_macro_output(config: &BuildConfig, wasm_file: &Path) -> Result<()> {
 fn collect_css(config: &BuildConfig, wasm_file: &Path) -> Result<()> {
     let spinner = create_spinner("🎨 Bundling css")?;
 
+    // Files are named by the hash of their content, so the same stylesheet emitted by multiple
+    // crates is only included once.
+    let mut seen = HashSet::new();
     let mut css_content = String::new();
     for file in get_macro_output_files(config).context("Reading macro output")? {
+        if !seen.insert(file.file_name().map(ToOwned::to_owned)) {
+            continue;
+        }
         fs::File::open(file)?.read_to_string(&mut css_content)?;
     }
 
@@ -950,26 +956,26 @@ impl<'i> lightningcss::visitor::Visitor<'i> for SymbolVisitor {
         &mut self,
         rule: &mut lightningcss::rules::CssRule<'i>,
     ) -> std::result::Result<(), Self::Error> {
-        if let lightningcss::rules::CssRule::Unknown(unknown_rule) = rule {
-            if unknown_rule.name == "keep" {
-                let tokens = &unknown_rule.prelude.0;
-                if let Some(token) = tokens.first() {
-                    match token {
-                        lightningcss::properties::custom::TokenOrValue::Token(
-                            lightningcss::properties::custom::Token::Ident(ident),
-                        ) => {
-                            let ident = ident.to_string();
-                            self.keep.insert(ident);
-                        }
-                        lightningcss::properties::custom::TokenOrValue::DashedIdent(ident) => {
-                            let ident = ident.to_string();
-                            self.keep.insert(ident);
-                        }
-                        _ => (),
+        if let lightningcss::rules::CssRule::Unknown(unknown_rule) = rule
+            && unknown_rule.name == "keep"
+        {
+            let tokens = &unknown_rule.prelude.0;
+            if let Some(token) = tokens.first() {
+                match token {
+                    lightningcss::properties::custom::TokenOrValue::Token(
+                        lightningcss::properties::custom::Token::Ident(ident),
+                    ) => {
+                        let ident = ident.to_string();
+                        self.keep.insert(ident);
+                    }
+                    lightningcss::properties::custom::TokenOrValue::DashedIdent(ident) => {
+                        let ident = ident.to_string();
+                        self.keep.insert(ident);
                     }
+                    _ => (),
                 }
-                *rule = lightningcss::rules::CssRule::Ignored;
             }
+            *rule = lightningcss::rules::CssRule::Ignored;
         }
         rule.visit_children(self)
     }
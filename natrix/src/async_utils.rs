@@ -1,23 +1,24 @@
 //! Async utils
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::time::Duration;
 
+use futures_core::Stream;
+use futures_util::StreamExt;
 use wasm_bindgen::prelude::Closure;
 use wasm_bindgen::{JsCast, JsValue};
 
+use crate::component::Component;
+use crate::element::Element;
+use crate::render_callbacks::ReactiveNode;
+use crate::shared::SharedSignal;
+use crate::signal::{RenderingState, Signal};
+use crate::state::{DeferredCtx, State};
 use crate::utils::debug_expect;
 
 /// Sleeps for the given duration using js `setTimeout`.
 pub async fn sleep(time: Duration) {
-    let milis = if let Ok(milis) = i32::try_from(time.as_millis()) {
-        milis
-    } else {
-        debug_assert!(
-            false,
-            "Sleep duration {}ms overflows `i32` (will use `i32::MAX` in release mode.)",
-            time.as_millis()
-        );
-        i32::MAX
-    };
+    let milis = duration_to_milis(time);
 
     let (tx, rx) = futures_channel::oneshot::channel();
     let res = crate::get_window().set_timeout_with_callback_and_timeout_and_arguments_0(
@@ -44,6 +45,287 @@ pub async fn next_animation_frame() {
     debug_expect!(rx.await, "Failed to receive animation frame signal");
 }
 
+/// Convert a `Duration` to milliseconds for js timer apis
+fn duration_to_milis(time: Duration) -> i32 {
+    if let Ok(milis) = i32::try_from(time.as_millis()) {
+        milis
+    } else {
+        debug_assert!(
+            false,
+            "Duration {}ms overflows `i32` (will use `i32::MAX` in release mode.)",
+            time.as_millis()
+        );
+        i32::MAX
+    }
+}
+
+/// The shared interval behind [`State::use_clock`]
+///
+/// The interval is cleared when this is dropped, i.e when the component is unmounted.
+pub(crate) struct Clock {
+    /// The current time in milliseconds since the unix epoch
+    pub(crate) now: Signal<f64>,
+    /// The current tick of the interval
+    tick: Duration,
+    /// The id of the running interval
+    interval: Option<i32>,
+    /// The callback run on every tick, this is reused when the tick changes.
+    callback: Closure<dyn FnMut()>,
+}
+
+impl Clock {
+    /// Create a clock updating the state behind `deferred`, and start its interval
+    pub(crate) fn new<C: Component>(deferred: DeferredCtx<C>, tick: Duration) -> Self {
+        let callback = Closure::<dyn FnMut()>::new(move || {
+            let _ = deferred.with_mut(State::tick_clock);
+        });
+
+        let mut clock = Self {
+            now: Signal::new(web_sys::js_sys::Date::now()),
+            tick,
+            interval: None,
+            callback,
+        };
+        clock.start();
+        clock
+    }
+
+    /// Use the given tick if its shorter than the current one
+    pub(crate) fn request_tick(&mut self, tick: Duration) {
+        if tick < self.tick {
+            self.tick = tick;
+            self.stop();
+            self.start();
+        }
+    }
+
+    /// Start the interval with the current tick
+    fn start(&mut self) {
+        let res = crate::get_window().set_interval_with_callback_and_timeout_and_arguments_0(
+            self.callback.as_ref().unchecked_ref(),
+            duration_to_milis(self.tick),
+        );
+        self.interval = debug_expect!(
+            res.ok().map(Some),
+            or(None),
+            "Failed to set interval. This is a bug in the browser or the framework."
+        );
+    }
+
+    /// Stop the running interval
+    fn stop(&mut self) {
+        if let Some(interval) = self.interval.take() {
+            crate::get_window().clear_interval_with_handle(interval);
+        }
+    }
+}
+
+impl Drop for Clock {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// The reason a clipboard operation failed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClipboardError {
+    /// The clipboard api is not available, for example because the page is not served over https
+    Unavailable,
+    /// The browser rejected the operation, usually because permission was denied
+    Rejected(String),
+}
+
+impl std::fmt::Display for ClipboardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unavailable => f.write_str("The clipboard api is not available"),
+            Self::Rejected(reason) => write!(f, "Clipboard access was rejected: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ClipboardError {}
+
+/// Get `navigator.clipboard`, which is `undefined` in insecure contexts
+fn get_clipboard() -> Result<web_sys::Clipboard, ClipboardError> {
+    let navigator = crate::get_window().navigator();
+    web_sys::js_sys::Reflect::get(&navigator, &JsValue::from_str("clipboard"))
+        .ok()
+        .and_then(|clipboard| clipboard.dyn_into::<web_sys::Clipboard>().ok())
+        .ok_or(ClipboardError::Unavailable)
+}
+
+/// Await a clipboard promise, converting a rejection to a [`ClipboardError`]
+async fn await_clipboard(
+    promise: Result<web_sys::js_sys::Promise, ClipboardError>,
+) -> Result<JsValue, ClipboardError> {
+    wasm_bindgen_futures::JsFuture::from(promise?)
+        .await
+        .map_err(|error| {
+            let reason = error
+                .dyn_ref::<web_sys::js_sys::Error>()
+                .map(|error| String::from(error.message()))
+                .or_else(|| error.as_string())
+                .unwrap_or_default();
+            ClipboardError::Rejected(reason)
+        })
+}
+
+/// Start writing `text` to the clipboard, see [`State::write_clipboard`]
+pub(crate) fn write_clipboard(
+    text: &str,
+) -> impl Future<Output = Result<(), ClipboardError>> + 'static {
+    let promise = get_clipboard().map(|clipboard| clipboard.write_text(text));
+    async move { await_clipboard(promise).await.map(|_| ()) }
+}
+
+/// Start reading text from the clipboard, see [`State::read_clipboard`]
+pub(crate) fn read_clipboard() -> impl Future<Output = Result<String, ClipboardError>> + 'static {
+    let promise = get_clipboard().map(|clipboard| clipboard.read_text());
+    async move {
+        let text = await_clipboard(promise).await?;
+        Ok(text.as_string().unwrap_or_default())
+    }
+}
+
+/// The connection state of a [`WebSocket`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebSocketStatus {
+    /// The connection has not been established yet
+    Connecting,
+    /// The connection is open and messages can be sent
+    Open,
+    /// The connection has been closed
+    Closed,
+    /// The connection failed, or the url was invalid
+    Error,
+}
+
+/// A websocket connection started by [`State::use_websocket`].
+///
+/// This is a cheap handle and can be cloned freely,
+/// the connection is closed when the component that started it is unmounted.
+#[derive(Clone)]
+pub struct WebSocket {
+    /// The socket, `None` if it could not be created
+    socket: Option<web_sys::WebSocket>,
+    /// The last text message received
+    last_message: SharedSignal<Option<String>>,
+    /// The connection state
+    status: SharedSignal<WebSocketStatus>,
+}
+
+impl WebSocket {
+    /// The last text message received, `None` until the first message arrives.
+    ///
+    /// Binary messages are ignored.
+    /// Messages arriving before the components have been updated replace each other, i.e only
+    /// the latest one is rendered.
+    #[must_use]
+    pub fn last_message(&self) -> &SharedSignal<Option<String>> {
+        &self.last_message
+    }
+
+    /// The state of the connection
+    #[must_use]
+    pub fn status(&self) -> &SharedSignal<WebSocketStatus> {
+        &self.status
+    }
+
+    /// Send a text message
+    ///
+    /// # Errors
+    /// If the connection is not open
+    pub fn send(&self, message: &str) -> Result<(), &'static str> {
+        let Some(socket) = &self.socket else {
+            return Err("Websocket could not be created");
+        };
+        if socket.ready_state() != web_sys::WebSocket::OPEN {
+            return Err("Websocket is not open");
+        }
+        socket
+            .send_with_str(message)
+            .map_err(|_| "Failed to send websocket message")
+    }
+}
+
+/// The socket behind [`State::use_websocket`]
+///
+/// The socket is closed when this is dropped, i.e when the component is unmounted.
+pub(crate) struct WebSocketConnection {
+    /// The socket
+    socket: web_sys::WebSocket,
+    /// The `message` callback, kept alive until the socket is closed
+    _on_message: Closure<dyn Fn(web_sys::MessageEvent)>,
+    /// The `open`, `close` and `error` callbacks, kept alive until the socket is closed
+    _on_status: [Closure<dyn Fn()>; 3],
+}
+
+impl WebSocketConnection {
+    /// Connect to the given url, returning the handle exposed to the user
+    pub(crate) fn connect(url: &str) -> (Option<Self>, WebSocket) {
+        let socket = web_sys::WebSocket::new(url).ok();
+        let handle = WebSocket {
+            socket: socket.clone(),
+            last_message: SharedSignal::new(None),
+            status: SharedSignal::new(if socket.is_some() {
+                WebSocketStatus::Connecting
+            } else {
+                WebSocketStatus::Error
+            }),
+        };
+        let connection = socket.map(|socket| Self::listen(socket, &handle));
+        (connection, handle)
+    }
+
+    /// Register the callbacks updating the signals of `handle`
+    fn listen(socket: web_sys::WebSocket, handle: &WebSocket) -> Self {
+        let last_message = handle.last_message.clone();
+        let on_message =
+            Closure::<dyn Fn(web_sys::MessageEvent)>::new(move |event: web_sys::MessageEvent| {
+                if let Some(message) = event.data().as_string() {
+                    last_message.set(Some(message));
+                }
+            });
+
+        let status = handle.status.clone();
+        let on_open = Closure::<dyn Fn()>::new(move || status.set(WebSocketStatus::Open));
+        let status = handle.status.clone();
+        let on_close = Closure::<dyn Fn()>::new(move || {
+            // A failed connection is also closed, in which case the error is more useful
+            status.update(|status| {
+                if *status != WebSocketStatus::Error {
+                    *status = WebSocketStatus::Closed;
+                }
+            });
+        });
+        let status = handle.status.clone();
+        let on_error = Closure::<dyn Fn()>::new(move || status.set(WebSocketStatus::Error));
+
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+        socket.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+        Self {
+            socket,
+            _on_message: on_message,
+            _on_status: [on_open, on_close, on_error],
+        }
+    }
+}
+
+impl Drop for WebSocketConnection {
+    fn drop(&mut self) {
+        // The callbacks are dropped with this, so they have to be removed first
+        self.socket.set_onmessage(None);
+        self.socket.set_onopen(None);
+        self.socket.set_onclose(None);
+        self.socket.set_onerror(None);
+        debug_expect!(self.socket.close(), "Failed to close websocket");
+    }
+}
+
 /// Convert a `tx` to a `Function` that sends a message once called.
 /// `Closure::once_into_js` is used to ensure that the closure is dropped after it is called.
 fn create_closure(tx: futures_channel::oneshot::Sender<()>) -> JsValue {
@@ -51,3 +333,68 @@ fn create_closure(tx: futures_channel::oneshot::Sender<()>) -> JsValue {
         let _ = tx.send(());
     })
 }
+
+/// A element that renders the latest item of a `Stream`.
+///
+/// Constructed using [`e::stream`](crate::html_elements::stream).
+#[must_use = "Web elements are useless if not rendered"]
+pub struct StreamElement<S, F> {
+    /// The stream to listen to
+    stream: S,
+    /// The function to render each item
+    render: F,
+}
+
+impl<S, F> StreamElement<S, F> {
+    /// Create a new stream element
+    pub fn new(stream: S, render: F) -> Self {
+        Self { stream, render }
+    }
+}
+
+impl<C, S, F, E> Element<C> for StreamElement<S, F>
+where
+    C: Component,
+    S: Stream + 'static,
+    S::Item: 'static,
+    F: Fn(S::Item) -> E + 'static,
+    E: Element<C>,
+{
+    fn render_box(
+        self: Box<Self>,
+        ctx: &mut State<C>,
+        render_state: &mut RenderingState,
+    ) -> web_sys::Node {
+        let Self { stream, render } = *self;
+
+        let pending: Rc<RefCell<Option<S::Item>>> = Rc::new(RefCell::new(None));
+        let pending_hook = Rc::clone(&pending);
+
+        // The hook is only ever re-run by the task below, so until the first item arrives it
+        // renders a placeholder comment, and once the stream ends it keeps the last item.
+        let (me, node) = ReactiveNode::create_initial(
+            Box::new(move |_ctx| pending_hook.borrow_mut().take().map(&render)),
+            ctx,
+        );
+        render_state.hooks.push(me);
+
+        // The sender is dropped alongside the rest of the keep alive objects when this element is
+        // removed, which ends the stream in the task below.
+        let (cancel_tx, cancel_rx) = futures_channel::oneshot::channel::<()>();
+        render_state.keep_alive.push(Box::new(cancel_tx));
+
+        let deferred = ctx.deferred_borrow();
+        wasm_bindgen_futures::spawn_local(async move {
+            let mut stream = std::pin::pin!(stream.take_until(cancel_rx));
+            while let Some(item) = stream.next().await {
+                let Some(mut ctx) = deferred.borrow_mut() else {
+                    break;
+                };
+                *pending.borrow_mut() = Some(item);
+                ctx.run_hooks(vec![me]);
+            }
+        });
+
+        node
+    }
+}
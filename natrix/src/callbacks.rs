@@ -3,8 +3,14 @@
 use crate::component::Component;
 use crate::element::Element;
 use crate::events::Event;
-use crate::html_elements::ToAttribute;
-use crate::render_callbacks::{ReactiveAttribute, ReactiveNode, SimpleReactive};
+use crate::html_elements::{AttributeName, ToAttribute, ToInnerText, ToProperty};
+use crate::render_callbacks::{
+    ReactiveAttribute,
+    ReactiveInnerText,
+    ReactiveNode,
+    ReactiveProperty,
+    SimpleReactive,
+};
 use crate::signal::RenderingState;
 use crate::state::{RenderCtx, State};
 
@@ -25,6 +31,29 @@ where
     }
 }
 
+/// A reactive closure that keeps focus within its content when it re-renders.
+///
+/// Constructed using [`e::preserve_focus`](crate::html_elements::preserve_focus).
+#[must_use = "Web elements are useless if not rendered"]
+pub struct PreserveFocus<F>(pub(crate) F);
+
+impl<F, C, R> Element<C> for PreserveFocus<F>
+where
+    F: Fn(&mut RenderCtx<C>) -> R + 'static,
+    R: Element<C> + 'static,
+    C: Component,
+{
+    fn render_box(
+        self: Box<Self>,
+        ctx: &mut State<C>,
+        render_state: &mut RenderingState,
+    ) -> web_sys::Node {
+        let (me, node) = ReactiveNode::create_initial_preserving_focus(Box::new(self.0), ctx);
+        render_state.hooks.push(me);
+        node
+    }
+}
+
 impl<F, C, R> ToAttribute<C> for F
 where
     F: Fn(&mut RenderCtx<C>) -> R + 'static,
@@ -33,7 +62,7 @@ where
 {
     fn apply_attribute(
         self: Box<Self>,
-        name: &'static str,
+        name: AttributeName,
         node: &web_sys::Element,
         ctx: &mut State<C>,
         rendering_state: &mut RenderingState,
@@ -50,6 +79,52 @@ where
     }
 }
 
+impl<F, C, R> ToProperty<C> for F
+where
+    F: Fn(&mut RenderCtx<C>) -> R + 'static,
+    R: ToProperty<C>,
+    C: Component,
+{
+    fn apply_property(
+        self: Box<Self>,
+        name: &'static str,
+        node: &web_sys::Element,
+        ctx: &mut State<C>,
+        rendering_state: &mut RenderingState,
+    ) {
+        let hook = SimpleReactive::init_new(
+            Box::new(move |ctx| ReactiveProperty {
+                name,
+                data: self(ctx),
+            }),
+            node.clone(),
+            ctx,
+        );
+        rendering_state.hooks.push(hook);
+    }
+}
+
+impl<F, C, R> ToInnerText<C> for F
+where
+    F: Fn(&mut RenderCtx<C>) -> R + 'static,
+    R: ToInnerText<C>,
+    C: Component,
+{
+    fn apply_inner_text(
+        self: Box<Self>,
+        node: &web_sys::Element,
+        ctx: &mut State<C>,
+        rendering_state: &mut RenderingState,
+    ) {
+        let hook = SimpleReactive::init_new(
+            Box::new(move |ctx| ReactiveInnerText { data: self(ctx) }),
+            node.clone(),
+            ctx,
+        );
+        rendering_state.hooks.push(hook);
+    }
+}
+
 /// Utility trait for use in stateless components
 ///
 /// When defining a stateless component it is much easier to use `impl Event<C>` than writing out
@@ -1,16 +1,19 @@
 //! Component traits
 
+use std::any::Any;
 use std::cell::RefCell;
 use std::rc::Rc;
 
 use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use wasm_bindgen::JsCast;
 
-use crate::element::Element;
+use crate::element::{Comment, Element};
 use crate::get_document;
-use crate::html_elements::ToAttribute;
+use crate::html_elements::{AttributeName, AttributeValue, ToAttribute};
+use crate::render_callbacks::ReactiveNode;
 use crate::signal::{RenderingState, SignalMethods};
-use crate::state::{ComponentData, E, HookKey, State};
-use crate::utils::SmallAny;
+use crate::state::{ComponentData, DeferredCtx, E, HookKey, State, SyncDelivery};
+use crate::utils::{SmallAny, debug_expect};
 
 /// The base component, this is implemented by the `#[derive(Component)]` macro and handles
 /// associating a component with its reactive state as well as converting to a struct to its
@@ -116,10 +119,133 @@ pub trait Component: ComponentBase {
     /// See the [Reactivity](https://vivax3794.github.io/natrix/reactivity.html) chapther in the book for more info
     fn render() -> impl Element<Self>;
 
+    /// Class added to the root element of the component.
+    ///
+    /// Set this to the `NATRIX_SCOPE` constant generated by `scoped_css!(@component "...")`
+    /// to scope the styles to this component.
+    const STYLE_SCOPE: Option<&'static str> = None;
+
     /// Called when the component is mounted.
     /// Can be used to setup Effects or start async tasks.
+    ///
+    /// This is called right before the component is rendered, for components with a
+    /// [`on_init`](Self::on_init) this is after the init succeeded.
     fn on_mount(_ctx: E<Self>) {}
 
+    /// Called when the component is removed from the dom, such as when the closure that rendered
+    /// it re-runs or the custom element it is mounted in is disconnected.
+    ///
+    /// This is only called if [`on_mount`](Self::on_mount) was called. The state of the component
+    /// is dropped right after, stopping everything it started.
+    ///
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// use std::cell::Cell;
+    /// use std::rc::Rc;
+    ///
+    /// #[derive(Component)]
+    /// struct Viewer {
+    ///     #[natrix(skip)]
+    ///     viewers: Rc<Cell<u32>>,
+    /// }
+    ///
+    /// impl Component for Viewer {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         e::div()
+    ///     }
+    ///
+    ///     fn on_mount(ctx: E<Self>) {
+    ///         ctx.viewers.set(ctx.viewers.get() + 1);
+    ///     }
+    ///
+    ///     fn on_unmount(ctx: E<Self>) {
+    ///         ctx.viewers.set(ctx.viewers.get() - 1);
+    ///     }
+    /// }
+    /// ```
+    fn on_unmount(_ctx: E<Self>) {}
+
+    /// Async setup to finish before the component is rendered, such as loading configuration.
+    ///
+    /// Returning `None` (the default) renders the component immediately, otherwise
+    /// [`render_loading`](Self::render_loading) is shown until the future resolves.
+    /// If it resolves to `Ok` [`on_mount`](Self::on_mount) is called and the component is
+    /// rendered, on `Err` the returned element is rendered instead.
+    ///
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # use natrix::component::InitResult;
+    /// # use natrix::state::DeferredCtx;
+    /// # async fn load_name() -> Result<String, String> { Ok(String::new()) }
+    /// #[derive(Component)]
+    /// struct Profile {
+    ///     name: String,
+    /// }
+    ///
+    /// impl Component for Profile {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         e::h1().text(|ctx: R<Self>| ctx.name.clone())
+    ///     }
+    ///
+    ///     fn on_init(
+    ///         ctx: DeferredCtx<Self>,
+    ///     ) -> Option<impl Future<Output = InitResult<Self>> + 'static> {
+    ///         Some(async move {
+    ///             let Ok(name) = load_name().await else {
+    ///                 return Err("Failed to load".into_box());
+    ///             };
+    ///             if let Some(mut ctx) = ctx.borrow_mut() {
+    ///                 *ctx.name = name;
+    ///             }
+    ///             Ok(())
+    ///         })
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    fn on_init(
+        _ctx: DeferredCtx<Self>,
+    ) -> Option<impl Future<Output = InitResult<Self>> + 'static> {
+        None::<std::future::Ready<InitResult<Self>>>
+    }
+
+    /// Rendered in place of the component while [`on_init`](Self::on_init) is running.
+    /// Defaults to a empty placeholder.
+    #[must_use]
+    fn render_loading() -> impl Element<Self> {
+        Comment
+    }
+
+    /// Rendered in place of the component if its initial render fails, such as the browser
+    /// refusing to create one of its elements.
+    /// Defaults to a empty placeholder.
+    ///
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// #[derive(Component)]
+    /// struct Chart;
+    ///
+    /// impl Component for Chart {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         e::canvas().width(400).height(300)
+    ///     }
+    ///
+    ///     fn render_fallback() -> impl Element<Self> {
+    ///         e::p().text("Failed to render chart")
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    fn render_fallback() -> impl Element<Self> {
+        Comment
+    }
+
     /// Handle a incoming message
     /// Default implementation does nothing
     #[expect(
@@ -144,6 +270,211 @@ pub trait Component: ComponentBase {
             .into(),
         );
     }
+
+    /// Handle a error raised by a [`on_try`](crate::html_elements::HtmlElement::on_try) handler
+    /// (or [`State::raise_error`]) in this component or one of its sub components.
+    ///
+    /// Return `Err` to pass the error on to the parent component, which is the default.
+    /// Errors reaching the root component are logged to the console.
+    ///
+    /// # Errors
+    /// Returns the error back if this component did not handle it.
+    ///
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # use std::num::ParseIntError;
+    /// #[derive(Component)]
+    /// struct Form {
+    ///     error: Option<String>,
+    /// }
+    ///
+    /// impl Component for Form {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         e::div()
+    ///             .child(|ctx: R<Self>| ctx.error.clone())
+    ///             .child(e::button().on_try::<events::Click, _>(
+    ///                 |_ctx: E<Self>, _| -> Result<(), ParseIntError> {
+    ///                     "abc".parse::<u8>()?;
+    ///                     Ok(())
+    ///                 },
+    ///             ))
+    ///     }
+    ///
+    ///     fn handle_error(ctx: E<Self>, error: String) -> Result<(), String> {
+    ///         *ctx.error = Some(error);
+    ///         Ok(())
+    ///     }
+    /// }
+    /// ```
+    fn handle_error(_ctx: E<Self>, error: String) -> Result<(), String> {
+        Err(error)
+    }
+}
+
+/// The result of [`Component::on_init`], the error is rendered in place of the component.
+pub type InitResult<C> = Result<(), Box<dyn Element<C>>>;
+
+/// Run the lifecycle of a newly created component and render it
+fn render_lifecycle<I: Component>(
+    element: impl Element<I>,
+    ctx: &mut State<I>,
+    render_state: &mut RenderingState,
+) -> web_sys::Node {
+    if let Some(init) = I::on_init(ctx.deferred_borrow()) {
+        return render_after_init(element.into_box(), init, ctx, render_state);
+    }
+
+    call_on_mount(ctx);
+    let node = render_or_fallback(element.into_box(), ctx, render_state);
+    ctx.set_root_node(&node);
+    apply_style_scope::<I>(&node);
+    node
+}
+
+/// Call [`Component::on_mount`], marking the component as mounted so that
+/// [`Component::on_unmount`] is called once it is removed.
+fn call_on_mount<I: Component>(ctx: &mut State<I>) {
+    ctx.mounted = true;
+    I::on_mount(ctx);
+}
+
+/// Call [`Component::on_unmount`] if the component was mounted.
+fn unmount<I: Component>(state: &Rc<RefCell<State<I>>>) {
+    if let Ok(mut ctx) = state.try_borrow_mut() {
+        if std::mem::take(&mut ctx.mounted) {
+            I::on_unmount(&mut ctx);
+        }
+        return;
+    }
+
+    // The component is borrowed by one of its own handlers, such as one emitting a message that
+    // makes the parent remove it, so wait for it to finish.
+    let state = Rc::clone(state);
+    wasm_bindgen_futures::spawn_local(async move {
+        let Ok(mut ctx) = state.try_borrow_mut() else {
+            debug_assert!(false, "Component state borrowed across a yield point");
+            return;
+        };
+        if std::mem::take(&mut ctx.mounted) {
+            I::on_unmount(&mut ctx);
+        }
+    });
+}
+
+/// Keeps a sub component alive while it is in the dom, calling [`Component::on_unmount`] once
+/// dropped.
+struct MountedComponent<I: Component>(Rc<RefCell<State<I>>>);
+
+impl<I: Component> Drop for MountedComponent<I> {
+    fn drop(&mut self) {
+        unmount(&self.0);
+    }
+}
+
+/// Render the element, rendering [`Component::render_fallback`] instead if it produced a invalid
+/// tree.
+///
+/// Sub components track their failures in their own state, so a failed sub component only falls
+/// back itself.
+fn render_or_fallback<I: Component>(
+    element: Box<dyn Element<I>>,
+    ctx: &mut State<I>,
+    render_state: &mut RenderingState,
+) -> web_sys::Node {
+    let hooks_before = render_state.hooks.len();
+    let keep_alive_before = render_state.keep_alive.len();
+
+    ctx.render_failed = false;
+    let node = element.render_box(ctx, render_state);
+    if !std::mem::take(&mut ctx.render_failed) {
+        return node;
+    }
+
+    // Nothing of the failed render is shown, so its hooks and listeners are dropped
+    let failed_hooks = render_state.hooks.split_off(hooks_before);
+    ctx.drop_hooks(failed_hooks);
+    render_state.keep_alive.truncate(keep_alive_before);
+
+    I::render_fallback().render(ctx, render_state)
+}
+
+/// Render the loading placeholder, and replace it with the component once `init` resolves.
+fn render_after_init<I: Component>(
+    element: Box<dyn Element<I>>,
+    init: impl Future<Output = InitResult<I>> + 'static,
+    ctx: &mut State<I>,
+    render_state: &mut RenderingState,
+) -> web_sys::Node {
+    let pending: Rc<RefCell<Option<Box<dyn Element<I>>>>> = Rc::new(RefCell::new(None));
+    let pending_hook = Rc::clone(&pending);
+
+    // The hook is only re-run by the task below, once the init has resolved.
+    let (me, node) = ReactiveNode::create_initial(
+        Box::new(move |_ctx| match pending_hook.borrow_mut().take() {
+            Some(element) => InitRoot(element).into_box(),
+            None => I::render_loading().into_box(),
+        }),
+        ctx,
+    );
+    render_state.hooks.push(me);
+
+    let deferred = ctx.deferred_borrow();
+    wasm_bindgen_futures::spawn_local(async move {
+        let result = init.await;
+        let Some(mut ctx) = deferred.borrow_mut() else {
+            return;
+        };
+
+        let element = match result {
+            Ok(()) => {
+                call_on_mount(&mut ctx);
+                element
+            }
+            Err(error) => error,
+        };
+        *pending.borrow_mut() = Some(element);
+        ctx.run_hooks(vec![me]);
+    });
+
+    node
+}
+
+/// The element rendered once the `on_init` of a component resolves, which is the actual root of
+/// the component.
+struct InitRoot<I: Component>(Box<dyn Element<I>>);
+
+impl<I: Component> Element<I> for InitRoot<I> {
+    fn render_box(
+        self: Box<Self>,
+        ctx: &mut State<I>,
+        render_state: &mut RenderingState,
+    ) -> web_sys::Node {
+        let node = render_or_fallback(self.0, ctx, render_state);
+        ctx.set_root_node(&node);
+        apply_style_scope::<I>(&node);
+        node
+    }
+}
+
+/// Add the `STYLE_SCOPE` class of the component to its root element
+pub(crate) fn apply_style_scope<C: Component>(node: &web_sys::Node) {
+    let Some(scope) = C::STYLE_SCOPE else {
+        return;
+    };
+    let Some(element) = node.dyn_ref::<web_sys::Element>() else {
+        debug_assert!(
+            false,
+            "Component {} has a style scope, but its root is not an element",
+            std::any::type_name::<C>()
+        );
+        return;
+    };
+    debug_expect!(
+        element.class_list().add_1(scope),
+        "Failed to add style scope {scope}"
+    );
 }
 
 /// Type of the emitting message handler
@@ -270,15 +601,17 @@ where
 
         let mut borrow_data = data.borrow_mut();
         if let Some(handler) = self.message_handler.get() {
+            let handler: Rc<dyn Fn(E<P>, I::EmitMessage)> = Rc::from(handler);
             let (tx, rx) = futures_channel::mpsc::unbounded();
-            borrow_data.register_parent(tx);
+            borrow_data.register_parent(tx, sync_delivery(ctx, Rc::clone(&handler)));
 
-            ctx.spawn_listening_task(handler, rx);
+            ctx.spawn_listening_task(move |ctx, msg| handler(ctx, msg), rx);
         }
+        borrow_data.register_error_parent(ctx.error_forwarder());
+        borrow_data.register_context(ctx.shared_context());
         if let Some(receiver) = self.receiver.get() {
             borrow_data.spawn_recivier_task(receiver);
         }
-        I::on_mount(&mut borrow_data);
 
         let mut hooks = Vec::new();
 
@@ -286,15 +619,34 @@ where
             keep_alive: render_state.keep_alive,
             hooks: &mut hooks,
             parent_dep: HookKey::default(),
+            pinned: false,
         };
 
-        let node = element.render(&mut borrow_data, &mut state);
+        let node = render_lifecycle(element, &mut borrow_data, &mut state);
         drop(borrow_data);
-        render_state.keep_alive.push(Box::new(data));
+        render_state
+            .keep_alive
+            .push(Box::new(MountedComponent(data)));
         node
     }
 }
 
+/// Create the function passing messages to the parent `handler` immediately, returning the
+/// message if the parent is currently borrowed.
+fn sync_delivery<P: Component, M: 'static>(
+    parent: &mut State<P>,
+    handler: Rc<dyn Fn(E<P>, M)>,
+) -> SyncDelivery<M> {
+    let parent = parent.deferred_borrow();
+    Rc::new(move |msg| {
+        let Some(mut parent) = parent.try_borrow_mut() else {
+            return Some(msg);
+        };
+        handler(&mut parent, msg);
+        None
+    })
+}
+
 /// The result of rendering a component
 ///
 /// This should be kept in memory for as long as the component is in the dom.
@@ -310,6 +662,12 @@ pub struct RenderResult<C: Component> {
     keep_alive: Vec<Box<dyn SmallAny>>,
 }
 
+impl<C: Component> Drop for RenderResult<C> {
+    fn drop(&mut self) {
+        unmount(&self.data);
+    }
+}
+
 /// Mount the specified component at natrixses default location.
 /// This is what should be used when building with the natrix cli.
 ///
@@ -343,6 +701,66 @@ pub fn mount_at<C: Component>(component: C, target_id: &'static str) -> Result<(
     Ok(())
 }
 
+/// Mount the specified component at natrixses default location, with `context` available to
+/// every component in the tree through [`State::context`].
+///
+/// This is meant for app wide services such as a api client or configuration, which are shared by
+/// reference and never change. Use signals for data that changes.
+/// ```rust
+/// # use natrix::prelude::*;
+/// struct Config {
+///     api_url: &'static str,
+/// }
+///
+/// #[derive(Component)]
+/// struct App;
+///
+/// impl Component for App {
+///     # type EmitMessage = NoMessages;
+///     # type ReceiveMessage = NoMessages;
+///     fn render() -> impl Element<Self> {
+///         e::div().child(|ctx: R<Self>| {
+///             ctx.context::<Config>().map(|config| config.api_url)
+///         })
+///     }
+/// }
+///
+/// # fn run() {
+/// natrix::component::mount_with_context(App, Config { api_url: "https://example.com/api" });
+/// # }
+/// ```
+///
+/// **WARNING:** This method implicitly leaks the memory of the root component
+/// # Panics
+/// If the mount point is not found, which should never happen if using `natrix build`
+#[expect(
+    clippy::expect_used,
+    reason = "This will never happen if `natrix build` is used, and also happens early in the app lifecycle"
+)]
+pub fn mount_with_context<C: Component, T: 'static>(component: C, context: T) {
+    #[cfg(feature = "panic_hook")]
+    crate::panics::set_panic_hook();
+
+    mount_at_with_context(component, context, natrix_shared::MOUNT_POINT).expect("Failed to mount");
+}
+
+/// Mounts the component at the target id with the given context, see [`mount_with_context`].
+///
+/// **WARNING:** This method implicitly leaks the memory of the root component
+///
+/// # Errors
+/// If target mount point is not found.
+pub fn mount_at_with_context<C: Component, T: 'static>(
+    component: C,
+    context: T,
+    target_id: &'static str,
+) -> Result<(), &'static str> {
+    let result = render_component_with(component, target_id, Some(Rc::new(context)))?;
+
+    std::mem::forget(result);
+    Ok(())
+}
+
 /// Mounts the component at the target id
 /// Replacing the element with the component
 /// # Errors
@@ -351,11 +769,47 @@ pub fn render_component<C: Component>(
     component: C,
     target_id: &str,
 ) -> Result<RenderResult<C>, &'static str> {
+    render_component_with(component, target_id, None)
+}
+
+/// Mounts the component at the target id with the given app wide context
+/// # Errors
+/// If target mount point is not found.
+pub(crate) fn render_component_with<C: Component>(
+    component: C,
+    target_id: &str,
+    context: Option<Rc<dyn Any>>,
+) -> Result<RenderResult<C>, &'static str> {
+    let (result, node) = render_root(component, None, context);
+
+    let document = get_document();
+    let target = document
+        .get_element_by_id(target_id)
+        .ok_or("Failed to get mount point")?;
+    target
+        .replace_with_with_node_1(&node)
+        .map_err(|_| "Failed to replace mount point")?;
+
+    Ok(result)
+}
+
+/// Render the component as a root component, returning its node without inserting it.
+///
+/// If given, messages from `receiver` are passed to [`Component::handle_message`], and `context`
+/// is available to the whole tree through [`State::context`].
+pub(crate) fn render_root<C: Component>(
+    component: C,
+    receiver: Option<UnboundedReceiver<C::ReceiveMessage>>,
+    context: Option<Rc<dyn Any>>,
+) -> (RenderResult<C>, web_sys::Node) {
     let data = component.into_state();
     let element = C::render();
 
     let mut borrow_data = data.borrow_mut();
-    C::on_mount(&mut borrow_data);
+    borrow_data.register_context(context);
+    if let Some(receiver) = receiver {
+        borrow_data.spawn_recivier_task(receiver);
+    }
 
     let mut keep_alive = Vec::new();
     let mut hooks = Vec::new();
@@ -364,20 +818,13 @@ pub fn render_component<C: Component>(
         keep_alive: &mut keep_alive,
         hooks: &mut hooks,
         parent_dep: HookKey::default(),
+        pinned: false,
     };
-    let node = element.render(&mut borrow_data, &mut state);
-
-    let document = get_document();
-    let target = document
-        .get_element_by_id(target_id)
-        .ok_or("Failed to get mount point")?;
-    target
-        .replace_with_with_node_1(&node)
-        .map_err(|_| "Failed to replace mount point")?;
+    let node = render_lifecycle(element, &mut borrow_data, &mut state);
 
     drop(borrow_data);
 
-    Ok(RenderResult { data, keep_alive })
+    (RenderResult { data, keep_alive }, node)
 }
 
 impl ComponentData for () {
@@ -387,6 +834,9 @@ impl ComponentData for () {
     fn signals_mut(&mut self) -> Self::FieldRef<'_> {
         []
     }
+    fn field_names() -> &'static [&'static str] {
+        &[]
+    }
 
     fn pop_signals(&mut self) -> Self::SignalState {}
     fn set_signals(&mut self, _state: Self::SignalState) {}
@@ -458,7 +908,7 @@ impl<E: Element<()>, C: Component> Element<C> for NonReactive<E> {
 impl<A: ToAttribute<()>, C: Component> ToAttribute<C> for NonReactive<A> {
     fn apply_attribute(
         self: Box<Self>,
-        name: &'static str,
+        name: AttributeName,
         node: &web_sys::Element,
         _ctx: &mut State<C>,
         rendering_state: &mut RenderingState,
@@ -467,4 +917,8 @@ impl<A: ToAttribute<()>, C: Component> ToAttribute<C> for NonReactive<A> {
         let mut state = state.borrow_mut();
         Box::new(self.0).apply_attribute(name, node, &mut state, rendering_state);
     }
+
+    fn attribute_value(&self) -> Option<AttributeValue> {
+        self.0.attribute_value()
+    }
 }
@@ -0,0 +1,286 @@
+//! Reading and writing cookies through `document.cookie`.
+//!
+//! Names and values are percent encoded when written and decoded when read, so they can contain
+//! characters such as `;` and spaces.
+//! ```rust
+//! use std::time::Duration;
+//!
+//! use natrix::cookies::{self, CookieOptions, SameSite};
+//!
+//! # fn run() {
+//! cookies::set_cookie(
+//!     "consent",
+//!     "analytics",
+//!     &CookieOptions::new()
+//!         .max_age(Duration::from_secs(60 * 60 * 24 * 365))
+//!         .path("/")
+//!         .same_site(SameSite::Lax)
+//!         .secure(),
+//! );
+//! assert_eq!(cookies::get_cookie("consent").as_deref(), Some("analytics"));
+//! # }
+//! ```
+
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::time::Duration;
+
+use wasm_bindgen::JsCast;
+
+use crate::utils::debug_expect;
+
+/// The `SameSite` attribute of a cookie, controlling if it is sent with cross site requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SameSite {
+    /// Only sent with requests from the same site
+    Strict,
+    /// Also sent when navigating to the site from another site
+    Lax,
+    /// Sent with all requests, browsers require the cookie to be `secure` for this
+    None,
+}
+
+impl SameSite {
+    /// The value of the attribute
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Strict => "Strict",
+            Self::Lax => "Lax",
+            Self::None => "None",
+        }
+    }
+}
+
+/// The attributes of a cookie written with [`set_cookie`].
+///
+/// Without a `max_age` the cookie is removed when the browser session ends, and without a `path`
+/// it is only sent for the current path and the paths below it.
+/// ```rust
+/// # use std::time::Duration;
+/// # use natrix::cookies::{CookieOptions, SameSite};
+/// let options = CookieOptions::new()
+///     .max_age(Duration::from_secs(3600))
+///     .path("/")
+///     .same_site(SameSite::Strict)
+///     .secure();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CookieOptions {
+    /// How long until the cookie expires
+    max_age: Option<Duration>,
+    /// The path the cookie is sent for
+    path: Option<Cow<'static, str>>,
+    /// Only send the cookie over https
+    secure: bool,
+    /// The `SameSite` attribute
+    same_site: Option<SameSite>,
+}
+
+impl CookieOptions {
+    /// The default options, a session cookie for the current path
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expire the cookie after `max_age`, a zero duration removes the cookie.
+    #[must_use]
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Send the cookie for `path` and the paths below it, usually `"/"` for the whole site.
+    ///
+    /// `;` and control characters are percent encoded, as they would otherwise end the attribute.
+    #[must_use]
+    pub fn path(mut self, path: impl Into<Cow<'static, str>>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Only send the cookie over https
+    #[must_use]
+    pub fn secure(mut self) -> Self {
+        self.secure = true;
+        self
+    }
+
+    /// Set the `SameSite` attribute
+    #[must_use]
+    pub fn same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = Some(same_site);
+        self
+    }
+}
+
+/// The document as a `HtmlDocument`, which is what has the cookie api
+fn html_document() -> Option<web_sys::HtmlDocument> {
+    let document = crate::get_document()
+        .dyn_into::<web_sys::HtmlDocument>()
+        .ok();
+    debug_assert!(document.is_some(), "Document is not a html document");
+    document
+}
+
+/// Get the value of the cookie `name`, or `None` if it isnt set (or isnt readable from js, such
+/// as `HttpOnly` cookies).
+#[must_use]
+pub fn get_cookie(name: &str) -> Option<String> {
+    let cookies = html_document()?.cookie().ok()?;
+    find_cookie(&cookies, name)
+}
+
+/// Set the cookie `name` to `value` with the given attributes.
+///
+/// A cookie with the same name and path is replaced.
+pub fn set_cookie(name: &str, value: &str, options: &CookieOptions) {
+    let Some(document) = html_document() else {
+        return;
+    };
+    debug_expect!(
+        document.set_cookie(&format_cookie(name, value, options)),
+        "Failed to set cookie"
+    );
+}
+
+/// Find the decoded value of `name` in a `document.cookie` string, which has the form
+/// `name=value; other=value`.
+fn find_cookie(cookies: &str, name: &str) -> Option<String> {
+    let name = encode(name);
+    cookies.split(';').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        (key.trim() == name).then(|| decode(value.trim()))
+    })
+}
+
+/// Format the string to assign to `document.cookie`
+fn format_cookie(name: &str, value: &str, options: &CookieOptions) -> String {
+    let mut parts = vec![format!("{}={}", encode(name), encode(value))];
+    if let Some(max_age) = options.max_age {
+        parts.push(format!("max-age={}", max_age.as_secs()));
+    }
+    if let Some(path) = &options.path {
+        let path = percent_encode(path, |char| char != ';' && !char.is_control());
+        parts.push(format!("path={path}"));
+    }
+    if let Some(same_site) = options.same_site {
+        parts.push(format!("samesite={}", same_site.as_str()));
+    }
+    if options.secure {
+        parts.push("secure".to_owned());
+    }
+    parts.join("; ")
+}
+
+/// Percent encode a cookie name or value, the same as `encodeURIComponent`
+fn encode(text: &str) -> String {
+    percent_encode(text, |char| {
+        char.is_ascii_alphanumeric() || "-_.!~*'()".contains(char)
+    })
+}
+
+/// Percent encode the utf-8 bytes of the characters of `text` not accepted by `keep`
+fn percent_encode(text: &str, keep: impl Fn(char) -> bool) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for char in text.chars() {
+        if keep(char) {
+            encoded.push(char);
+        } else {
+            for byte in char.encode_utf8(&mut [0; 4]).bytes() {
+                let _ = write!(encoded, "%{byte:02X}");
+            }
+        }
+    }
+    encoded
+}
+
+/// Decode a percent encoded cookie value, leaving it as is if it isnt valid percent encoding
+/// (such as cookies set by the server)
+fn decode(text: &str) -> String {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte != b'%' {
+            bytes.push(byte);
+            rest = tail;
+            continue;
+        }
+
+        let decoded = tail
+            .get(..2)
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        let Some(decoded) = decoded else {
+            return text.to_owned();
+        };
+        bytes.push(decoded);
+        rest = tail.get(2..).unwrap_or_default();
+    }
+    String::from_utf8(bytes).unwrap_or_else(|_| text.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{CookieOptions, SameSite, decode, encode, find_cookie, format_cookie};
+
+    #[test]
+    fn encode_round_trips() {
+        let text = "a b;c=d,é%";
+        assert_eq!(encode(text), "a%20b%3Bc%3Dd%2C%C3%A9%25");
+        assert_eq!(decode(&encode(text)), text);
+    }
+
+    #[test]
+    fn invalid_encoding_is_kept() {
+        assert_eq!(decode("100%"), "100%");
+        assert_eq!(decode("%zz"), "%zz");
+        assert_eq!(decode("%+1"), "%+1");
+        assert_eq!(decode("%FF"), "%FF");
+    }
+
+    #[test]
+    fn find_cookie_by_name() {
+        let cookies = "theme=dark; user%20name=a%3Bb; empty=";
+        assert_eq!(find_cookie(cookies, "theme").as_deref(), Some("dark"));
+        assert_eq!(find_cookie(cookies, "user name").as_deref(), Some("a;b"));
+        assert_eq!(find_cookie(cookies, "empty").as_deref(), Some(""));
+        assert_eq!(find_cookie(cookies, "missing"), None);
+        assert_eq!(find_cookie("", "theme"), None);
+    }
+
+    #[test]
+    fn format_cookie_attributes() {
+        assert_eq!(
+            format_cookie("a b", "c;d", &CookieOptions::new()),
+            "a%20b=c%3Bd"
+        );
+        assert_eq!(
+            format_cookie(
+                "theme",
+                "dark",
+                &CookieOptions::new()
+                    .max_age(Duration::from_mins(1))
+                    .path("/")
+                    .same_site(SameSite::Lax)
+                    .secure()
+            ),
+            "theme=dark; max-age=60; path=/; samesite=Lax; secure"
+        );
+    }
+
+    #[test]
+    fn format_cookie_encodes_path() {
+        assert_eq!(
+            format_cookie(
+                "theme",
+                "dark",
+                &CookieOptions::new().path("/a;domain=evil.com\n/b c")
+            ),
+            "theme=dark; path=/a%3Bdomain=evil.com%0A/b c"
+        );
+    }
+}
@@ -0,0 +1,196 @@
+//! Registering components as custom elements
+//!
+//! This allows natrix components to be used from plain html, or from other frameworks.
+//!
+//! ```rust,no_run
+//! # use natrix::prelude::*;
+//! use natrix::custom_element::{CustomElement, register_custom_element};
+//!
+//! #[derive(Component)]
+//! struct Greeting {
+//!     name: String,
+//! }
+//!
+//! impl Component for Greeting {
+//!     type EmitMessage = NoMessages;
+//!     type ReceiveMessage = String;
+//!     fn render() -> impl Element<Self> {
+//!         e::h1().text(|ctx: R<Self>| format!("Hello {}", *ctx.name))
+//!     }
+//!
+//!     fn handle_message(ctx: E<Self>, msg: Self::ReceiveMessage) {
+//!         *ctx.name = msg;
+//!     }
+//! }
+//!
+//! impl CustomElement for Greeting {
+//!     const OBSERVED_ATTRIBUTES: &'static [&'static str] = &["name"];
+//!
+//!     fn create() -> Self {
+//!         Self {
+//!             name: String::from("World"),
+//!         }
+//!     }
+//!
+//!     fn attribute_changed(_name: &str, value: Option<String>) -> Option<String> {
+//!         Some(value.unwrap_or_else(|| String::from("World")))
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let _ = register_custom_element::<Greeting>("natrix-greeting");
+//! }
+//! ```
+//! After which `<natrix-greeting name="Natrix"></natrix-greeting>` can be used in html.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use futures_channel::mpsc::UnboundedSender;
+use wasm_bindgen::JsValue;
+use wasm_bindgen::prelude::Closure;
+
+use crate::component::{Component, RenderResult, render_root};
+use crate::utils::debug_expect;
+
+/// A component that can be registered as a custom element with [`register_custom_element`].
+pub trait CustomElement: Component {
+    /// The attributes passed to [`attribute_changed`](Self::attribute_changed)
+    const OBSERVED_ATTRIBUTES: &'static [&'static str] = &[];
+
+    /// Create the component for a newly connected element
+    fn create() -> Self;
+
+    /// Convert a change of one of the [`OBSERVED_ATTRIBUTES`](Self::OBSERVED_ATTRIBUTES) to
+    /// a message for the component, `value` is `None` if the attribute was removed.
+    ///
+    /// This is also called for the attributes already present when the element is connected.
+    #[must_use]
+    fn attribute_changed(_name: &str, _value: Option<String>) -> Option<Self::ReceiveMessage> {
+        None
+    }
+}
+
+/// A mounted instance of a custom element
+struct Instance<C: Component> {
+    /// The custom element the component is mounted in
+    host: web_sys::Element,
+    /// The root node of the component
+    node: web_sys::Node,
+    /// Sender for the attribute messages
+    sender: UnboundedSender<C::ReceiveMessage>,
+    /// Keeps the component alive
+    #[expect(dead_code, reason = "This is only used to keep the component alive")]
+    result: RenderResult<C>,
+}
+
+impl<C: Component> Instance<C> {
+    /// Send the message to the component
+    fn send(&self, message: C::ReceiveMessage) {
+        debug_expect!(
+            self.sender.unbounded_send(message),
+            "Failed to send attribute message to component"
+        );
+    }
+}
+
+/// The mounted instances of a given custom element
+type Instances<C> = Rc<RefCell<Vec<Instance<C>>>>;
+
+/// Register the component as a custom element with the given tag name.
+///
+/// The component is mounted in the light dom of the element when it is connected, and unmounted
+/// when it is disconnected, as such moving the element will recreate the component.
+///
+/// # Errors
+/// The exception thrown by `customElements.define`, a `SyntaxError` `DOMException` if the name is
+/// not a valid custom element name, or a `NotSupportedError` `DOMException` if it is already
+/// registered.
+pub fn register_custom_element<C: CustomElement>(name: &str) -> Result<(), JsValue> {
+    let instances: Instances<C> = Rc::new(RefCell::new(Vec::new()));
+
+    let observed: web_sys::js_sys::Array = C::OBSERVED_ATTRIBUTES
+        .iter()
+        .copied()
+        .map(JsValue::from_str)
+        .collect();
+
+    let connect = {
+        let instances = Rc::clone(&instances);
+        Closure::<dyn Fn(web_sys::Element)>::new(move |host| connect::<C>(&instances, host))
+    };
+    let disconnect = {
+        let instances = Rc::clone(&instances);
+        Closure::<dyn Fn(web_sys::Element)>::new(move |host| disconnect(&instances, &host))
+    };
+    let changed = Closure::<dyn Fn(web_sys::Element, String, Option<String>)>::new(
+        move |host, attribute: String, value| {
+            let instances = instances.borrow();
+            let Some(instance) = instances.iter().find(|instance| instance.host == host) else {
+                // Attributes are read in `connect` for elements not yet connected
+                return;
+            };
+            if let Some(message) = C::attribute_changed(&attribute, value) {
+                instance.send(message);
+            }
+        },
+    );
+
+    // The callbacks live for as long as the element is registered, i.e forever.
+    natrix_js_shims::define_custom_element(
+        name,
+        observed.into(),
+        connect.into_js_value(),
+        disconnect.into_js_value(),
+        changed.into_js_value(),
+    )
+}
+
+/// Mount a new component in the connected element
+fn connect<C: CustomElement>(instances: &Instances<C>, host: web_sys::Element) {
+    crate::return_if_panic!();
+
+    let (sender, receiver) = futures_channel::mpsc::unbounded();
+    let (result, node) = render_root(C::create(), Some(receiver), None);
+    let instance = Instance {
+        host,
+        node,
+        sender,
+        result,
+    };
+
+    for attribute in C::OBSERVED_ATTRIBUTES {
+        if let Some(message) = instance
+            .host
+            .get_attribute(attribute)
+            .and_then(|value| C::attribute_changed(attribute, Some(value)))
+        {
+            instance.send(message);
+        }
+    }
+
+    // Appending may connect nested custom elements, so this is done before borrowing `instances`
+    debug_expect!(
+        instance.host.append_child(&instance.node),
+        "Failed to mount custom element component"
+    );
+    instances.borrow_mut().push(instance);
+}
+
+/// Unmount the component of the disconnected element
+fn disconnect<C: Component>(instances: &Instances<C>, host: &web_sys::Element) {
+    crate::return_if_panic!();
+
+    let mut borrow = instances.borrow_mut();
+    let Some(index) = borrow.iter().position(|instance| instance.host == *host) else {
+        return;
+    };
+    let instance = borrow.swap_remove(index);
+    // Dropping the component might disconnect nested custom elements
+    drop(borrow);
+
+    debug_expect!(
+        instance.host.remove_child(&instance.node),
+        "Failed to unmount custom element component"
+    );
+}
@@ -1,9 +1,14 @@
 //! Implementation of the `Element` trait for various abstract types.
 
+use std::any::Any;
+use std::hash::Hash;
+use std::rc::Rc;
+
 use crate::component::Component;
 use crate::signal::RenderingState;
 use crate::state::State;
 use crate::type_macros;
+use crate::utils::debug_expect;
 
 /// An `Element` is anything that can produce a DOM node.
 /// The most common examples include `HtmlElement` and types like `String`.
@@ -53,6 +58,58 @@ pub trait Element<C: Component>: 'static {
         Box::new(self).render_box(ctx, render_state)
     }
 
+    /// The text this element renders to if it is plain static text.
+    ///
+    /// Parents use this to set the text of a element in one call instead of creating a text node
+    /// per child.
+    #[doc(hidden)]
+    fn static_text(&self) -> Option<&str> {
+        None
+    }
+
+    /// The key given to this element with [`with_key`](Self::with_key).
+    #[doc(hidden)]
+    fn key(&self) -> Option<Rc<dyn ElementKey>> {
+        None
+    }
+
+    /// Give this element a identity, so that when a reactive closure re-runs and returns a
+    /// element with the same key the existing dom node is kept instead of being replaced.
+    ///
+    /// Reactive parts of the kept element still update as normal, but anything else about the
+    /// newly returned element is ignored. So the key should change whenever the static parts do.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     logged_in: bool,
+    /// #     clicks: u32,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::div().child(|ctx: R<Self>| {
+    ///     // Re-runs on every click, but the input is only recreated when `logged_in` changes
+    ///     let _ = *ctx.clicks;
+    ///     if *ctx.logged_in {
+    ///         e::input().with_key("search").boxed()
+    ///     } else {
+    ///         e::button().text("Log in").with_key("login").boxed()
+    ///     }
+    /// })
+    /// # }}
+    /// ```
+    fn with_key<K: Hash + Eq + 'static>(self, key: K) -> Keyed<Self>
+    where
+        Self: Sized,
+    {
+        Keyed {
+            key: Rc::new(key),
+            element: self,
+        }
+    }
+
     /// Wrap this element in a `Box`.
     /// This lets you easially return different element types from the same function.
     fn into_box(self) -> Box<dyn Element<C>>
@@ -61,6 +118,27 @@ pub trait Element<C: Component>: 'static {
     {
         Box::new(self)
     }
+
+    /// Wrap this element in a `Box`, alias for [`into_box`](Self::into_box).
+    ///
+    /// `Box<dyn Element<C>>` is itself a element, so this can be used to store different element
+    /// types in the same collection.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// fn toolbar<C: Component>(compact: bool) -> impl Element<C> {
+    ///     let mut items: Vec<Box<dyn Element<C>>> = vec![e::button().text("Save").boxed()];
+    ///     if !compact {
+    ///         items.push("Unsaved changes".boxed());
+    ///     }
+    ///     e::div().child(items)
+    /// }
+    /// ```
+    fn boxed(self) -> Box<dyn Element<C>>
+    where
+        Self: Sized,
+    {
+        self.into_box()
+    }
 }
 
 impl<C: Component> Element<C> for Box<dyn Element<C>> {
@@ -71,6 +149,14 @@ impl<C: Component> Element<C> for Box<dyn Element<C>> {
     ) -> web_sys::Node {
         (*self).render_box(ctx, render_state)
     }
+
+    fn static_text(&self) -> Option<&str> {
+        (**self).static_text()
+    }
+
+    fn key(&self) -> Option<Rc<dyn ElementKey>> {
+        (**self).key()
+    }
 }
 
 impl<C: Component> Element<C> for web_sys::Node {
@@ -83,17 +169,60 @@ impl<C: Component> Element<C> for web_sys::Node {
     }
 }
 
+/// The key of a [`Keyed`] element, compared with the key of the previous render.
+#[doc(hidden)]
+pub trait ElementKey: Any {
+    /// Is this the same key as `other`
+    fn key_eq(&self, other: &dyn ElementKey) -> bool;
+
+    /// Upcast to `Any`
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<K: Hash + Eq + 'static> ElementKey for K {
+    fn key_eq(&self, other: &dyn ElementKey) -> bool {
+        other.as_any().downcast_ref::<K>() == Some(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// A element with a identity, created by [`Element::with_key`].
+pub struct Keyed<E> {
+    /// The key of the element
+    key: Rc<dyn ElementKey>,
+    /// The element
+    element: E,
+}
+
+impl<C: Component, E: Element<C>> Element<C> for Keyed<E> {
+    fn render_box(
+        self: Box<Self>,
+        ctx: &mut State<C>,
+        render_state: &mut RenderingState,
+    ) -> web_sys::Node {
+        self.element.render(ctx, render_state)
+    }
+
+    fn key(&self) -> Option<Rc<dyn ElementKey>> {
+        Some(Rc::clone(&self.key))
+    }
+}
+
 /// A simple Dom comment, used as a placeholder and replacement target.
 pub struct Comment;
 
 impl<C: Component> Element<C> for Comment {
     fn render_box(
         self: Box<Self>,
-        _ctx: &mut State<C>,
+        ctx: &mut State<C>,
         _render_state: &mut RenderingState,
     ) -> web_sys::Node {
         let Ok(node) = web_sys::Comment::new() else {
             debug_assert!(false, "Failed to create comment node");
+            ctx.render_failed = true;
             return generate_fallback_node();
         };
 
@@ -101,6 +230,17 @@ impl<C: Component> Element<C> for Comment {
     }
 }
 
+/// Renders nothing (a empty comment node), so branches that have nothing to show can return `()`.
+impl<C: Component> Element<C> for () {
+    fn render_box(
+        self: Box<Self>,
+        ctx: &mut State<C>,
+        render_state: &mut RenderingState,
+    ) -> web_sys::Node {
+        Element::<C>::render(Comment, ctx, render_state)
+    }
+}
+
 impl<T: Element<C>, C: Component> Element<C> for Option<T> {
     fn render_box(
         self: Box<Self>,
@@ -127,6 +267,76 @@ impl<T: Element<C>, E: Element<C>, C: Component> Element<C> for Result<T, E> {
     }
 }
 
+/// One of two elements, usually produced by [`e::either`](crate::html_elements::either).
+///
+/// This renders the same as a `Result`, but does not imply either branch is a error.
+pub enum Branch<L, R> {
+    /// The first branch
+    Left(L),
+    /// The second branch
+    Right(R),
+}
+
+impl<L: Element<C>, R: Element<C>, C: Component> Element<C> for Branch<L, R> {
+    fn render_box(
+        self: Box<Self>,
+        ctx: &mut State<C>,
+        render_state: &mut RenderingState,
+    ) -> web_sys::Node {
+        match *self {
+            Branch::Left(element) => element.render(ctx, render_state),
+            Branch::Right(element) => element.render(ctx, render_state),
+        }
+    }
+}
+
+/// Render the given elements in order into a `DocumentFragment`
+pub(crate) fn render_fragment<T: Element<C>, C: Component>(
+    elements: impl IntoIterator<Item = T>,
+    ctx: &mut State<C>,
+    render_state: &mut RenderingState,
+) -> web_sys::Node {
+    let fragment = crate::get_document().create_document_fragment();
+    for element in elements {
+        let node = element.render(ctx, render_state);
+        debug_expect!(
+            fragment.append_child(&node),
+            "Failed to append child to fragment"
+        );
+    }
+    fragment.into()
+}
+
+impl<T: Element<C>, C: Component> Element<C> for Vec<T> {
+    fn render_box(
+        self: Box<Self>,
+        ctx: &mut State<C>,
+        render_state: &mut RenderingState,
+    ) -> web_sys::Node {
+        render_fragment(*self, ctx, render_state)
+    }
+}
+
+/// This avoids the heap allocation of a `Vec` for small static groups of elements.
+impl<T: Element<C>, const N: usize, C: Component> Element<C> for [T; N] {
+    fn render_box(
+        self: Box<Self>,
+        ctx: &mut State<C>,
+        render_state: &mut RenderingState,
+    ) -> web_sys::Node {
+        render_fragment(*self, ctx, render_state)
+    }
+}
+
+/// Create a text node with the given content, shared by the string and number elements
+pub(crate) fn text_node(content: &str) -> web_sys::Node {
+    let Ok(text) = web_sys::Text::new_with_data(content) else {
+        debug_assert!(false, "Failed to make text");
+        return generate_fallback_node();
+    };
+    text.into()
+}
+
 /// Generate a Element implementation for a type that can be converted to `&str`
 macro_rules! string_element {
     ($t:ty) => {
@@ -136,9 +346,11 @@ macro_rules! string_element {
                 _ctx: &mut State<C>,
                 _render_state: &mut RenderingState,
             ) -> web_sys::Node {
-                let text = web_sys::Text::new().expect("Failed to make text");
-                text.set_text_content(Some(&self));
-                text.into()
+                text_node(&self)
+            }
+
+            fn static_text(&self) -> Option<&str> {
+                Some(self)
             }
         }
     };
@@ -161,11 +373,7 @@ macro_rules! int_element {
                 _render_state: &mut RenderingState,
             ) -> web_sys::Node {
                 let mut buffer = $fmt::Buffer::new();
-                let result = buffer.format(*self);
-
-                let text = web_sys::Text::new().expect("Failed to make text");
-                text.set_text_content(Some(result));
-                text.into()
+                text_node(buffer.format(*self))
             }
         }
     };
@@ -173,6 +381,40 @@ macro_rules! int_element {
 
 type_macros::numerics!(int_element);
 
+/// Generate a implementation of `Element` for a `NonZero*` integer type, formatting the inner
+/// value with `itoa`.
+macro_rules! nonzero_element {
+    ($($T:ident),*) => {
+        $(
+            impl<C: Component> Element<C> for std::num::$T {
+                fn render_box(
+                    self: Box<Self>,
+                    _ctx: &mut State<C>,
+                    _render_state: &mut RenderingState,
+                ) -> web_sys::Node {
+                    let mut buffer = itoa::Buffer::new();
+                    text_node(buffer.format(self.get()))
+                }
+            }
+        )*
+    };
+}
+
+nonzero_element!(
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize
+);
+
 #[cfg(feature = "either")]
 /// Impl of `Element` on `Either`
 mod either_element {
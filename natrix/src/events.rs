@@ -3,7 +3,7 @@
 use wasm_bindgen::JsCast;
 
 /// Trait for converting a struct to needed event info.
-pub(crate) trait Event {
+pub trait Event {
     /// The js event the handler gets
     type JsEvent: JsCast;
     /// The actual name
@@ -30,6 +30,7 @@ impl_event!(AnimationStart => "animationstart", AnimationEvent);
 impl_event!(AuxClick => "auxclick", PointerEvent);
 impl_event!(BeforeInput => "beforeinput", InputEvent);
 impl_event!(Blur => "blur", FocusEvent);
+impl_event!(Change => "change", Event);
 impl_event!(Click => "click", PointerEvent);
 impl_event!(CompositionEnd => "compositionend", CompositionEvent);
 impl_event!(CompositionStart => "compositionstart", CompositionEvent);
@@ -39,6 +40,7 @@ impl_event!(ContextMenu => "contextmenu", PointerEvent);
 impl_event!(Copy => "copy", ClipboardEvent);
 impl_event!(Cut => "cut", ClipboardEvent);
 impl_event!(DoubleClick => "dblclick", MouseEvent);
+impl_event!(Error => "error", Event);
 impl_event!(Focus => "focus", FocusEvent);
 impl_event!(FocusIn => "focusin", FocusEvent);
 impl_event!(FocusOut => "focusout", FocusEvent);
@@ -48,6 +50,7 @@ impl_event!(GotPointerCapture => "gotpointercapture", PointerEvent);
 impl_event!(Input => "input", InputEvent);
 impl_event!(KeyDown => "keydown", KeyboardEvent);
 impl_event!(KeyUp => "keyup", KeyboardEvent);
+impl_event!(Load => "load", Event);
 impl_event!(LostPointerCapture => "lostpointercapture", PointerEvent);
 impl_event!(MouseDown => "mousedown", MouseEvent);
 impl_event!(MouseEnter => "mouseenter", MouseEvent);
@@ -68,6 +71,7 @@ impl_event!(PointerUp => "pointerup", PointerEvent);
 impl_event!(Scroll => "scroll", Event);
 impl_event!(ScrollEnd => "scrollend", Event);
 impl_event!(SecurityPolicyViolation => "securitypolicyviolation", Event);
+impl_event!(Submit => "submit", SubmitEvent);
 impl_event!(TouchCancel => "touchcancel", TouchEvent);
 impl_event!(TouchEnd => "touchend", TouchEvent);
 impl_event!(TouchMove => "touchmove", TouchEvent);
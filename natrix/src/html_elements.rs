@@ -14,19 +14,26 @@
 //! # ;
 //! ```
 
+use std::any::Any;
 use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::fmt::{Display, Write};
+use std::rc::Rc;
+use std::time::Duration;
 
 use wasm_bindgen::prelude::Closure;
-use wasm_bindgen::{JsCast, intern};
+use wasm_bindgen::{JsCast, JsValue, intern};
 
-use crate::callbacks::EventHandler;
+use crate::async_utils::StreamElement;
+use crate::callbacks::{EventHandler, PreserveFocus};
 use crate::component::Component;
-use crate::element::{Element, generate_fallback_node};
+use crate::element::{Branch, Element, generate_fallback_node, render_fragment};
 use crate::events::Event;
+use crate::render_callbacks::{ReactiveClassToggle, SimpleReactive};
 use crate::signal::RenderingState;
-use crate::state::{DeferredCtx, State};
+use crate::state::{DeferredCtx, RenderCtx, State};
 use crate::utils::debug_expect;
-use crate::{get_document, type_macros};
+use crate::{events, get_document, navigation, type_macros};
 
 /// A trait for using a arbitrary type as a attribute value.
 #[diagnostic::on_unimplemented(
@@ -40,11 +47,86 @@ pub trait ToAttribute<C: Component>: 'static {
     /// conditionally remove the attribute
     fn apply_attribute(
         self: Box<Self>,
-        name: &'static str,
+        name: AttributeName,
         node: &web_sys::Element,
         ctx: &mut State<C>,
         rendering_state: &mut RenderingState,
     );
+
+    /// The value `apply_attribute` would leave the attribute at.
+    ///
+    /// Reactive attributes use this to skip the dom write if the value didnt change,
+    /// `None` means the value cant be described this way and should always be applied.
+    #[doc(hidden)]
+    fn attribute_value(&self) -> Option<AttributeValue> {
+        None
+    }
+}
+
+/// The name of a attribute, and the namespace it is in.
+///
+/// Attributes set with [`HtmlElement::attr`] have no namespace, see [`HtmlElement::attr_ns`] for
+/// setting namespaced attributes such as `xlink:href`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AttributeName {
+    /// The namespace of the attribute, `None` for regular attributes
+    pub namespace: Option<&'static str>,
+    /// The qualified name of the attribute, including the prefix if any (such as `xlink:href`)
+    pub name: &'static str,
+}
+
+impl AttributeName {
+    /// Set the attribute on the node
+    pub fn set(self, node: &web_sys::Element, value: &str) {
+        let result = match self.namespace {
+            Some(namespace) => node.set_attribute_ns(Some(namespace), self.name, value),
+            None => node.set_attribute(self.name, value),
+        };
+        debug_expect!(result, "Failed to set attribute {self}");
+    }
+
+    /// Remove the attribute from the node
+    pub fn remove(self, node: &web_sys::Element) {
+        let result = match self.namespace {
+            // `removeAttributeNS` takes the local name, without the prefix
+            Some(namespace) => {
+                let local_name = self
+                    .name
+                    .rsplit_once(':')
+                    .map_or(self.name, |(_, local)| local);
+                node.remove_attribute_ns(Some(namespace), local_name)
+            }
+            None => node.remove_attribute(self.name),
+        };
+        debug_expect!(result, "Failed to remove attribute {self}");
+    }
+}
+
+impl From<&'static str> for AttributeName {
+    fn from(name: &'static str) -> Self {
+        Self {
+            namespace: None,
+            name,
+        }
+    }
+}
+
+impl Display for AttributeName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.namespace {
+            Some(namespace) => write!(f, "{} ({namespace})", self.name),
+            None => f.write_str(self.name),
+        }
+    }
+}
+
+/// The resulting state of a attribute, see `ToAttribute::attribute_value`
+#[derive(PartialEq, Eq)]
+pub(crate) enum AttributeValue {
+    /// The attribute is set to this value
+    Set(String),
+    /// The attribute is removed
+    Removed,
 }
 
 /// generate a `ToAttribute` implementation for a string type
@@ -53,15 +135,16 @@ macro_rules! attribute_string {
         impl<C: Component> ToAttribute<C> for $type {
             fn apply_attribute(
                 self: Box<Self>,
-                name: &'static str,
+                name: AttributeName,
                 node: &web_sys::Element,
                 _ctx: &mut State<C>,
                 _rendering_state: &mut RenderingState,
             ) {
-                debug_expect!(
-                    node.set_attribute(name, &self),
-                    "Failed to set attribute {name}"
-                );
+                name.set(node, &self);
+            }
+
+            fn attribute_value(&self) -> Option<AttributeValue> {
+                Some(AttributeValue::Set(String::from(&**self)))
             }
         }
     };
@@ -75,7 +158,7 @@ macro_rules! attribute_int {
         impl<C: Component> ToAttribute<C> for $T {
             fn apply_attribute(
                 self: Box<Self>,
-                name: &'static str,
+                name: AttributeName,
                 node: &web_sys::Element,
                 _ctx: &mut State<C>,
                 _rendering_state: &mut RenderingState,
@@ -83,10 +166,12 @@ macro_rules! attribute_int {
                 let mut buffer = $fmt::Buffer::new();
                 let result = buffer.format(*self);
 
-                debug_expect!(
-                    node.set_attribute(name, result),
-                    "Failed to set attribute {name}"
-                );
+                name.set(node, result);
+            }
+
+            fn attribute_value(&self) -> Option<AttributeValue> {
+                let mut buffer = $fmt::Buffer::new();
+                Some(AttributeValue::Set(String::from(buffer.format(*self))))
             }
         }
     };
@@ -97,21 +182,23 @@ type_macros::numerics!(attribute_int);
 impl<C: Component> ToAttribute<C> for bool {
     fn apply_attribute(
         self: Box<Self>,
-        name: &'static str,
+        name: AttributeName,
         node: &web_sys::Element,
         _ctx: &mut State<C>,
         _rendering_state: &mut RenderingState,
     ) {
         if *self {
-            debug_expect!(
-                node.set_attribute(name, ""),
-                "Failed to set attribute {name}"
-            );
+            name.set(node, "");
         } else {
-            debug_expect!(
-                node.remove_attribute(name),
-                "Failed to remove attribute {name}"
-            );
+            name.remove(node);
+        }
+    }
+
+    fn attribute_value(&self) -> Option<AttributeValue> {
+        if *self {
+            Some(AttributeValue::Set(String::new()))
+        } else {
+            Some(AttributeValue::Removed)
         }
     }
 }
@@ -119,7 +206,7 @@ impl<C: Component> ToAttribute<C> for bool {
 impl<C: Component, T: ToAttribute<C>> ToAttribute<C> for Option<T> {
     fn apply_attribute(
         self: Box<Self>,
-        name: &'static str,
+        name: AttributeName,
         node: &web_sys::Element,
         ctx: &mut State<C>,
         rendering_state: &mut RenderingState,
@@ -127,17 +214,21 @@ impl<C: Component, T: ToAttribute<C>> ToAttribute<C> for Option<T> {
         if let Some(inner) = *self {
             Box::new(inner).apply_attribute(name, node, ctx, rendering_state);
         } else {
-            debug_expect!(
-                node.remove_attribute(name),
-                "Failed to remove attribute {name}"
-            );
+            name.remove(node);
+        }
+    }
+
+    fn attribute_value(&self) -> Option<AttributeValue> {
+        match self {
+            Some(inner) => inner.attribute_value(),
+            None => Some(AttributeValue::Removed),
         }
     }
 }
 impl<C: Component, T: ToAttribute<C>, E: ToAttribute<C>> ToAttribute<C> for Result<T, E> {
     fn apply_attribute(
         self: Box<Self>,
-        name: &'static str,
+        name: AttributeName,
         node: &web_sys::Element,
         ctx: &mut State<C>,
         rendering_state: &mut RenderingState,
@@ -147,21 +238,495 @@ impl<C: Component, T: ToAttribute<C>, E: ToAttribute<C>> ToAttribute<C> for Resu
             Err(inner) => Box::new(inner).apply_attribute(name, node, ctx, rendering_state),
         }
     }
+
+    fn attribute_value(&self) -> Option<AttributeValue> {
+        match self {
+            Ok(inner) => inner.attribute_value(),
+            Err(inner) => inner.attribute_value(),
+        }
+    }
+}
+
+/// A trait for using a arbitrary type as the text content of a element, see
+/// [`HtmlElement::inner_text`].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a valid text value.",
+    note = "Try converting the value to a string"
+)]
+pub trait ToInnerText<C: Component>: 'static {
+    /// Set the text content of the given node
+    fn apply_inner_text(
+        self: Box<Self>,
+        node: &web_sys::Element,
+        ctx: &mut State<C>,
+        rendering_state: &mut RenderingState,
+    );
+}
+
+/// generate a `ToInnerText` implementation for a string type
+macro_rules! inner_text_string {
+    ($type:ty) => {
+        impl<C: Component> ToInnerText<C> for $type {
+            fn apply_inner_text(
+                self: Box<Self>,
+                node: &web_sys::Element,
+                _ctx: &mut State<C>,
+                _rendering_state: &mut RenderingState,
+            ) {
+                node.set_text_content(Some(&self));
+            }
+        }
+    };
+}
+
+type_macros::strings!(inner_text_string);
+
+/// generate `ToInnerText` for a int using itoa
+macro_rules! inner_text_int {
+    ($T:ident, $fmt:ident) => {
+        impl<C: Component> ToInnerText<C> for $T {
+            fn apply_inner_text(
+                self: Box<Self>,
+                node: &web_sys::Element,
+                _ctx: &mut State<C>,
+                _rendering_state: &mut RenderingState,
+            ) {
+                let mut buffer = $fmt::Buffer::new();
+                node.set_text_content(Some(buffer.format(*self)));
+            }
+        }
+    };
+}
+
+type_macros::numerics!(inner_text_int);
+
+thread_local! {
+    /// Buffer reused between renders for formatting `text_display` values.
+    static TEXT_BUFFER: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Text content formatted using its `Display` implementation, see
+/// [`HtmlElement::text_display`].
+struct DisplayText<T>(T);
+
+impl<C: Component, T: Display + 'static> ToInnerText<C> for DisplayText<T> {
+    fn apply_inner_text(
+        self: Box<Self>,
+        node: &web_sys::Element,
+        _ctx: &mut State<C>,
+        _rendering_state: &mut RenderingState,
+    ) {
+        TEXT_BUFFER.with_borrow_mut(|buffer| {
+            buffer.clear();
+            debug_expect!(
+                write!(buffer, "{}", self.0),
+                "`Display` implementation returned a error"
+            );
+            node.set_text_content(Some(buffer));
+        });
+    }
+}
+
+/// The shared state of a [`HtmlElement::text_rate_limited`] element
+struct TextLimiter {
+    /// The minimum time between writes in milliseconds
+    interval: f64,
+    /// The time of the last write in milliseconds since the unix epoch
+    last_write: Cell<f64>,
+    /// The latest text not yet written, this is only `Some` while a write is scheduled
+    pending: RefCell<Option<String>>,
+}
+
+impl TextLimiter {
+    /// Write `text` to `node`, or schedule it for when the interval has passed
+    fn write(self: &Rc<Self>, node: &web_sys::Element, text: String) {
+        let now = web_sys::js_sys::Date::now();
+        let mut pending = self.pending.borrow_mut();
+
+        if pending.is_some() {
+            *pending = Some(text);
+            return;
+        }
+
+        let elapsed = now - self.last_write.get();
+        if elapsed >= self.interval {
+            self.last_write.set(now);
+            node.set_text_content(Some(&text));
+            return;
+        }
+
+        *pending = Some(text);
+        let limiter = Rc::downgrade(self);
+        let node = node.clone();
+        let remaining = Duration::from_secs_f64((self.interval - elapsed).max(0.0) / 1000.0);
+        wasm_bindgen_futures::spawn_local(async move {
+            crate::async_utils::sleep(remaining).await;
+            crate::async_utils::next_animation_frame().await;
+
+            // The element was unmounted in the meantime
+            let Some(limiter) = limiter.upgrade() else {
+                return;
+            };
+            if let Some(text) = limiter.pending.borrow_mut().take() {
+                limiter.last_write.set(web_sys::js_sys::Date::now());
+                node.set_text_content(Some(&text));
+            }
+        });
+    }
+}
+
+/// Text content written at most once per interval, see [`HtmlElement::text_rate_limited`].
+struct RateLimitedText<T> {
+    /// The value to display
+    value: T,
+    /// The limiter shared between updates of the element
+    limiter: Rc<TextLimiter>,
+}
+
+impl<C: Component, T: Display + 'static> ToInnerText<C> for RateLimitedText<T> {
+    fn apply_inner_text(
+        self: Box<Self>,
+        node: &web_sys::Element,
+        _ctx: &mut State<C>,
+        _rendering_state: &mut RenderingState,
+    ) {
+        self.limiter.write(node, self.value.to_string());
+    }
+}
+
+/// A trait for using a arbitrary type as the value of a dom property, see [`HtmlElement::prop`].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a valid property value.",
+    note = "Try converting the value to a `JsValue`"
+)]
+pub trait ToProperty<C: Component>: 'static {
+    /// Set the property on the given node
+    fn apply_property(
+        self: Box<Self>,
+        name: &'static str,
+        node: &web_sys::Element,
+        ctx: &mut State<C>,
+        rendering_state: &mut RenderingState,
+    );
+}
+
+/// Set the property `name` of `node` to `value`
+fn set_property(node: &web_sys::Element, name: &'static str, value: &JsValue) {
+    debug_expect!(
+        web_sys::js_sys::Reflect::set(node, &JsValue::from_str(name), value),
+        "Failed to set property {name}"
+    );
+}
+
+/// generate a `ToProperty` implementation for a type `JsValue` can be created from
+macro_rules! property_value {
+    ($($type:ty),*) => {
+        $(
+            impl<C: Component> ToProperty<C> for $type {
+                fn apply_property(
+                    self: Box<Self>,
+                    name: &'static str,
+                    node: &web_sys::Element,
+                    _ctx: &mut State<C>,
+                    _rendering_state: &mut RenderingState,
+                ) {
+                    set_property(node, name, &JsValue::from(*self));
+                }
+            }
+        )*
+    };
+}
+
+property_value!(bool, f64, i32, u32);
+
+impl<C: Component> ToProperty<C> for JsValue {
+    fn apply_property(
+        self: Box<Self>,
+        name: &'static str,
+        node: &web_sys::Element,
+        _ctx: &mut State<C>,
+        _rendering_state: &mut RenderingState,
+    ) {
+        set_property(node, name, &self);
+    }
+}
+
+/// generate a `ToProperty` implementation for a string type
+macro_rules! property_string {
+    ($type:ty) => {
+        impl<C: Component> ToProperty<C> for $type {
+            fn apply_property(
+                self: Box<Self>,
+                name: &'static str,
+                node: &web_sys::Element,
+                _ctx: &mut State<C>,
+                _rendering_state: &mut RenderingState,
+            ) {
+                set_property(node, name, &JsValue::from_str(&self));
+            }
+        }
+    };
+}
+
+type_macros::strings!(property_string);
+
+/// Apply a boolean to the `disabled` attribute while mirroring it to `aria-disabled`.
+struct Disabled(bool);
+
+impl<C: Component> ToAttribute<C> for Disabled {
+    fn apply_attribute(
+        self: Box<Self>,
+        name: AttributeName,
+        node: &web_sys::Element,
+        ctx: &mut State<C>,
+        rendering_state: &mut RenderingState,
+    ) {
+        Box::new(self.0).apply_attribute(name, node, ctx, rendering_state);
+        let aria = self.0.then_some("true");
+        let aria_name = AttributeName::from(intern("aria-disabled"));
+        Box::new(aria).apply_attribute(aria_name, node, ctx, rendering_state);
+    }
+
+    // `aria-disabled` is derived from the same bool, so it is covered by this as well.
+    fn attribute_value(&self) -> Option<AttributeValue> {
+        ToAttribute::<C>::attribute_value(&self.0)
+    }
+}
+
+/// A value that can be used with [`HtmlElement::disabled`], either a `bool` or a reactive closure
+/// returning a `bool`.
+pub trait DisabledValue<C: Component> {
+    /// Convert this into the attribute value to apply
+    fn into_attribute(self) -> Box<dyn ToAttribute<C>>;
+}
+
+impl<C: Component> DisabledValue<C> for bool {
+    fn into_attribute(self) -> Box<dyn ToAttribute<C>> {
+        Box::new(Disabled(self))
+    }
+}
+
+impl<C: Component, F> DisabledValue<C> for F
+where
+    F: Fn(&mut RenderCtx<C>) -> bool + 'static,
+{
+    fn into_attribute(self) -> Box<dyn ToAttribute<C>> {
+        Box::new(move |ctx: &mut RenderCtx<C>| Disabled(self(ctx)))
+    }
+}
+
+/// The `contenteditable` attribute, written as `"true"`/`"false"` rather than present/absent.
+struct Editable(bool);
+
+impl<C: Component> ToAttribute<C> for Editable {
+    fn apply_attribute(
+        self: Box<Self>,
+        name: AttributeName,
+        node: &web_sys::Element,
+        _ctx: &mut State<C>,
+        _rendering_state: &mut RenderingState,
+    ) {
+        let value = if self.0 { "true" } else { "false" };
+        name.set(node, value);
+    }
+
+    fn attribute_value(&self) -> Option<AttributeValue> {
+        Some(AttributeValue::Set(self.0.to_string()))
+    }
+}
+
+/// A value that can be used with [`HtmlElement::contenteditable`], either a `bool` or a reactive
+/// closure returning a `bool`.
+pub trait EditableValue<C: Component> {
+    /// Convert this into the attribute value to apply
+    fn into_attribute(self) -> Box<dyn ToAttribute<C>>;
+}
+
+impl<C: Component> EditableValue<C> for bool {
+    fn into_attribute(self) -> Box<dyn ToAttribute<C>> {
+        Box::new(Editable(self))
+    }
+}
+
+impl<C: Component, F> EditableValue<C> for F
+where
+    F: Fn(&mut RenderCtx<C>) -> bool + 'static,
+{
+    fn into_attribute(self) -> Box<dyn ToAttribute<C>> {
+        Box::new(move |ctx: &mut RenderCtx<C>| Editable(self(ctx)))
+    }
+}
+
+/// A reference to a rendered element, set by [`HtmlElement::node_ref`].
+///
+/// Clones refer to the same element.
+/// ```rust
+/// # use natrix::prelude::*;
+/// use natrix::html_elements::NodeRef;
+///
+/// #[derive(Component)]
+/// struct Search {
+///     #[natrix(skip)]
+///     input: NodeRef,
+/// }
+///
+/// impl Component for Search {
+///     # type EmitMessage = NoMessages;
+///     # type ReceiveMessage = NoMessages;
+///     fn render() -> impl Element<Self> {
+///         e::div().child(|ctx: R<Self>| e::input().node_ref(&ctx.input))
+///     }
+///
+///     fn on_mount(ctx: E<Self>) {
+///         // The element is not rendered yet, so this is deferred
+///         ctx.focus(&ctx.input);
+///     }
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct NodeRef(Rc<RefCell<Option<web_sys::Element>>>);
+
+impl NodeRef {
+    /// Create a empty reference
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the referenced element, `None` until the element has been rendered.
+    #[must_use]
+    pub fn get(&self) -> Option<web_sys::Element> {
+        self.0.borrow().clone()
+    }
+
+    /// Set the referenced element
+    fn set(&self, element: web_sys::Element) {
+        *self.0.borrow_mut() = Some(element);
+    }
+
+    /// Focus the element if it is in the dom, returning if it was.
+    pub(crate) fn try_focus(&self) -> bool {
+        let element = self.0.borrow();
+        let Some(element) = element
+            .as_ref()
+            .filter(|element| element.is_connected())
+            .and_then(|element| element.dyn_ref::<web_sys::HtmlElement>())
+        else {
+            return false;
+        };
+        debug_expect!(element.focus(), "Failed to focus element");
+        true
+    }
+
+    /// The current `value` of the element, if it is a mounted `input`, `textarea` or `select`.
+    pub(crate) fn input_value(&self) -> Option<String> {
+        let element = self.0.borrow();
+        let element = element.as_ref().filter(|element| element.is_connected())?;
+        let tag = element.local_name();
+        if !matches!(tag.as_str(), "input" | "textarea" | "select") {
+            return None;
+        }
+        web_sys::js_sys::Reflect::get(element, &JsValue::from_str("value"))
+            .ok()?
+            .as_string()
+    }
+}
+
+/// A event handler registered on a element
+type EventCallback<C> = Box<dyn Fn(&mut State<C>, web_sys::Event)>;
+
+/// Convert a handler for a specific event type to a generic callback
+fn typed_event_callback<C: Component, E: Event>(
+    function: impl EventHandler<C, E>,
+) -> EventCallback<C> {
+    let function = function.func();
+    Box::new(move |ctx, event| {
+        if let Ok(event) = event.dyn_into::<E::JsEvent>() {
+            function(ctx, event);
+        } else {
+            debug_assert!(false, "Mismatched event types");
+        }
+    })
+}
+
+/// A event handler registered with `on_item`, receiving the key of the list item
+type ItemEventHandler<C> = Rc<dyn Fn(&mut State<C>, web_sys::Event, &dyn Any)>;
+
+/// Convert a handler for a specific event and key type to a generic item callback
+fn typed_item_callback<C: Component, E: Event, K: 'static>(
+    function: impl Fn(&mut State<C>, E::JsEvent, &K) + 'static,
+) -> ItemEventHandler<C> {
+    Rc::new(move |ctx, event, key| {
+        let (Ok(event), Some(key)) = (event.dyn_into::<E::JsEvent>(), key.downcast_ref::<K>())
+        else {
+            debug_assert!(false, "Mismatched event or item key types");
+            return;
+        };
+        function(ctx, event, key);
+    })
+}
+
+/// A item event handler that can be registered on many list items, without allocating a new
+/// closure for every item.
+///
+/// Create it once outside the list, and register it on each item with
+/// [`on_item_shared`](HtmlElement::on_item_shared). Clones refer to the same handler.
+pub struct SharedHandler<C: Component, E, K> {
+    /// The type erased handler
+    function: ItemEventHandler<C>,
+    /// The event and key types the handler expects
+    marker: std::marker::PhantomData<(E, K)>,
+}
+
+impl<C: Component, E, K> Clone for SharedHandler<C, E, K> {
+    fn clone(&self) -> Self {
+        Self {
+            function: Rc::clone(&self.function),
+            marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<C: Component, E: Event, K: 'static> SharedHandler<C, E, K> {
+    /// Create a shared handler, which receives the key of the item it is triggered on.
+    pub fn new(function: impl Fn(&mut State<C>, E::JsEvent, &K) + 'static) -> Self {
+        Self {
+            function: typed_item_callback::<C, E, K>(function),
+            marker: std::marker::PhantomData,
+        }
+    }
 }
 
+/// Events that are suppressed on elements with the `disabled` attribute
+const DISABLED_EVENTS: [&str; 3] = ["click", "dblclick", "auxclick"];
+
 /// A Generic html node with a given name.
 #[must_use = "Web elements are useless if not rendered"]
 pub struct HtmlElement<C: Component, T = ()> {
     /// The name of the tag
     tag: &'static str,
+    /// The namespace to create the element in, `None` for html elements
+    namespace: Option<&'static str>,
     /// List of child elements
     children: Vec<Box<dyn Element<C>>>,
     /// Events to be registered on the element
-    events: Vec<(&'static str, Box<dyn Fn(&mut State<C>, web_sys::Event)>)>,
+    events: Vec<(&'static str, EventCallback<C>)>,
+    /// Events to be registered on the element in the capture phase
+    capture_events: Vec<(&'static str, EventCallback<C>)>,
+    /// Events to be registered on the element, that also receive the key of the list item
+    item_events: Vec<(&'static str, ItemEventHandler<C>)>,
     /// Potentially dynamic attributes to apply
-    attributes: Vec<(&'static str, Box<dyn ToAttribute<C>>)>,
+    attributes: Vec<(AttributeName, Box<dyn ToAttribute<C>>)>,
+    /// Potentially dynamic properties to apply
+    properties: Vec<(&'static str, Box<dyn ToProperty<C>>)>,
     /// Css classes to apply
     classes: Vec<Cow<'static, str>>,
+    /// Css classes to reactively add or remove
+    class_toggles: Vec<(&'static str, Box<dyn Fn(&mut RenderCtx<C>) -> bool>)>,
+    /// Text content to set, replacing any children
+    inner_text: Option<Box<dyn ToInnerText<C>>>,
+    /// Reference to fill with the rendered element
+    node_ref: Option<NodeRef>,
     /// Phantom data to allow for genericity
     phantom: std::marker::PhantomData<T>,
 }
@@ -173,14 +738,30 @@ impl<C: Component, T> HtmlElement<C, T> {
     pub fn new(tag: &'static str) -> Self {
         Self {
             tag,
+            namespace: None,
             events: Vec::new(),
+            capture_events: Vec::new(),
+            item_events: Vec::new(),
             children: Vec::new(),
             attributes: Vec::new(),
+            properties: Vec::new(),
             classes: Vec::new(),
+            class_toggles: Vec::new(),
+            inner_text: None,
+            node_ref: None,
             phantom: std::marker::PhantomData,
         }
     }
 
+    /// Create a new element with the specific tag in the given namespace, such as
+    /// [`namespaces::SVG`].
+    pub fn new_ns(namespace: &'static str, tag: &'static str) -> Self {
+        Self {
+            namespace: Some(namespace),
+            ..Self::new(tag)
+        }
+    }
+
     /// Register a event handler for this element.
     ///
     /// The event handler is a closure taking a mutable reference to `S<Self>`.
@@ -200,66 +781,676 @@ impl<C: Component, T> HtmlElement<C, T> {
     /// # }}
     /// ```
     /// For more information see [Reactivity](https://vivax3794.github.io/natrix/reactivity.html) in the book.
+    ///
+    /// This is additive, registering the same event twice will call both handlers.
+    /// Use [`on_replace`](Self::on_replace) if you want to replace any existing handlers instead.
     pub fn on<E: Event>(mut self, function: impl EventHandler<C, E>) -> Self {
-        let function = function.func();
-        self.events.push((
-            E::EVENT_NAME,
-            Box::new(move |ctx, event| {
-                if let Ok(event) = event.dyn_into::<E::JsEvent>() {
-                    function(ctx, event);
-                } else {
-                    debug_assert!(false, "Mismatched event types");
-                }
-            }),
-        ));
+        self.events
+            .push((E::EVENT_NAME, typed_event_callback::<C, E>(function)));
         self
     }
 
-    /// Push a child to this element.
-    /// This accepts any valid element including closures.
+    /// Register a fallible event handler for this element.
+    ///
+    /// A returned `Err` is passed to [`Component::handle_error`] of this component, which can
+    /// handle it or pass it on to its parent, instead of the handler having to panic.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # use std::num::ParseIntError;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     value: u8,
+    /// #     input: String,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::button().on_try::<events::Click, _>(|ctx: E<Self>, _| -> Result<(), ParseIntError> {
+    ///     *ctx.value = ctx.input.parse()?;
+    ///     Ok(())
+    /// })
+    /// # }}
+    /// ```
+    pub fn on_try<E: Event, Er: Display>(
+        self,
+        function: impl Fn(&mut State<C>, E::JsEvent) -> Result<(), Er> + 'static,
+    ) -> Self {
+        self.on::<E>(move |ctx: &mut State<C>, event: E::JsEvent| {
+            if let Err(error) = function(ctx, event) {
+                ctx.raise_error(error);
+            }
+        })
+    }
+
+    /// Register a async event handler for this element.
+    ///
+    /// The returned future is spawned with [`State::use_async`], so the handler receives a
+    /// [`DeferredCtx`] instead of the state. Like with `use_async` the `DeferredRef` returned by
+    /// [`borrow_mut`](DeferredCtx::borrow_mut) must not be held across a `.await`, and the future
+    /// returns `Option<()>` so `?` can be used to stop once the component is unmounted.
+    ///
+    /// The future only starts after the event has been dispatched, so calls such as
+    /// `prevent_default` have to happen before the first `.await`.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # use natrix::state::DeferredCtx;
+    /// # async fn fetch_user() -> String { String::new() }
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     loading: bool,
+    /// #     user: Option<String>,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::button().on_async::<events::Click, _>(|ctx: DeferredCtx<Self>, _| async move {
+    ///     *ctx.borrow_mut()?.loading = true;
+    ///     let user = fetch_user().await;
+    ///
+    ///     let mut ctx = ctx.borrow_mut()?;
+    ///     *ctx.loading = false;
+    ///     *ctx.user = Some(user);
+    ///     Some(())
+    /// })
+    /// # }}
+    /// ```
+    pub fn on_async<E: Event, F>(
+        self,
+        function: impl Fn(DeferredCtx<C>, E::JsEvent) -> F + 'static,
+    ) -> Self
+    where
+        F: Future<Output = Option<()>> + 'static,
+    {
+        self.on::<E>(move |ctx: &mut State<C>, event: E::JsEvent| {
+            ctx.use_async(|ctx| function(ctx, event));
+        })
+    }
+
+    /// Register a event handler for this element that can mutate its own captured state.
+    ///
+    /// This is for state private to the handler, state used for rendering should be stored in the
+    /// component like usual.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     message: &'static str,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// let mut clicks = 0;
+    /// e::button().on_mut::<events::Click>(move |ctx: E<Self>, _| {
+    ///     clicks += 1;
+    ///     if clicks == 3 {
+    ///         *ctx.message = "Stop clicking me!";
+    ///     }
+    /// })
+    /// # }}
+    /// ```
+    ///
+    /// The handler is never called reentrantly, if the event is dispatched again while the
+    /// handler is running (for example by calling `.click()` on the element) the nested
+    /// dispatch is ignored.
+    pub fn on_mut<E: Event>(
+        mut self,
+        function: impl FnMut(&mut State<C>, E::JsEvent) + 'static,
+    ) -> Self {
+        let function = RefCell::new(function);
+        let function = move |ctx: &mut State<C>, event: E::JsEvent| {
+            let Ok(mut function) = function.try_borrow_mut() else {
+                return;
+            };
+            function(ctx, event);
+        };
+        self.events
+            .push((E::EVENT_NAME, typed_event_callback::<C, E>(function)));
+        self
+    }
+
+    /// Register a event handler for this element that is called in the capture phase, i.e before
+    /// any handlers on its descendants.
+    ///
+    /// This is needed for things like focus trapping, where the handler should run before the
+    /// event reaches the element it targets.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     clicks: u32,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::div()
+    ///     // Counts clicks on any of the children, even if they stop propagation
+    ///     .on_capture::<events::Click>(|ctx: E<Self>, _| {
+    ///         *ctx.clicks += 1;
+    ///     })
+    ///     .child(e::button().text("Click me"))
+    /// # }}
+    /// ```
+    pub fn on_capture<E: Event>(mut self, function: impl EventHandler<C, E>) -> Self {
+        self.capture_events
+            .push((E::EVENT_NAME, typed_event_callback::<C, E>(function)));
+        self
+    }
+
+    /// Register a event handler for this element, replacing any handlers previously registered
+    /// for the same event.
+    ///
+    /// This is useful when composing builders where a event might already have a handler.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     some_value: i32,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::button()
+    ///     .on::<events::Click>(|ctx: E<Self>, _| {
+    ///         *ctx.some_value += 1;
+    ///     })
+    ///     // Only this handler will be called
+    ///     .on_replace::<events::Click>(|ctx: E<Self>, _| {
+    ///         *ctx.some_value += 10;
+    ///     })
+    /// # }}
+    /// ```
+    pub fn on_replace<E: Event>(mut self, function: impl EventHandler<C, E>) -> Self {
+        self.events.retain(|(name, _)| *name != E::EVENT_NAME);
+        self.item_events.retain(|(name, _)| *name != E::EVENT_NAME);
+        self.capture_events
+            .retain(|(name, _)| *name != E::EVENT_NAME);
+        self.on::<E>(function)
+    }
+
+    /// Register a event handler that also receives the key of the list item this element is
+    /// rendered in.
+    ///
+    /// For [`for_each!`](crate::for_each) this is the key of the item, and for
+    /// [`List`](crate::list::List) it is the index as a `usize`. The key is looked up when the
+    /// element is rendered, so it stays correct when the items are reordered.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// #[derive(Clone, PartialEq)]
+    /// struct Todo {
+    ///     id: u32,
+    ///     text: String,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Todos {
+    ///     todos: Vec<Todo>,
+    /// }
+    ///
+    /// impl Component for Todos {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         e::ul().child(for_each!(|ctx| ctx.todos => |todo| {
+    ///             e::li()
+    ///                 .text(todo.text)
+    ///                 .on_item::<events::Click, u32>(|ctx: E<Self>, _, id| {
+    ///                     ctx.todos.retain(|todo| todo.id != *id);
+    ///                 })
+    ///         }))
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// In debug mode this panics if the element is not rendered inside a list, or the key is not
+    /// of type `K`. In release mode the handler is ignored.
+    pub fn on_item<E: Event, K: 'static>(
+        mut self,
+        function: impl Fn(&mut State<C>, E::JsEvent, &K) + 'static,
+    ) -> Self {
+        self.item_events
+            .push((E::EVENT_NAME, typed_item_callback::<C, E, K>(function)));
+        self
+    }
+
+    /// Register a [`SharedHandler`] that receives the key of the list item this element is
+    /// rendered in.
+    ///
+    /// This behaves like [`on_item`](Self::on_item), but every item refers to the same handler
+    /// instead of each allocating its own closure, which adds up for large lists.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// use natrix::html_elements::SharedHandler;
+    /// use natrix::list::List;
+    /// use natrix::state::State;
+    ///
+    /// #[derive(Component)]
+    /// struct Rows {
+    ///     rows: Vec<u32>,
+    ///     selected: Option<usize>,
+    /// }
+    ///
+    /// impl Component for Rows {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         let select: SharedHandler<Self, events::Click, usize> =
+    ///             SharedHandler::new(|ctx: E<Self>, _, index| *ctx.selected = Some(*index));
+    ///
+    ///         e::ul().child(List::new(
+    ///             |ctx: &State<Self>| &ctx.rows,
+    ///             move |_ctx, getter| {
+    ///                 e::li()
+    ///                     .text(move |ctx: R<Self>| getter.get_watched(ctx))
+    ///                     .on_item_shared(&select)
+    ///             },
+    ///         ))
+    ///     }
+    /// }
+    /// ```
+    pub fn on_item_shared<E: Event, K: 'static>(
+        mut self,
+        handler: &SharedHandler<C, E, K>,
+    ) -> Self {
+        self.item_events
+            .push((E::EVENT_NAME, Rc::clone(&handler.function)));
+        self
+    }
+
+    /// Register a handler that is called when the user presses `Enter` on this element.
+    ///
+    /// For `textarea` elements `Shift+Enter` is ignored so it can still be used to insert a
+    /// newline. Key presses that are part of a IME composition are also ignored.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     submitted: bool,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::input().on_enter(|ctx: E<Self>| {
+    ///     *ctx.submitted = true;
+    /// })
+    /// # }}
+    /// ```
+    /// This is registered as a normal `keydown` handler, so it composes with other handlers.
+    pub fn on_enter(self, function: impl Fn(&mut State<C>) + 'static) -> Self {
+        let allow_shift = self.tag != "textarea";
+        self.on::<events::KeyDown>(move |ctx: &mut State<C>, event: web_sys::KeyboardEvent| {
+            if event.key() == "Enter"
+                && !event.is_composing()
+                && (allow_shift || !event.shift_key())
+            {
+                function(ctx);
+            }
+        })
+    }
+
+    /// Push a child to this element.
+    /// This accepts any valid element including closures.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     toggle: bool,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::div()
+    ///     .child(e::h1().text("Wow!"))
+    ///     .child(|ctx: R<Self>| {
+    ///         if *ctx.toggle {
+    ///             "Hello"
+    ///         } else {
+    ///             "World"
+    ///         }
+    ///     })
+    /// # }}
+    /// ```
+    pub fn child<E: Element<C> + 'static>(mut self, child: E) -> Self {
+        self.children.push(Box::new(child));
+        self
+    }
+
+    /// Push a child to this element if it is `Some`.
+    ///
+    /// Unlike passing a `Option` to `.child` this does not insert a placeholder for `None`, so it
+    /// should be preferred for children that are decided when building the element.
+    /// `bool::then` is a convenient way to create the option while keeping type inference happy.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # let show_title = true;
+    /// # let _: e::HtmlElement<(), _> =
+    /// e::div()
+    ///     .child_opt(show_title.then(|| e::h1().text("Title")))
+    /// # ;
+    /// ```
+    pub fn child_opt<E: Element<C> + 'static>(self, child: Option<E>) -> Self {
+        match child {
+            Some(child) => self.child(child),
+            None => self,
+        }
+    }
+
+    /// Push every child of the iterator to this element, in order.
+    ///
+    /// This can be freely mixed with `.child`, the children are rendered in the order they were
+    /// added.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # let _: e::HtmlElement<(), _> =
+    /// e::ul()
+    ///     .child(e::li().text("First"))
+    ///     .children(["Second", "Third"].map(|text| e::li().text(text)))
+    ///     .child(e::li().text("Last"))
+    /// # ;
+    /// ```
+    pub fn children<E: Element<C> + 'static>(
+        mut self,
+        children: impl IntoIterator<Item = E>,
+    ) -> Self {
+        self.children.extend(
+            children
+                .into_iter()
+                .map(|child| Box::new(child) as Box<dyn Element<C>>),
+        );
+        self
+    }
+
+    /// Push a child that is only rendered while `condition` returns `true`.
+    ///
+    /// The child is only constructed when it is shown, and a comment is left in its place while
+    /// hidden. The condition is [`watch`](RenderCtx::watch)ed, so changes to the signals it reads
+    /// only re-render the child when the result flips.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// use natrix::state::State;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     open: bool,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::div().child_if(|ctx: &State<Self>| *ctx.open, || e::div().text("panel"))
+    /// # }}
+    /// ```
+    pub fn child_if<E: Element<C>>(
+        self,
+        condition: impl Fn(&State<C>) -> bool + 'static,
+        child: impl Fn() -> E + 'static,
+    ) -> Self {
+        self.child(conditional_child(condition, child))
+    }
+
+    /// Set the text content of the element directly, instead of creating a child text node.
+    ///
+    /// This is faster for elements that only contain text, as reactive updates set the
+    /// `textContent` of the element in place. This replaces any children, so it should not be
+    /// combined with [`child`](Self::child) (which is a debug assertion).
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     name: String,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::h1().inner_text(|ctx: R<Self>| ctx.name.clone())
+    /// # }}
+    /// ```
+    pub fn inner_text(mut self, text: impl ToInnerText<C>) -> Self {
+        self.inner_text = Some(Box::new(text));
+        self
+    }
+
+    /// Set the text content of the element to a value implementing [`Display`], formatting it
+    /// without allocating a new `String` for every update.
+    ///
+    /// The value is written with `write!` into a buffer reused between all elements, which is
+    /// then passed to `set_text_content`. Like [`inner_text`](Self::inner_text) this replaces any
+    /// children of the element.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     ip: std::net::Ipv4Addr,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::span().text_display(|ctx: R<Self>| *ctx.ip)
+    /// # }}
+    /// ```
+    pub fn text_display<D: Display + 'static>(
+        self,
+        text: impl Fn(&mut RenderCtx<C>) -> D + 'static,
+    ) -> Self {
+        self.inner_text(move |ctx: &mut RenderCtx<C>| DisplayText(text(ctx)))
+    }
+
+    /// Like [`text_display`](Self::text_display), but writes the text at most once per
+    /// `interval`.
+    ///
+    /// Updates arriving faster than that are coalesced, and the latest value is written on the
+    /// first animation frame after the interval has passed. This is useful for values that change
+    /// very often, such as counters driven by animation frames.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # use std::time::Duration;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     frames: u64,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::span().text_rate_limited(Duration::from_millis(250), |ctx: R<Self>| *ctx.frames)
+    /// # }}
+    /// ```
+    pub fn text_rate_limited<D: Display + 'static>(
+        self,
+        interval: Duration,
+        text: impl Fn(&mut RenderCtx<C>) -> D + 'static,
+    ) -> Self {
+        let limiter = Rc::new(TextLimiter {
+            interval: interval.as_secs_f64() * 1000.0,
+            last_write: Cell::new(0.0),
+            pending: RefCell::new(None),
+        });
+        self.inner_text(move |ctx: &mut RenderCtx<C>| RateLimitedText {
+            value: text(ctx),
+            limiter: Rc::clone(&limiter),
+        })
+    }
+
+    /// This is a simple alias for `child`
+    pub fn text<E: Element<C>>(self, text: E) -> Self {
+        self.child(text)
+    }
+
+    /// Add a attribute to the node.
+    pub fn attr(mut self, key: &'static str, value: impl ToAttribute<C>) -> Self {
+        self.attributes.push((key.into(), Box::new(value)));
+        self
+    }
+
+    /// Add a attribute in the given namespace to the node, using `setAttributeNS`.
+    ///
+    /// The `name` is the qualified name, including the prefix if any.
+    /// See [`namespaces`] for the common namespaces.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// use natrix::html_elements::namespaces;
+    /// # let _: e::HtmlElement<(), _> =
+    /// e::svg::use_().attr_ns(namespaces::XLINK, "xlink:href", "#icon-home")
+    /// # ;
+    /// ```
+    /// Like [`attr`](Self::attr) the value can be reactive.
+    pub fn attr_ns(
+        mut self,
+        namespace: &'static str,
+        name: &'static str,
+        value: impl ToAttribute<C>,
+    ) -> Self {
+        let name = AttributeName {
+            namespace: Some(namespace),
+            name,
+        };
+        self.attributes.push((name, Box::new(value)));
+        self
+    }
+
+    /// Set a dom property of the element, such as `value`, `checked` or `indeterminate`.
+    ///
+    /// Unlike attributes, which are the initial state written in the html, properties are the
+    /// live state of the element. For example once the user has typed in a `<input>` changing its
+    /// `value` attribute no longer changes the displayed text, but setting the `value` property
+    /// does. Some properties such as `indeterminate` do not have a attribute at all.
+    ///
+    /// Use `.attr` for state that should be visible in the html (and to css selectors), and
+    /// `.prop` for the current state of form controls.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     text: String,
+    /// #     some_selected: bool,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::div()
+    ///     .child(e::input().prop("value", |ctx: R<Self>| ctx.text.clone()))
+    ///     .child(
+    ///         e::input()
+    ///             .attr("type", "checkbox")
+    ///             .prop("indeterminate", |ctx: R<Self>| *ctx.some_selected),
+    ///     )
+    /// # }}
+    /// ```
+    /// Reactive properties are set every time the closure re-runs, even if the value is the
+    /// same, as the user might have changed the live state in the meantime.
+    pub fn prop(mut self, name: &'static str, value: impl ToProperty<C>) -> Self {
+        self.properties.push((name, Box::new(value)));
+        self
+    }
+
+    /// Set the `disabled` attribute, also setting `aria-disabled="true"` for assistive
+    /// technologies.
+    ///
+    /// While disabled any registered click handlers (`click`, `dblclick` and `auxclick`) will not
+    /// be called, this also applies to elements where `disabled` has no native effect (such as a
+    /// `div` with a `button` role).
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     loading: bool,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::button()
+    ///     .text("Submit")
+    ///     .disabled(|ctx: R<Self>| *ctx.loading)
+    /// # }}
+    /// ```
+    pub fn disabled(mut self, value: impl DisabledValue<C>) -> Self {
+        self.attributes
+            .push(("disabled".into(), value.into_attribute()));
+        self
+    }
+
+    /// Set the `tabindex` attribute, `0` makes the element focusable in document order and `-1`
+    /// makes it focusable only from code (such as [`State::focus`]).
+    pub fn tabindex(self, value: impl ToAttribute<C>) -> Self {
+        self.attr("tabindex", value)
+    }
+
+    /// Set the `contenteditable` attribute.
+    ///
+    /// Booleans are written as `"true"` and `"false"`, so `false` can be used to opt out of
+    /// editing inside a editable parent.
     /// ```rust
     /// # use natrix::prelude::*;
     /// # #[derive(Component)]
     /// # struct MyComponent {
-    /// #     toggle: bool,
+    /// #     editing: bool,
     /// # }
     /// # impl Component for MyComponent {
     /// # type EmitMessage = NoMessages;
     /// # type ReceiveMessage = NoMessages;
     /// # fn render() -> impl Element<Self> {
     /// e::div()
-    ///     .child(e::h1().text("Wow!"))
-    ///     .child(|ctx: R<Self>| {
-    ///         if *ctx.toggle {
-    ///             "Hello"
-    ///         } else {
-    ///             "World"
-    ///         }
-    ///     })
+    ///     .contenteditable(|ctx: R<Self>| *ctx.editing)
+    ///     .tabindex(0)
     /// # }}
     /// ```
-    pub fn child<E: Element<C> + 'static>(mut self, child: E) -> Self {
-        self.children.push(Box::new(child));
+    pub fn contenteditable(mut self, value: impl EditableValue<C>) -> Self {
+        self.attributes
+            .push(("contenteditable".into(), value.into_attribute()));
         self
     }
 
-    /// This is a simple alias for `child`
-    pub fn text<E: Element<C>>(self, text: E) -> Self {
-        self.child(text)
-    }
-
-    /// Add a attribute to the node.
-    pub fn attr(mut self, key: &'static str, value: impl ToAttribute<C>) -> Self {
-        self.attributes.push((key, Box::new(value)));
+    /// Store the rendered element in the given [`NodeRef`], for example to focus it.
+    pub fn node_ref(mut self, node_ref: &NodeRef) -> Self {
+        self.node_ref = Some(node_ref.clone());
         self
     }
 
     /// Add a class to the element.
+    ///
+    /// A string containing whitespace adds each of the classes in it, and duplicate classes are
+    /// only added once.
     pub fn class(mut self, class: impl Into<Cow<'static, str>>) -> Self {
         self.classes.push(class.into());
         self
     }
 
+    /// Reactively add or remove a single class, based on the returned bool.
+    ///
+    /// This only touches the given class, any other classes on the element are kept.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     active: bool,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::button()
+    ///     .class("tab")
+    ///     .class_toggle("active", |ctx: R<Self>| *ctx.active)
+    /// # }}
+    /// ```
+    pub fn class_toggle(
+        mut self,
+        class: &'static str,
+        enabled: impl Fn(&mut RenderCtx<C>) -> bool + 'static,
+    ) -> Self {
+        self.class_toggles.push((class, Box::new(enabled)));
+        self
+    }
+
     /// Add multiple classes to the element.
     pub fn classes(
         mut self,
@@ -278,28 +1469,76 @@ impl<C: Component, T: 'static> Element<C> for HtmlElement<C, T> {
     ) -> web_sys::Node {
         let Self {
             tag: name,
-            events,
+            namespace,
+            mut events,
+            capture_events,
+            item_events,
             children,
             attributes,
+            properties,
             classes,
+            class_toggles,
+            inner_text,
+            node_ref,
             phantom: _,
         } = *self;
 
         let document = get_document();
-        let Ok(element) = document.create_element(intern(name)) else {
-            debug_assert!(false, "Failed to create element {name}");
+        let element = match namespace {
+            Some(namespace) => document.create_element_ns(Some(namespace), intern(name)),
+            None => document.create_element(intern(name)),
+        };
+        let Ok(element) = element else {
+            // This is most likely a invalid tag name, so the component renders its fallback
+            ctx.render_failed = true;
             return generate_fallback_node();
         };
 
-        for child in children {
-            let child = child.render_box(ctx, render_state);
-            debug_expect!(element.append_child(&child), "Failed to append child");
+        if let Some(text) = join_static_text(&children) {
+            element.set_text_content(Some(&text));
+        } else {
+            for child in children {
+                let child = child.render_box(ctx, render_state);
+                debug_expect!(element.append_child(&child), "Failed to append child");
+            }
+        }
+        if let Some(inner_text) = inner_text {
+            debug_assert!(
+                !element.has_child_nodes(),
+                "`inner_text` used on <{name}> together with children"
+            );
+            inner_text.apply_inner_text(&element, ctx, render_state);
+        }
+
+        if !item_events.is_empty() {
+            if let Some(key) = ctx.item_key() {
+                for (event, function) in item_events {
+                    let key = Rc::clone(&key);
+                    events.push((
+                        event,
+                        Box::new(move |ctx, event| function(ctx, event, &*key)),
+                    ));
+                }
+            } else {
+                debug_assert!(false, "`on_item` used on <{name}> outside of a list item");
+            }
         }
 
         for (event, function) in events {
             create_event_handler(
                 &element,
                 event,
+                false,
+                function,
+                ctx.deferred_borrow(),
+                render_state,
+            );
+        }
+        for (event, function) in capture_events {
+            create_event_handler(
+                &element,
+                event,
+                true,
                 function,
                 ctx.deferred_borrow(),
                 render_state,
@@ -307,31 +1546,492 @@ impl<C: Component, T: 'static> Element<C> for HtmlElement<C, T> {
         }
 
         for (key, value) in attributes {
-            value.apply_attribute(intern(key), &element, ctx, render_state);
+            let key = AttributeName {
+                name: intern(key.name),
+                ..key
+            };
+            value.apply_attribute(key, &element, ctx, render_state);
         }
+        // Properties are applied after attributes, as setting some attributes (such as `value`)
+        // also resets the property.
+        for (name, value) in properties {
+            value.apply_property(intern(name), &element, ctx, render_state);
+            render_state.pinned = true;
+        }
+        apply_classes(&element, &classes);
+        // Applied after the static classes, as those might replace the `class` attribute.
+        for (class, enabled) in class_toggles {
+            let hook = SimpleReactive::init_new(
+                Box::new(move |ctx| ReactiveClassToggle {
+                    class,
+                    enabled: enabled(ctx),
+                }),
+                element.clone(),
+                ctx,
+            );
+            render_state.hooks.push(hook);
+        }
+        if let Some(node_ref) = node_ref {
+            node_ref.set(element.clone());
+            render_state.pinned = true;
+        }
+
+        element.into()
+    }
+}
+
+/// Join the children into a single string if all of them are static text.
+fn join_static_text<C: Component>(children: &[Box<dyn Element<C>>]) -> Option<Cow<'_, str>> {
+    match children {
+        [] => None,
+        [child] => child.static_text().map(Cow::Borrowed),
+        children => children
+            .iter()
+            .map(Element::static_text)
+            .collect::<Option<String>>()
+            .map(Cow::Owned),
+    }
+}
+
+thread_local! {
+    /// Buffer reused between renders for assembling the `class` attribute.
+    static CLASS_BUFFER: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Add the classes to the element.
+///
+/// If no `class` attribute was set by `.attr` the classes are joined into a reused buffer and set
+/// in a single call, rather than calling `classList.add` for each one.
+/// Either way the classes are split on whitespace and duplicates are skipped, like `classList.add`
+/// would for a single class.
+fn apply_classes(element: &web_sys::Element, classes: &[Cow<'static, str>]) {
+    if classes.is_empty() {
+        return;
+    }
+    let classes = classes
+        .iter()
+        .flat_map(|class| class.split_ascii_whitespace());
+
+    if element.has_attribute("class") {
         for class in classes {
             debug_expect!(
-                element.class_list().add_1(&class),
+                element.class_list().add_1(class),
                 "Failed to add class {class}"
             );
         }
+        return;
+    }
 
-        element.into()
+    CLASS_BUFFER.with_borrow_mut(|buffer| {
+        buffer.clear();
+        for class in classes {
+            if buffer.split(' ').any(|existing| existing == class) {
+                continue;
+            }
+            if !buffer.is_empty() {
+                buffer.push(' ');
+            }
+            buffer.push_str(class);
+        }
+        debug_expect!(
+            element.set_attribute(intern("class"), buffer),
+            "Failed to set classes {buffer}"
+        );
+    });
+}
+
+/// Render the latest item of a `Stream`, re-rendering every time a new item arrives.
+///
+/// A placeholder is rendered until the first item arrives, and once the stream completes the last
+/// item is kept. The stream is dropped when the element is removed from the dom.
+///
+/// ```rust
+/// # use natrix::prelude::*;
+/// # fn example<C: Component>(feed: impl futures_core::Stream<Item = u32> + 'static) -> impl Element<C> {
+/// e::div().child(e::stream(feed, |value| e::span().text(value)))
+/// # }
+/// ```
+pub fn stream<S, F>(stream: S, render: F) -> StreamElement<S, F> {
+    StreamElement::new(stream, render)
+}
+
+/// Render a reactive closure, keeping focus within its content when it re-renders.
+///
+/// Normally when a closure re-runs its content is replaced, so any focused element inside it
+/// loses focus. With this the element at the same position in the new content is focused
+/// instead (if it has the same tag), and for text inputs the cursor position is kept as well.
+///
+/// This has some overhead on every re-render, so it is opt-in.
+/// ```rust
+/// # use natrix::prelude::*;
+/// # #[derive(Component)]
+/// # struct MyComponent {
+/// #     name: String,
+/// # }
+/// # impl Component for MyComponent {
+/// # type EmitMessage = NoMessages;
+/// # type ReceiveMessage = NoMessages;
+/// # fn render() -> impl Element<Self> {
+/// e::form().child(e::preserve_focus(|ctx: R<Self>| {
+///     let invalid = ctx.name.is_empty();
+///     e::div()
+///         .child(
+///             e::input()
+///                 .prop("value", ctx.name.clone())
+///                 .on::<events::Input>(|ctx: E<Self>, event: web_sys::InputEvent| {
+///                     if let Some(text) = event.data() {
+///                         ctx.name.push_str(&text);
+///                     }
+///                 }),
+///         )
+///         .child(invalid.then_some("Name is required"))
+/// }))
+/// # }}
+/// ```
+pub fn preserve_focus<F>(callback: F) -> PreserveFocus<F> {
+    PreserveFocus(callback)
+}
+
+/// A enum that can be picked from a `<select>`, see [`select_options`].
+///
+/// This should be implemented with `#[derive(SelectOptions)]`, which is only supported on enums
+/// without fields. The value of each option is the name of the variant.
+pub trait SelectOptions: Sized + 'static {
+    /// All the variants, in the order they are shown
+    const VARIANTS: &'static [Self];
+
+    /// The value of the option for this variant
+    fn value(&self) -> &'static str;
+
+    /// Get the variant with the given option value, `None` if it is unknown.
+    fn from_value(value: &str) -> Option<Self>;
+}
+
+/// A `<select>` with a `<option>` for every variant of `T`, labeled using its `Display`
+/// implementation.
+///
+/// `selected` returns the currently selected variant, and `on_change` is called with the new
+/// variant when the user picks a different option. Option values that are not a known variant
+/// are ignored.
+/// ```rust
+/// # use natrix::prelude::*;
+/// #[derive(SelectOptions, Clone, Copy, PartialEq)]
+/// enum Theme {
+///     Light,
+///     Dark,
+/// }
+///
+/// impl std::fmt::Display for Theme {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         match self {
+///             Self::Light => write!(f, "Light mode"),
+///             Self::Dark => write!(f, "Dark mode"),
+///         }
+///     }
+/// }
+///
+/// #[derive(Component)]
+/// struct Settings {
+///     theme: Theme,
+/// }
+///
+/// impl Component for Settings {
+///     # type EmitMessage = NoMessages;
+///     # type ReceiveMessage = NoMessages;
+///     fn render() -> impl Element<Self> {
+///         e::select_options(
+///             |ctx: R<Self>| *ctx.theme,
+///             |ctx: E<Self>, theme| *ctx.theme = theme,
+///         )
+///     }
+/// }
+/// ```
+pub fn select_options<C, T>(
+    selected: impl Fn(&mut RenderCtx<C>) -> T + 'static,
+    on_change: impl Fn(&mut State<C>, T) + 'static,
+) -> HtmlElement<C, _select>
+where
+    C: Component,
+    T: SelectOptions + Display,
+{
+    let options: Vec<_> = T::VARIANTS
+        .iter()
+        .map(|variant| option().value(variant.value()).text(variant.to_string()))
+        .collect();
+
+    select()
+        .child(options)
+        .prop("value", move |ctx: &mut RenderCtx<C>| selected(ctx).value())
+        .on::<events::Change>(move |ctx: &mut State<C>, event: web_sys::Event| {
+            let value = event
+                .target()
+                .and_then(|target| {
+                    web_sys::js_sys::Reflect::get(&target, &JsValue::from_str("value")).ok()
+                })
+                .and_then(|value| value.as_string());
+            if let Some(variant) = value.as_deref().and_then(T::from_value) {
+                on_change(ctx, variant);
+            }
+        })
+}
+
+/// A group of sibling elements without a wrapper node, constructed using [`fragment`].
+///
+/// Unlike a `Vec` the children can be of different types.
+/// ```rust
+/// # use natrix::prelude::*;
+/// # let show_subtitle = true;
+/// # let _: e::Fragment<()> =
+/// e::fragment()
+///     .child(e::h1().text("Title"))
+///     .child_opt(show_subtitle.then(|| e::h2().text("Subtitle")))
+///     .child("Some text")
+/// # ;
+/// ```
+#[must_use = "Web elements are useless if not rendered"]
+pub struct Fragment<C: Component> {
+    /// The children of the fragment
+    children: Vec<Box<dyn Element<C>>>,
+}
+
+impl<C: Component> Fragment<C> {
+    /// Push a child to the fragment
+    pub fn child<E: Element<C> + 'static>(mut self, child: E) -> Self {
+        self.children.push(Box::new(child));
+        self
+    }
+
+    /// Push a child to the fragment if it is `Some`, see [`HtmlElement::child_opt`]
+    pub fn child_opt<E: Element<C> + 'static>(self, child: Option<E>) -> Self {
+        match child {
+            Some(child) => self.child(child),
+            None => self,
+        }
+    }
+
+    /// Push every child of the iterator to the fragment, see [`HtmlElement::children`]
+    pub fn children<E: Element<C> + 'static>(
+        mut self,
+        children: impl IntoIterator<Item = E>,
+    ) -> Self {
+        self.children.extend(
+            children
+                .into_iter()
+                .map(|child| Box::new(child) as Box<dyn Element<C>>),
+        );
+        self
+    }
+
+    /// Push a child that is only rendered while `condition` returns `true`, see
+    /// [`HtmlElement::child_if`]
+    pub fn child_if<E: Element<C>>(
+        self,
+        condition: impl Fn(&State<C>) -> bool + 'static,
+        child: impl Fn() -> E + 'static,
+    ) -> Self {
+        self.child(conditional_child(condition, child))
+    }
+}
+
+/// A reactive child that is only constructed while `condition` returns `true`
+fn conditional_child<C: Component, E: Element<C>>(
+    condition: impl Fn(&State<C>) -> bool + 'static,
+    child: impl Fn() -> E + 'static,
+) -> impl Element<C> {
+    let condition = Rc::new(condition);
+    move |ctx: &mut RenderCtx<C>| {
+        let condition = Rc::clone(&condition);
+        ctx.watch(move |ctx| condition(ctx)).then(&child)
+    }
+}
+
+/// Render `left` while `condition` returns `true`, and `right` otherwise.
+///
+/// Only the shown branch is constructed. The condition is [`watch`](RenderCtx::watch)ed, so
+/// changes to the signals it reads only re-render the branch when the result flips.
+/// ```rust
+/// # use natrix::prelude::*;
+/// use natrix::state::State;
+/// # #[derive(Component)]
+/// # struct MyComponent {
+/// #     logged_in: bool,
+/// # }
+/// # impl Component for MyComponent {
+/// # type EmitMessage = NoMessages;
+/// # type ReceiveMessage = NoMessages;
+/// # fn render() -> impl Element<Self> {
+/// e::div().child(e::either(
+///     |ctx: &State<Self>| *ctx.logged_in,
+///     || e::button().text("Log out"),
+///     || e::button().text("Log in"),
+/// ))
+/// # }}
+/// ```
+pub fn either<C: Component, L: Element<C>, R: Element<C>>(
+    condition: impl Fn(&State<C>) -> bool + 'static,
+    left: impl Fn() -> L + 'static,
+    right: impl Fn() -> R + 'static,
+) -> impl Element<C> {
+    let condition = Rc::new(condition);
+    move |ctx: &mut RenderCtx<C>| {
+        let condition = Rc::clone(&condition);
+        if ctx.watch(move |ctx| condition(ctx)) {
+            Branch::Left(left())
+        } else {
+            Branch::Right(right())
+        }
+    }
+}
+
+impl<C: Component> Element<C> for Fragment<C> {
+    fn render_box(
+        self: Box<Self>,
+        ctx: &mut State<C>,
+        render_state: &mut RenderingState,
+    ) -> web_sys::Node {
+        render_fragment(self.children, ctx, render_state)
+    }
+}
+
+/// A fragment of sibling elements, rendered without a wrapper node.
+pub fn fragment<C: Component>() -> Fragment<C> {
+    Fragment {
+        children: Vec::new(),
+    }
+}
+
+/// A `<template>` element, whose child is rendered into the template content.
+///
+/// The content is inert and not displayed, use [`clone_content`] to get a copy of it.
+/// ```rust
+/// # use natrix::prelude::*;
+/// # let _: e::TemplateElement<e::HtmlElement<(), _>> =
+/// e::template(e::li().class("row").child(e::span().text("Item")))
+///     .id("row-template")
+/// # ;
+/// ```
+#[must_use = "Web elements are useless if not rendered"]
+pub struct TemplateElement<E> {
+    /// The content of the template
+    content: E,
+    /// The id of the template element
+    id: Option<&'static str>,
+}
+
+impl<E> TemplateElement<E> {
+    /// Set the id of the template, which is used to find it with [`clone_content`]
+    pub fn id(mut self, id: &'static str) -> Self {
+        self.id = Some(id);
+        self
+    }
+}
+
+impl<C: Component, E: Element<C>> Element<C> for TemplateElement<E> {
+    fn render_box(
+        self: Box<Self>,
+        ctx: &mut State<C>,
+        render_state: &mut RenderingState,
+    ) -> web_sys::Node {
+        let Ok(template) = get_document().create_element(intern("template")) else {
+            debug_assert!(false, "Failed to create element template");
+            ctx.render_failed = true;
+            return generate_fallback_node();
+        };
+        let Ok(template) = template.dyn_into::<web_sys::HtmlTemplateElement>() else {
+            debug_assert!(false, "<template> wasnt a HtmlTemplateElement");
+            ctx.render_failed = true;
+            return generate_fallback_node();
+        };
+        if let Some(id) = self.id {
+            template.set_id(id);
+        }
+
+        let hooks_before = render_state.hooks.len();
+        let keep_alive_before = render_state.keep_alive.len();
+        let content = self.content.render(ctx, render_state);
+        debug_assert!(
+            render_state.hooks.len() == hooks_before
+                && render_state.keep_alive.len() == keep_alive_before,
+            "Template content should be static, reactive closures and event handlers are not copied by `clone_content`"
+        );
+
+        debug_expect!(
+            template.content().append_child(&content),
+            "Failed to append template content"
+        );
+
+        template.into()
     }
 }
 
+/// `<template>`, the child is rendered into the template content rather than as a child.
+///
+/// The content should be static, see [`clone_content`].
+pub fn template<E>(content: E) -> TemplateElement<E> {
+    TemplateElement { content, id: None }
+}
+
+/// Get a deep clone of the content of the `<template>` with the given id.
+///
+/// This is faster than building the same markup with `HtmlElement` many times.
+/// The returned node can be used as a child element like any other node.
+///
+/// Reactive closures and event handlers do not survive cloning, the cloned nodes are plain dom
+/// nodes. Dynamic parts should be added around the clone instead:
+/// ```rust
+/// # use natrix::prelude::*;
+/// # #[derive(Component)]
+/// # struct MyComponent {
+/// #     count: u32,
+/// # }
+/// # impl Component for MyComponent {
+/// # type EmitMessage = NoMessages;
+/// # type ReceiveMessage = NoMessages;
+/// # fn render() -> impl Element<Self> {
+/// e::div()
+///     .child(e::clone_content("row-template"))
+///     .child(|ctx: R<Self>| *ctx.count)
+/// # }}
+/// ```
+///
+/// Returns `None` if no `<template>` with the given id is in the document, so the template has
+/// to be rendered before this is called.
+#[must_use]
+pub fn clone_content(id: &str) -> Option<web_sys::Node> {
+    let document = get_document();
+    let template = document
+        .get_element_by_id(id)?
+        .dyn_into::<web_sys::HtmlTemplateElement>()
+        .ok()?;
+    document
+        .import_node_with_deep(&template.content(), true)
+        .ok()
+}
+
 /// Wrap the given function in the needed reactivity machinery and set it as the event handler for
-/// the specified event
+/// the specified event, in the capture phase if `capture` is set.
 fn create_event_handler<C: Component>(
     element: &web_sys::Element,
     event: &str,
-    function: Box<dyn Fn(&mut State<C>, web_sys::Event)>,
+    capture: bool,
+    function: EventCallback<C>,
     ctx_weak: DeferredCtx<C>,
     render_state: &mut RenderingState<'_>,
 ) {
+    let suppress_when_disabled = DISABLED_EVENTS.contains(&event);
     let callback: Box<dyn Fn(web_sys::Event) + 'static> = Box::new(move |event| {
         crate::return_if_panic!();
 
+        if suppress_when_disabled {
+            let disabled = event
+                .current_target()
+                .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+                .is_some_and(|target| target.has_attribute("disabled"));
+            if disabled {
+                return;
+            }
+        }
+
         let Some(mut ctx) = ctx_weak.borrow_mut() else {
             debug_assert!(
                 false,
@@ -340,15 +2040,15 @@ fn create_event_handler<C: Component>(
             return;
         };
 
-        ctx.clear();
+        // The borrow cleared the signals, and runs `update` when dropped. So the update also
+        // happens if the handler unwinds, instead of leaving the written flags for the next event.
         function(&mut ctx, event);
-        ctx.update();
     });
     let closure = Closure::wrap(callback);
     let function = closure.as_ref().unchecked_ref();
 
     debug_expect!(
-        element.add_event_listener_with_callback(intern(event), function),
+        element.add_event_listener_with_callback_and_bool(intern(event), function, capture),
         "Failed to attach event handler"
     );
 
@@ -414,7 +2114,6 @@ blockquote, dd, div, dl, dt, figcaption, figure, hr, li, menu, ol, p, pre, ul,
 a, abbr, b, bdi, bdo, br, cite, code, data, dfn, em, i, kbd, mark, q, rp, rt, ruby, s, samp, small, span, strong, sub, sup, time, u, var, wbr,
 area, audio, img, map, track, video,
 embed, fencedframe, iframe, object, picture, source,
-svg, math,
 canvas, script,
 del, ins,
 caption, col, colgroup, table, tbody, td, tfoot, th, thead, tr,
@@ -422,42 +2121,319 @@ button, datalist, fieldset, form, input, label, legend, meter, optgroup, option,
 details, dialog, summary
 }
 
+/// The namespaces commonly used with [`HtmlElement::new_ns`] and [`HtmlElement::attr_ns`]
+pub mod namespaces {
+    /// The html namespace, used by elements created with
+    /// [`HtmlElement::new`](super::HtmlElement::new)
+    pub const HTML: &str = "http://www.w3.org/1999/xhtml";
+    /// The svg namespace, for `<svg>` and its children
+    pub const SVG: &str = "http://www.w3.org/2000/svg";
+    /// The `MathML` namespace, for `<math>` and its children
+    pub const MATHML: &str = "http://www.w3.org/1998/Math/MathML";
+    /// The xlink namespace, for the deprecated `xlink:*` svg attributes such as `xlink:href`
+    pub const XLINK: &str = "http://www.w3.org/1999/xlink";
+    /// The xml namespace, for `xml:*` attributes such as `xml:lang`
+    pub const XML: &str = "http://www.w3.org/XML/1998/namespace";
+    /// The xmlns namespace, for declaring namespace prefixes with `xmlns:*` attributes
+    pub const XMLNS: &str = "http://www.w3.org/2000/xmlns/";
+}
+
+#[doc(hidden)]
+#[expect(non_camel_case_types, reason = "Matches the `elements!` structs")]
+pub struct _math;
+
+/// `<math>`, created in the [`MathML` namespace](namespaces::MATHML).
+pub fn math<C: Component>() -> HtmlElement<C, _math> {
+    HtmlElement::new_ns(namespaces::MATHML, "math")
+}
+
+#[doc(hidden)]
+#[expect(non_camel_case_types, reason = "Matches the `elements!` structs")]
+pub struct _svg;
+
+/// `<svg>`, created in the [svg namespace](svg::NAMESPACE).
+///
+/// See the [`svg`](mod@svg) module for the other svg elements.
+pub fn svg<C: Component>() -> HtmlElement<C, _svg> {
+    HtmlElement::new_ns(svg::NAMESPACE, "svg")
+}
+
+/// Svg elements, which need to be created in the svg namespace to be displayed.
+pub mod svg {
+    use super::HtmlElement;
+    use crate::component::Component;
+
+    /// The svg namespace, the same as [`namespaces::SVG`](super::namespaces::SVG)
+    pub const NAMESPACE: &str = super::namespaces::SVG;
+
+    #[doc(hidden)]
+    #[expect(non_camel_case_types, reason = "Matches the `elements!` structs")]
+    pub struct _use;
+
+    /// `<use>`, named `use_` as `use` is a keyword.
+    pub fn use_<C: Component>() -> HtmlElement<C, _use> {
+        HtmlElement::new_ns(NAMESPACE, "use")
+    }
+
+    impl<C: Component> HtmlElement<C, _use> {
+        /// Set the `href` attribute, the element to clone, such as `#icon-home`.
+        ///
+        /// This uses the svg 2 `href` attribute, which replaced the deprecated `xlink:href`.
+        /// Use [`attr_ns`](HtmlElement::attr_ns) with
+        /// [`namespaces::XLINK`](super::namespaces::XLINK) if you need to support older browsers.
+        pub fn href(self, value: impl super::ToAttribute<C>) -> Self {
+            self.attr("href", value)
+        }
+    }
+
+    impl<C: Component> HtmlElement<C, super::_svg> {
+        /// Set the `viewBox` attribute
+        pub fn view_box(self, value: impl super::ToAttribute<C>) -> Self {
+            self.attr("viewBox", value)
+        }
+    }
+
+    /// A icon from a svg sprite, `<svg><use href="{href}"/></svg>`
+    ///
+    /// The returned `<svg>` can be sized and styled like any other element.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # let _: e::HtmlElement<(), _> =
+    /// e::svg::icon("#icon-home").class("icon").width(24).height(24)
+    /// # ;
+    /// ```
+    pub fn icon<C: Component>(href: &'static str) -> HtmlElement<C, super::_svg> {
+        super::svg().child(use_().href(href))
+    }
+}
+
 attr_helpers! {
     a => href, target, rel, download, hreflang, referrerpolicy;
     audio => autoplay, controls, muted, preload, src;
-    button => disabled, form, formaction, formenctype, formmethod, formnovalidate, formtarget, name, value;
+    button => form, formaction, formenctype, formmethod, formnovalidate, formtarget, name, value;
     canvas => height, width;
     col => span;
     colgroup => span;
     details => open;
     embed => height, src, width;
-    fieldset => disabled, form;
+    fieldset => form;
     form => acceptcharset, action, autocomplete, enctype, method, name, novalidate, target;
     iframe => allow, allowfullscreen, allowpaymentrequest, height, loading, name, referrerpolicy, sandbox, src, width;
     img => alt, crossorigin, decoding, height, ismap, loading, referrerpolicy, sizes, src, srcset, usemap, width;
-    input => accept, alt, autocomplete, checked, dirname, disabled, form, formaction, formenctype, formmethod, formnovalidate, formtarget, height, list, max, maxlength, min, minlength, multiple, name, pattern, placeholder, readonly, required, size, src, step, value;
+    input => accept, alt, autocomplete, checked, dirname, form, formaction, formenctype, formmethod, formnovalidate, formtarget, height, list, max, maxlength, min, minlength, multiple, name, pattern, placeholder, readonly, required, size, src, step, value;
     li => value;
     map => name;
     meter => form, high, low, max, min, optimum, value;
     object => data, form, height, name, usemap, width;
     ol => reversed, start;
-    optgroup => disabled, label;
-    option => disabled, label, selected, value;
+    optgroup => label;
+    option => label, selected, value;
     picture => srcset;
     progress => max, value;
     script => crossorigin, defer, integrity, nomodule, referrerpolicy, src;
-    select => autocomplete, disabled, form, multiple, name, required, size;
+    select => autocomplete, form, multiple, name, required, size;
     source => media, sizes, src, srcset;
+    svg => height, width;
     summary => open;
     table => summary;
-    textarea => autocomplete, cols, dirname, disabled, form, maxlength, minlength, name, placeholder, readonly, required, rows, wrap;
+    textarea => autocomplete, cols, dirname, form, maxlength, minlength, name, placeholder, readonly, required, rows, wrap;
     time => datetime;
     track => default, kind, label, src, srclang;
     video => autoplay, controls, crossorigin, height, muted, playsinline, poster, preload, src, width;
 }
 
+impl<C: Component> HtmlElement<C, _a> {
+    /// Set the `href` attribute to a internal route, navigating to it client side when clicked.
+    ///
+    /// The `href` is still set so the link works for accessibility tools, crawlers and "open in
+    /// new tab". Clicks with modifier keys (ctrl/meta/shift/alt), non-primary buttons, links with
+    /// a `target` and external urls fall through to normal browser navigation.
+    ///
+    /// See [`navigation::navigate`](crate::navigation::navigate) for how the navigation is
+    /// performed.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # let _: e::HtmlElement<(), _> =
+    /// e::a().href_route("/about").text("About")
+    /// # ;
+    /// ```
+    pub fn href_route(self, path: impl Into<Cow<'static, str>>) -> Self {
+        let path = path.into();
+        let route = path.clone();
+
+        self.href(path).on::<events::Click>(
+            move |_ctx: &mut State<C>, event: web_sys::PointerEvent| {
+                if !navigation::is_internal(&route) || !navigation::is_plain_click(&event) {
+                    return;
+                }
+
+                let has_target = event
+                    .current_target()
+                    .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+                    .and_then(|target| target.get_attribute("target"))
+                    .is_some_and(|target| !target.is_empty() && target != "_self");
+                if has_target {
+                    return;
+                }
+
+                event.prevent_default();
+                navigation::navigate(&route);
+            },
+        )
+    }
+}
+
+impl<C: Component> HtmlElement<C, _form> {
+    /// Register a handler for the form being submitted, which gets the submitted values.
+    ///
+    /// The default submission (navigating to the `action`) is prevented, and the values are read
+    /// with [`FormData`](web_sys::FormData) from the named controls of the form. File inputs are
+    /// left as `File` objects in the form data, so they can still be read with `get`.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     username: String,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::form()
+    ///     .child(e::input().name("username"))
+    ///     .child(e::button().text("Login"))
+    ///     .on_submit(|ctx: E<Self>, data: web_sys::FormData| {
+    ///         *ctx.username = data.get("username").as_string().unwrap_or_default();
+    ///     })
+    /// # }}
+    /// ```
+    /// This is registered as a normal `submit` handler, so it composes with other handlers.
+    pub fn on_submit(self, function: impl Fn(&mut State<C>, web_sys::FormData) + 'static) -> Self {
+        self.on::<events::Submit>(move |ctx: &mut State<C>, event: web_sys::SubmitEvent| {
+            event.prevent_default();
+
+            let Some(form) = event
+                .current_target()
+                .and_then(|target| target.dyn_into::<web_sys::HtmlFormElement>().ok())
+            else {
+                debug_assert!(false, "Submit event target is not a form");
+                return;
+            };
+            let Ok(data) = web_sys::FormData::new_with_form(&form) else {
+                debug_assert!(false, "Failed to read form data");
+                return;
+            };
+
+            function(ctx, data);
+        })
+    }
+}
+
+impl<C: Component> HtmlElement<C, _img> {
+    /// Show the image at `url` until this image has loaded.
+    ///
+    /// The placeholder is rendered as a separate `<img>` before this one, which is `hidden` until
+    /// its `load` event fires, at which point the placeholder is removed. Use
+    /// [`fallback`](ImagePlaceholder::fallback) to show a different image if loading fails.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     avatar: String,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::img()
+    ///     .src(|ctx: R<Self>| ctx.avatar.clone())
+    ///     .alt("Avatar")
+    ///     .placeholder("/spinner.svg")
+    ///     .fallback("/broken-image.svg")
+    /// # }}
+    /// ```
+    pub fn placeholder(self, url: &'static str) -> ImagePlaceholder<C> {
+        ImagePlaceholder {
+            image: self,
+            placeholder: url,
+            fallback: None,
+        }
+    }
+}
+
+/// A image showing a placeholder until it has loaded, see [`HtmlElement::placeholder`]
+#[must_use = "Web elements are useless if not rendered"]
+pub struct ImagePlaceholder<C: Component> {
+    /// The image to load
+    image: HtmlElement<C, _img>,
+    /// The url of the image to show while loading
+    placeholder: &'static str,
+    /// The url of the image to show if loading fails
+    fallback: Option<&'static str>,
+}
+
+impl<C: Component> ImagePlaceholder<C> {
+    /// Show the image at `url` instead if the image fails to load.
+    ///
+    /// Without a fallback the placeholder stays visible.
+    pub fn fallback(mut self, url: &'static str) -> Self {
+        self.fallback = Some(url);
+        self
+    }
+}
+
+impl<C: Component> Element<C> for ImagePlaceholder<C> {
+    fn render_box(
+        self: Box<Self>,
+        ctx: &mut State<C>,
+        render_state: &mut RenderingState,
+    ) -> web_sys::Node {
+        let placeholder_ref = NodeRef::new();
+        let placeholder = img()
+            .src(self.placeholder)
+            .alt("")
+            .attr("aria-hidden", "true")
+            .node_ref(&placeholder_ref);
+
+        let fallback = self.fallback;
+        let image = self
+            .image
+            .hidden(true)
+            .on::<events::Load>(move |_ctx: &mut State<C>, event: web_sys::Event| {
+                if let Some(image) = event_element(&event) {
+                    debug_expect!(image.remove_attribute("hidden"), "Failed to show image");
+                }
+                if let Some(placeholder) = placeholder_ref.get() {
+                    placeholder.remove();
+                }
+            })
+            .on::<events::Error>(move |_ctx: &mut State<C>, event: web_sys::Event| {
+                let (Some(image), Some(fallback)) = (event_element(&event), fallback) else {
+                    return;
+                };
+                // Avoid looping if the fallback fails to load as well
+                if image.get_attribute("src").as_deref() != Some(fallback) {
+                    debug_expect!(
+                        image.set_attribute("src", fallback),
+                        "Failed to set fallback"
+                    );
+                }
+            });
+
+        fragment()
+            .child(placeholder)
+            .child(image)
+            .render(ctx, render_state)
+    }
+}
+
+/// The element a event was dispatched to
+fn event_element(event: &web_sys::Event) -> Option<web_sys::Element> {
+    event.target()?.dyn_into().ok()
+}
+
 // https://developer.mozilla.org/en-US/docs/Web/HTML/Reference/Global_attributes
 global_attrs! {
     autocapitalize, autofocus, enterkeyhint, inert, inputmode, nonce, role, writingsuggestions,
-    accesskey, contenteditable, contextmenu, dir, draggable, dropzone, hidden, id, lang, spellcheck, style, tabindex, title, translate
+    accesskey, contextmenu, dir, draggable, dropzone, hidden, id, lang, spellcheck, style, title, translate
 }
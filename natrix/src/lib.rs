@@ -3,7 +3,6 @@
     clippy::todo,
     clippy::unreachable,
     clippy::unwrap_used,
-    clippy::unreachable,
     clippy::indexing_slicing,
     unsafe_code
 )]
@@ -34,16 +33,27 @@
 pub mod async_utils;
 pub mod callbacks;
 pub mod component;
+pub mod cookies;
+pub mod custom_element;
 pub mod element;
 pub mod events;
 pub mod html_elements;
 pub mod list;
+pub mod map_signal;
+pub mod navigation;
+pub mod profiling;
 mod render_callbacks;
+pub mod shared;
 mod signal;
 pub mod state;
 pub mod test_utils;
+#[cfg(feature = "testing")]
+pub use test_utils as testing;
+pub mod time;
+mod title;
 mod type_macros;
 mod utils;
+pub mod watchers;
 
 thread_local! {
     /// A lazy initlized reference to the js document.
@@ -120,10 +130,67 @@ If you which to allow execution after a panic (not recommended) you can disable
             #[cfg(debug_assertions)]
             {
                 let panic_message = info.to_string();
+                show_overlay(&panic_message);
                 web_sys::console::error_1(&panic_message.into());
             }
         }));
     }
+
+    /// Id of the panic overlay element
+    #[cfg(debug_assertions)]
+    const OVERLAY_ID: &str = "natrix-panic-overlay";
+
+    /// Insert a overlay into the page showing the panic message.
+    ///
+    /// This is anchored to the bottom of the page to not obscure the whole app.
+    /// The dismiss button only removes the overlay, so it does not touch any framework state after
+    /// the panic. It uses a event listener rather than a inline `onclick` handler, as those are
+    /// blocked by most content security policies.
+    /// This avoids the cached document as we might be panicking while initializing it.
+    #[cfg(debug_assertions)]
+    fn show_overlay(panic_message: &str) {
+        use wasm_bindgen::JsCast;
+        use wasm_bindgen::closure::Closure;
+
+        let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+            return;
+        };
+        let Some(body) = document.body() else {
+            return;
+        };
+        if document.get_element_by_id(OVERLAY_ID).is_some() {
+            return;
+        }
+
+        let (Ok(overlay), Ok(message), Ok(dismiss)) = (
+            document.create_element("div"),
+            document.create_element("pre"),
+            document.create_element("button"),
+        ) else {
+            return;
+        };
+
+        overlay.set_id(OVERLAY_ID);
+        let _ = overlay.set_attribute("role", "alert");
+        let _ = overlay.set_attribute(
+            "style",
+            "position:fixed;left:0;right:0;bottom:0;z-index:2147483647;max-height:50vh;overflow:auto;\
+             margin:0;padding:1em;background:#200;color:#fcc;border-top:3px solid #f44;font-family:monospace;",
+        );
+
+        message.set_text_content(Some(panic_message));
+        let _ = message.set_attribute("style", "margin:0;white-space:pre-wrap;");
+
+        dismiss.set_text_content(Some("Dismiss"));
+        let _ = dismiss.set_attribute("style", "float:right;");
+        let remove_overlay = overlay.clone();
+        let on_dismiss = Closure::once_into_js(move || remove_overlay.remove());
+        let _ = dismiss.add_event_listener_with_callback("click", on_dismiss.unchecked_ref());
+
+        let _ = overlay.append_child(&dismiss);
+        let _ = overlay.append_child(&message);
+        let _ = body.append_child(&overlay);
+    }
 }
 
 #[cfg(feature = "panic_hook")]
@@ -155,13 +222,21 @@ pub mod prelude {
     pub use natrix_macros::scoped_css;
     #[cfg(feature = "inline_css")]
     pub use natrix_macros::style;
-    pub use natrix_macros::{Component, global_css};
+    pub use natrix_macros::{Component, SelectOptions, global_css};
 
     pub use super::callbacks::EventHandler;
     pub use super::component::{C, Component, NoMessages, mount};
     pub use super::element::Element;
     pub use super::state::{E, R};
-    pub use super::{events, guard_option, guard_result, html_elements as e};
+    pub use super::{
+        events,
+        for_each,
+        guard_option,
+        guard_result,
+        guard_tuple,
+        html_elements as e,
+        match_guard,
+    };
 }
 
 /// Public exports of internal data structures for `natrix_macros` to use in generated code.
@@ -1,6 +1,10 @@
 //! Struct and traits for handling loops
 
+use std::any::Any;
 use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::rc::Rc;
 
 use crate::component::Component;
 use crate::element::Element;
@@ -11,6 +15,10 @@ use crate::utils::{SmallAny, debug_expect};
 
 /// List lets you efficiently render a list of items
 /// It takes a function that returns a vector of items and a render function
+///
+/// Event handlers registered with
+/// [`on_item`](crate::html_elements::HtmlElement::on_item) inside a item receive its index as a
+/// `usize`.
 pub struct List<F, R, C, I, E> {
     /// The function that returns the vector of items
     vec_get: F,
@@ -170,9 +178,12 @@ where
                         keep_alive: &mut keep_alive,
                         hooks: &mut hooks,
                         parent_dep: you,
+                        pinned: false,
                     };
 
-                    let node = hook.render(ctx, &mut render_state);
+                    let key: Rc<dyn Any> = Rc::new(item_index);
+                    let node =
+                        ctx.with_item_key(Some(key), |ctx| hook.render(ctx, &mut render_state));
                     let previous = self
                         .existing_hooks
                         .last()
@@ -245,3 +256,368 @@ where
         fragment.into()
     }
 }
+
+/// A list where items are tracked by a key rather than their position.
+///
+/// Items are only rendered when a new key appears, when the items are reordered the existing
+/// nodes are moved instead of re-rendered. Each item should render to a single node.
+///
+/// Keys should be unique within the list. A duplicate key is a debug assertion, in release
+/// builds only the first item with a given key is rendered and the others are dropped.
+///
+/// This is generally used via the [`for_each!`](crate::for_each) macro.
+pub struct KeyedList<F, Kf, R, C, I, K, E> {
+    /// The function that returns the vector of items
+    vec_get: F,
+    /// The function that returns the key of a item
+    key: Kf,
+    /// The function that renders each item
+    render: R,
+    /// Make trait impls happy
+    marker: std::marker::PhantomData<(C, I, K, E)>,
+}
+
+impl<F, Kf, R, C, I, K, E> KeyedList<F, Kf, R, C, I, K, E>
+where
+    F: Fn(&State<C>) -> &Vec<I> + 'static,
+    Kf: Fn(&I) -> K + 'static,
+    K: Hash + Eq + Clone + 'static,
+    R: Fn(Ra<C>, KeyedGetter<F, Kf, K>) -> E + 'static,
+{
+    /// Creates a new keyed list
+    /// The render method will be called with a `KeyedGetter` that can be used to get the item
+    pub fn new(vec_get: F, key: Kf, render: R) -> Self {
+        Self {
+            vec_get,
+            key,
+            render,
+            marker: std::marker::PhantomData,
+        }
+    }
+}
+
+/// A getter for the items of a `KeyedList`
+/// It is used to get the item with the given key
+#[derive(Clone)]
+#[cfg_attr(feature = "nightly", must_not_suspend)]
+pub struct KeyedGetter<F, Kf, K> {
+    /// The key of the item
+    pub key: K,
+    /// The function that returns the vector of items
+    getter: F,
+    /// The function that returns the key of a item
+    key_fn: Kf,
+}
+
+impl<F, Kf, K> KeyedGetter<F, Kf, K> {
+    /// Same as `.get` but uses `ctx.watch` to ensure we only update when the item changes
+    pub fn get_watched<C, I>(&self, ctx: &mut RenderCtx<C>) -> Option<I>
+    where
+        F: Fn(&State<C>) -> &Vec<I> + 'static,
+        Kf: Fn(&I) -> K + 'static,
+        K: PartialEq + 'static,
+        C: Component,
+        I: Clone + PartialEq + 'static,
+        Self: Clone,
+    {
+        let this = self.clone();
+        ctx.watch(move |ctx| this.get(ctx).cloned())
+    }
+
+    /// Get the item with the given key, or `None` if the list no longer contains it.
+    ///
+    /// The list drops the hooks of removed keys before they can run, so this is only `None` if
+    /// the getter is kept around after its item was removed.
+    /// **This does not use `ctx.watch`**, see [`SafeGetter::get`] for details.
+    pub fn get<'s, C, I>(&self, ctx: &'s State<C>) -> Option<&'s I>
+    where
+        F: Fn(&State<C>) -> &Vec<I>,
+        Kf: Fn(&I) -> K,
+        K: PartialEq,
+        C: Component,
+    {
+        let vec = (self.getter)(ctx);
+        vec.iter().find(|item| (self.key_fn)(item) == self.key)
+    }
+}
+
+/// State of a rendered keyed list item
+struct KeyedItemState<K> {
+    /// The key of this item
+    key: K,
+    /// Comment placed before the rendered content, the content might be swapped out by its own
+    /// hooks or be a fragment so we track it as the nodes between the two markers
+    start_marker: web_sys::Node,
+    /// Comment placed after the rendered content
+    end_marker: web_sys::Node,
+    /// The hooks that are used to render this item
+    hooks: Vec<HookKey>,
+    /// The keep alive objects that are used to render this item
+    #[expect(dead_code, reason = "This is used to keep alive various objects")]
+    keep_alive: Vec<Box<dyn SmallAny>>,
+}
+
+impl<K> KeyedItemState<K> {
+    /// The markers of this item and the nodes between them, in order
+    fn nodes(&self) -> Vec<web_sys::Node> {
+        let mut nodes = Vec::new();
+        let mut current = Some(self.start_marker.clone());
+        while let Some(node) = current {
+            let is_end = node.is_same_node(Some(&self.end_marker));
+            current = node.next_sibling();
+            nodes.push(node);
+            if is_end {
+                break;
+            }
+        }
+        nodes
+    }
+
+    /// Move this item and its content before `next`
+    fn move_before(&self, parent: &web_sys::Node, next: Option<&web_sys::Node>) {
+        for node in self.nodes() {
+            debug_expect!(parent.insert_before(&node, next), "Failed to move node");
+        }
+    }
+
+    /// Remove this item and its content from the dom
+    fn remove(&self, parent: &web_sys::Node) {
+        for node in self.nodes() {
+            debug_expect!(parent.remove_child(&node), "Failed to remove node");
+        }
+    }
+}
+
+/// The reactive hook state for `KeyedList`
+struct KeyedListState<F, Kf, R, C, I, K, E> {
+    /// The list that is being rendered
+    list: KeyedList<F, Kf, R, C, I, K, E>,
+    /// The currently rendered items, in order
+    items: Vec<KeyedItemState<K>>,
+    /// The start marker for the list
+    start_marker: web_sys::Node,
+}
+
+impl<F, Kf, R, C, I, K, E> KeyedListState<F, Kf, R, C, I, K, E>
+where
+    C: Component,
+    E: Element<C>,
+    F: Fn(&State<C>) -> &Vec<I> + Clone + 'static,
+    Kf: Fn(&I) -> K + Clone + 'static,
+    K: Hash + Eq + Clone + 'static,
+    R: Fn(Ra<C>, KeyedGetter<F, Kf, K>) -> E + Clone + 'static,
+{
+    /// Render a new item, inserting it before `next`
+    fn render_item(
+        &self,
+        ctx: &mut State<C>,
+        you: HookKey,
+        key: K,
+        parent: &web_sys::Node,
+        next: Option<&web_sys::Node>,
+    ) -> KeyedItemState<K> {
+        let getter = KeyedGetter {
+            key: key.clone(),
+            getter: self.list.vec_get.clone(),
+            key_fn: self.list.key.clone(),
+        };
+        let render_clone = self.list.render.clone();
+        let hook = move |ctx: Ra<C>| render_clone(ctx, getter.clone());
+
+        let mut hooks = Vec::new();
+        let mut keep_alive = Vec::new();
+        let mut render_state = RenderingState {
+            keep_alive: &mut keep_alive,
+            hooks: &mut hooks,
+            parent_dep: you,
+            pinned: false,
+        };
+        let item_key: Rc<dyn Any> = Rc::new(key.clone());
+        let node = ctx.with_item_key(Some(item_key), |ctx| hook.render(ctx, &mut render_state));
+
+        let document = crate::get_document();
+        let start_marker: web_sys::Node = document.create_comment("item start").into();
+        let end_marker: web_sys::Node = document.create_comment("item end").into();
+        debug_expect!(
+            parent.insert_before(&start_marker, next),
+            "Failed to insert start marker"
+        );
+        debug_expect!(parent.insert_before(&node, next), "Failed to insert node");
+        debug_expect!(
+            parent.insert_before(&end_marker, next),
+            "Failed to insert end marker"
+        );
+
+        KeyedItemState {
+            key,
+            start_marker,
+            end_marker,
+            hooks,
+            keep_alive,
+        }
+    }
+}
+
+impl<F, Kf, R, C, I, K, E> ReactiveHook<C> for KeyedListState<F, Kf, R, C, I, K, E>
+where
+    C: Component,
+    E: Element<C>,
+    F: Fn(&State<C>) -> &Vec<I> + Clone + 'static,
+    Kf: Fn(&I) -> K + Clone + 'static,
+    K: Hash + Eq + Clone + 'static,
+    I: 'static,
+    R: Fn(Ra<C>, KeyedGetter<F, Kf, K>) -> E + Clone + 'static,
+{
+    fn update(&mut self, ctx: &mut State<C>, you: HookKey) -> UpdateResult {
+        ctx.clear();
+        let keys: Vec<K> = (self.list.vec_get)(ctx)
+            .iter()
+            .map(&self.list.key)
+            .collect();
+        ctx.reg_dep(you);
+
+        let Some(parent) = self.start_marker.parent_node() else {
+            debug_assert!(false, "Parent node not found");
+            return UpdateResult::Nothing;
+        };
+
+        let mut existing: HashMap<K, KeyedItemState<K>> = std::mem::take(&mut self.items)
+            .into_iter()
+            .map(|item| (item.key.clone(), item))
+            .collect();
+        let mut seen = HashSet::with_capacity(keys.len());
+        let mut items = Vec::with_capacity(keys.len());
+
+        let mut cursor = self.start_marker.clone();
+        for key in keys {
+            if !seen.insert(key.clone()) {
+                debug_assert!(false, "Duplicate key in keyed list");
+                continue;
+            }
+
+            let next = cursor.next_sibling();
+            let item = if let Some(item) = existing.remove(&key) {
+                if next.as_ref() != Some(&item.start_marker) {
+                    item.move_before(&parent, next.as_ref());
+                }
+                item
+            } else {
+                self.render_item(ctx, you, key, &parent, next.as_ref())
+            };
+
+            cursor = item.end_marker.clone();
+            items.push(item);
+        }
+        self.items = items;
+
+        let mut to_drop = Vec::new();
+        for item in existing.into_values() {
+            item.remove(&parent);
+            to_drop.extend(item.hooks);
+        }
+
+        if to_drop.is_empty() {
+            UpdateResult::Nothing
+        } else {
+            UpdateResult::DropHooks(to_drop)
+        }
+    }
+
+    fn drop_us(self: Box<Self>) -> Vec<HookKey> {
+        self.items.into_iter().flat_map(|item| item.hooks).collect()
+    }
+}
+
+impl<F, Kf, R, C, I, K, E> Element<C> for KeyedList<F, Kf, R, C, I, K, E>
+where
+    C: Component,
+    E: Element<C>,
+    F: Fn(&State<C>) -> &Vec<I> + Clone + 'static,
+    Kf: Fn(&I) -> K + Clone + 'static,
+    K: Hash + Eq + Clone + 'static,
+    I: 'static,
+    R: Fn(Ra<C>, KeyedGetter<F, Kf, K>) -> E + Clone + 'static,
+{
+    fn render_box(
+        self: Box<Self>,
+        ctx: &mut State<C>,
+        render_state: &mut RenderingState,
+    ) -> web_sys::Node {
+        let document = crate::get_document();
+        let fragment = document.create_document_fragment();
+        let start_marker = document.create_comment("keyed list start");
+
+        debug_expect!(
+            fragment.append_child(&start_marker),
+            "Failed to append start marker"
+        );
+
+        let mut state = KeyedListState {
+            list: *self,
+            items: Vec::new(),
+            start_marker: start_marker.clone().into(),
+        };
+
+        let you = ctx.insert_hook(Box::new(DummyHook));
+        state.update(ctx, you);
+        ctx.set_hook(you, Box::new(state));
+        render_state.hooks.push(you);
+
+        fragment.into()
+    }
+}
+
+/// Render a keyed list of items, similar to `{#each}` in svelte.
+///
+/// The key defaults to the `id` field of the item, but can be given explicitly with `key = `.
+/// The item is given by value and the item is re-rendered when it changes,
+/// see [`KeyedList`] for more details.
+///
+/// Event handlers registered with
+/// [`on_item`](crate::html_elements::HtmlElement::on_item) inside the body receive the key of
+/// their item.
+///
+/// ```rust
+/// # use natrix::prelude::*;
+/// #[derive(Clone, PartialEq)]
+/// struct User {
+///     id: u32,
+///     name: String,
+/// }
+///
+/// #[derive(Component)]
+/// struct Users {
+///     users: Vec<User>,
+///     tags: Vec<&'static str>,
+/// }
+///
+/// impl Component for Users {
+///     # type EmitMessage = NoMessages;
+///     # type ReceiveMessage = NoMessages;
+///     fn render() -> impl Element<Self> {
+///         e::div()
+///             .child(e::ul().child(for_each!(|ctx| ctx.users => |user| e::li().text(user.name))))
+///             .child(e::ul().child(for_each!(
+///                 |ctx| ctx.tags, key = |tag| *tag => |tag| e::li().text(tag)
+///             )))
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! for_each {
+    (| $ctx:ident | $items:expr => | $item:ident | $body:expr) => {
+        ::natrix::for_each!(|$ctx| $items, key = |$item| $item.id.clone() => |$item| $body)
+    };
+    (| $ctx:ident | $items:expr, key = | $key_item:ident | $key:expr => | $item:ident | $body:expr) => {
+        ::natrix::list::KeyedList::new(
+            move |$ctx: &::natrix::state::State<Self>| &$items,
+            move |$key_item: &_| $key,
+            move |_ctx: ::natrix::state::R<Self>, getter| {
+                move |ctx: ::natrix::state::R<Self>| {
+                    let $item = getter.get_watched(ctx)?;
+                    Some($body)
+                }
+            },
+        )
+    };
+}
@@ -0,0 +1,294 @@
+//! A map signal that tracks reads and writes per key
+
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::component::ComponentBase;
+use crate::signal::SignalMethods;
+use crate::state::{ComponentData, HookKey};
+
+/// A `HashMap` whose entries are tracked individually.
+///
+/// Callbacks that only read specific keys (with [`get`](Self::get) or
+/// [`contains_key`](Self::contains_key)) only re-run when one of those keys is inserted, modified
+/// or removed. Callbacks that read the map as a whole (such as [`iter`](Self::iter) or
+/// [`len`](Self::len)) re-run on any change.
+///
+/// The field has to be marked `#[nested]`, otherwise it is tracked as a single value.
+/// ```rust
+/// # use natrix::prelude::*;
+/// use natrix::map_signal::MapSignal;
+///
+/// #[derive(Component)]
+/// struct Scores {
+///     #[nested]
+///     scores: MapSignal<&'static str, u32>,
+/// }
+///
+/// impl Component for Scores {
+///     # type EmitMessage = NoMessages;
+///     # type ReceiveMessage = NoMessages;
+///     fn render() -> impl Element<Self> {
+///         e::div()
+///             // Only re-runs when the "alice" entry changes
+///             .child(|ctx: R<Self>| ctx.scores.get(&"alice").copied().unwrap_or(0))
+///             // Re-runs on every change
+///             .child(|ctx: R<Self>| ctx.scores.len())
+///             .child(e::button().text("Bob scored").on::<events::Click>(|ctx: E<Self>, _| {
+///                 if let Some(score) = ctx.scores.get_mut(&"bob") {
+///                     *score += 1;
+///                 }
+///             }))
+///     }
+/// }
+/// ```
+pub struct MapSignal<K, V> {
+    /// The tracked map
+    data: HashMap<K, V>,
+    /// The read and write tracking
+    tracker: KeyTracker<K>,
+}
+
+/// The read and write tracking of a [`MapSignal`]
+struct KeyTracker<K> {
+    /// The keys read individually
+    /// this is a `RefCell` to allow for modification in `get`
+    read_keys: RefCell<HashSet<K>>,
+    /// The flag for whether the map was read as a whole
+    read_all: Cell<bool>,
+    /// The keys that have been written to
+    written_keys: HashSet<K>,
+    /// The dependencies of individual keys
+    key_deps: HashMap<K, Vec<HookKey>>,
+    /// The dependencies reading the whole map
+    deps: Vec<HookKey>,
+}
+
+/// The read and written keys of a [`MapSignal`] extracted
+#[doc(hidden)]
+pub struct MapSignalState<K> {
+    /// The keys read individually
+    read_keys: HashSet<K>,
+    /// Was the map read as a whole
+    read_all: bool,
+    /// The keys that have been written to
+    written_keys: HashSet<K>,
+}
+
+impl<K: Hash + Eq + Clone> KeyTracker<K> {
+    /// Mark the key as read
+    fn read(&self, key: &K) {
+        let mut read_keys = self.read_keys.borrow_mut();
+        if !read_keys.contains(key) {
+            read_keys.insert(key.clone());
+        }
+    }
+
+    /// Mark the key as written
+    fn write(&mut self, key: &K) {
+        if !self.written_keys.contains(key) {
+            self.written_keys.insert(key.clone());
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> SignalMethods for KeyTracker<K> {
+    fn clear(&mut self) {
+        self.read_keys.get_mut().clear();
+        self.read_all.set(false);
+        self.written_keys.clear();
+    }
+
+    fn register_dep(&mut self, dep: HookKey) {
+        for key in self.read_keys.get_mut().iter() {
+            self.key_deps.entry(key.clone()).or_default().push(dep);
+        }
+        if self.read_all.get() {
+            self.deps.push(dep);
+        }
+    }
+
+    fn drain_changed_deps(&mut self, hooks: &mut Vec<HookKey>) {
+        if self.written_keys.is_empty() {
+            return;
+        }
+        for key in &self.written_keys {
+            if let Some(mut deps) = self.key_deps.remove(key) {
+                hooks.append(&mut deps);
+            }
+        }
+        hooks.append(&mut self.deps);
+    }
+
+    #[cfg(feature = "profiling")]
+    fn flags(&mut self) -> crate::signal::SignalState {
+        crate::signal::SignalState {
+            written: !self.written_keys.is_empty(),
+            read: self.read_all.get() || !self.read_keys.get_mut().is_empty(),
+        }
+    }
+}
+
+impl<K, V> Default for MapSignal<K, V> {
+    fn default() -> Self {
+        Self::from(HashMap::new())
+    }
+}
+
+impl<K, V> From<HashMap<K, V>> for MapSignal<K, V> {
+    fn from(data: HashMap<K, V>) -> Self {
+        Self {
+            data,
+            tracker: KeyTracker {
+                read_keys: RefCell::new(HashSet::new()),
+                read_all: Cell::new(false),
+                written_keys: HashSet::new(),
+                key_deps: HashMap::new(),
+                deps: Vec::new(),
+            },
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> FromIterator<(K, V)> for MapSignal<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<HashMap<K, V>>())
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a MapSignal<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = std::collections::hash_map::Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: std::fmt::Debug, V: std::fmt::Debug> std::fmt::Debug for MapSignal<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.tracker.read_all.set(true);
+        self.data.fmt(f)
+    }
+}
+
+impl<K, V> MapSignal<K, V> {
+    /// Create a empty map
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark the map as read as a whole, and return the underlying map
+    fn read_all(&self) -> &HashMap<K, V> {
+        self.tracker.read_all.set(true);
+        &self.data
+    }
+
+    /// The number of entries, re-runs the reader on any change
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.read_all().len()
+    }
+
+    /// Is the map empty, re-runs the reader on any change
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.read_all().is_empty()
+    }
+
+    /// Iterate over the entries in arbitrary order, re-runs the reader on any change
+    pub fn iter(&self) -> std::collections::hash_map::Iter<'_, K, V> {
+        self.read_all().iter()
+    }
+
+    /// Iterate over the keys in arbitrary order, re-runs the reader on any change
+    pub fn keys(&self) -> std::collections::hash_map::Keys<'_, K, V> {
+        self.read_all().keys()
+    }
+
+    /// Iterate over the values in arbitrary order, re-runs the reader on any change
+    pub fn values(&self) -> std::collections::hash_map::Values<'_, K, V> {
+        self.read_all().values()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> MapSignal<K, V> {
+    /// Get the value of `key`, re-runs the reader only when that key changes
+    #[must_use]
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.tracker.read(key);
+        self.data.get(key)
+    }
+
+    /// Does the map contain `key`, re-runs the reader only when that key changes
+    #[must_use]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.tracker.read(key);
+        self.data.contains_key(key)
+    }
+
+    /// Get a mutable reference to the value of `key`, marking it as changed if it exists
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let value = self.data.get_mut(key)?;
+        self.tracker.write(key);
+        Some(value)
+    }
+
+    /// Insert a value, returning the previous value of the key
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.tracker.write(&key);
+        self.data.insert(key, value)
+    }
+
+    /// Remove a key, marking it as changed if it existed
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.data.remove(key)?;
+        self.tracker.write(key);
+        Some(value)
+    }
+
+    /// Remove all entries, marking every key as changed
+    pub fn clear(&mut self) {
+        for key in self.data.keys() {
+            self.tracker.write(key);
+        }
+        self.data.clear();
+    }
+}
+
+impl<K: Hash + Eq + Clone + 'static, V: 'static> ComponentData for MapSignal<K, V> {
+    type FieldRef<'a> = [&'a mut dyn SignalMethods; 1];
+    type SignalState = MapSignalState<K>;
+
+    fn signals_mut(&mut self) -> Self::FieldRef<'_> {
+        [&mut self.tracker]
+    }
+
+    fn field_names() -> &'static [&'static str] {
+        &["entries"]
+    }
+
+    fn pop_signals(&mut self) -> Self::SignalState {
+        MapSignalState {
+            read_keys: std::mem::take(self.tracker.read_keys.get_mut()),
+            read_all: self.tracker.read_all.replace(false),
+            written_keys: std::mem::take(&mut self.tracker.written_keys),
+        }
+    }
+
+    fn set_signals(&mut self, state: Self::SignalState) {
+        *self.tracker.read_keys.get_mut() = state.read_keys;
+        self.tracker.read_all.set(state.read_all);
+        self.tracker.written_keys = state.written_keys;
+    }
+}
+
+impl<K: Hash + Eq + Clone + 'static, V: 'static> ComponentBase for MapSignal<K, V> {
+    type Data = Self;
+
+    fn into_data(self) -> Self::Data {
+        self
+    }
+}
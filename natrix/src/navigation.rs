@@ -0,0 +1,256 @@
+//! Client side navigation helpers.
+
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::{JsCast, JsValue, intern};
+
+use crate::component::Component;
+use crate::shared::SharedSignal;
+use crate::state::State;
+use crate::utils::debug_expect;
+
+/// Navigate to the given path without reloading the page.
+///
+/// This pushes a new entry to the browser history and dispatches a `popstate` event on the window,
+/// so that anything listening for history changes (such as a router) can react to it.
+pub fn navigate(path: &str) {
+    let window = crate::get_window();
+
+    let Ok(history) = window.history() else {
+        debug_assert!(false, "Failed to get history");
+        return;
+    };
+    debug_expect!(
+        history.push_state_with_url(&JsValue::NULL, "", Some(path)),
+        "Failed to push history state"
+    );
+
+    let Ok(event) = web_sys::PopStateEvent::new("popstate") else {
+        debug_assert!(false, "Failed to create popstate event");
+        return;
+    };
+    debug_expect!(
+        window.dispatch_event(&event),
+        "Failed to dispatch popstate event"
+    );
+}
+
+/// Open `url` in the browsing context named `target`, such as `_blank` for a new tab.
+///
+/// Returns the opened window, or `None` if it was blocked (for example by a popup blocker, which
+/// usually allows it when called from a event handler) or could not be opened.
+#[must_use = "Use `let _ =` if you dont care if the window was opened"]
+pub fn open_url(url: &str, target: &str) -> Option<web_sys::Window> {
+    crate::get_window()
+        .open_with_url_and_target(url, target)
+        .ok()
+        .flatten()
+}
+
+/// Reload the current page, like the browsers reload button.
+pub fn reload() {
+    let result = crate::get_window().location().reload();
+    debug_expect!(result, "Failed to reload the page");
+}
+
+/// Read a query parameter of the current url
+fn read_query_param(name: &str) -> Option<String> {
+    let search = crate::get_window().location().search().ok()?;
+    web_sys::UrlSearchParams::new_with_str(&search)
+        .ok()?
+        .get(name)
+}
+
+/// Set a query parameter of the current url, or remove it if `value` is `None`.
+///
+/// This replaces the current history entry, so it does not reload the page or add a entry to the
+/// history. The other parameters are kept as is.
+fn write_query_param(name: &str, value: Option<&str>) {
+    let window = crate::get_window();
+
+    let Ok(href) = window.location().href() else {
+        debug_assert!(false, "Failed to get location");
+        return;
+    };
+    let Ok(url) = web_sys::Url::new(&href) else {
+        debug_assert!(false, "Failed to parse location {href}");
+        return;
+    };
+    let params = url.search_params();
+    match value {
+        Some(value) => params.set(name, value),
+        None => params.delete(name),
+    }
+
+    let Ok(history) = window.history() else {
+        debug_assert!(false, "Failed to get history");
+        return;
+    };
+    debug_expect!(
+        history.replace_state_with_url(&JsValue::NULL, "", Some(&url.href())),
+        "Failed to replace history state"
+    );
+}
+
+/// A url query parameter bound by [`State::use_query_param`].
+///
+/// This is a cheap handle and can be cloned freely.
+#[derive(Clone)]
+pub struct QueryParam {
+    /// The name of the parameter
+    name: Rc<str>,
+    /// The value used when the parameter is not in the url
+    default: Rc<str>,
+    /// The current value
+    value: SharedSignal<String>,
+}
+
+impl QueryParam {
+    /// Get the current value, subscribing the current hook of `ctx` to changes.
+    ///
+    /// This is the default value if the parameter is not in the url.
+    pub fn get<C: Component>(&self, ctx: &State<C>) -> String {
+        self.value.get(ctx)
+    }
+
+    /// Set the value, updating the url without reloading the page.
+    ///
+    /// Setting it to the default value removes the parameter from the url.
+    pub fn set(&self, value: impl Into<String>) {
+        let value = value.into();
+        let in_url = (*value != *self.default).then_some(value.as_str());
+        write_query_param(&self.name, in_url);
+        self.value.set(value);
+    }
+}
+
+/// The `popstate` listener behind a [`QueryParam`], removed once dropped.
+pub(crate) struct QueryParamListener {
+    /// The `popstate` listener on the window
+    listener: Closure<dyn Fn()>,
+}
+
+impl QueryParamListener {
+    /// Bind the query parameter `name`, returning the listener and the handle exposed to the user
+    pub(crate) fn bind(name: &str, default: &str) -> (Self, QueryParam) {
+        let param = QueryParam {
+            name: Rc::from(name),
+            default: Rc::from(default),
+            value: SharedSignal::new(read_query_param(name).unwrap_or_else(|| default.to_owned())),
+        };
+
+        let synced = param.clone();
+        let listener: Closure<dyn Fn()> = Closure::new(move || {
+            let current =
+                read_query_param(&synced.name).unwrap_or_else(|| synced.default.to_string());
+            if synced.value.with_untracked(|value| *value != current) {
+                synced.value.set(current);
+            }
+        });
+        debug_expect!(
+            crate::get_window().add_event_listener_with_callback(
+                intern("popstate"),
+                listener.as_ref().unchecked_ref(),
+            ),
+            "Failed to add popstate listener"
+        );
+
+        (Self { listener }, param)
+    }
+}
+
+impl Drop for QueryParamListener {
+    fn drop(&mut self) {
+        debug_expect!(
+            crate::get_window().remove_event_listener_with_callback(
+                intern("popstate"),
+                self.listener.as_ref().unchecked_ref(),
+            ),
+            "Failed to remove popstate listener"
+        );
+    }
+}
+
+/// The `returnValue` set on a prevented `beforeunload` event.
+///
+/// Browsers show their own generic message instead, but some require a non-empty value.
+const UNLOAD_WARNING: &str = "You have unsaved changes, are you sure you want to leave?";
+
+/// A `beforeunload` listener added by
+/// [`State::use_before_unload`](crate::state::State::use_before_unload), removed once dropped.
+pub(crate) struct UnloadGuard {
+    /// The `beforeunload` listener on the window
+    listener: Closure<dyn Fn(web_sys::BeforeUnloadEvent)>,
+}
+
+impl UnloadGuard {
+    /// Ask the user to confirm leaving the page whenever `should_warn` returns `true`.
+    pub(crate) fn new(should_warn: impl Fn() -> bool + 'static) -> Self {
+        let listener: Closure<dyn Fn(web_sys::BeforeUnloadEvent)> =
+            Closure::new(move |event: web_sys::BeforeUnloadEvent| {
+                if should_warn() {
+                    event.prevent_default();
+                    event.set_return_value(UNLOAD_WARNING);
+                }
+            });
+        debug_expect!(
+            crate::get_window().add_event_listener_with_callback(
+                intern("beforeunload"),
+                listener.as_ref().unchecked_ref(),
+            ),
+            "Failed to add beforeunload listener"
+        );
+        Self { listener }
+    }
+}
+
+impl Drop for UnloadGuard {
+    fn drop(&mut self) {
+        debug_expect!(
+            crate::get_window().remove_event_listener_with_callback(
+                intern("beforeunload"),
+                self.listener.as_ref().unchecked_ref(),
+            ),
+            "Failed to remove beforeunload listener"
+        );
+    }
+}
+
+/// Is this path pointing to a location on the current site.
+///
+/// Only absolute paths (`/foo`) are considered internal, protocol relative urls (`//example.com`)
+/// and full urls are considered external.
+pub(crate) fn is_internal(path: &str) -> bool {
+    path.starts_with('/') && !path.starts_with("//")
+}
+
+/// Should a click on a link be handled by client side navigation.
+///
+/// Modified clicks (ctrl/meta/shift/alt) and non-primary buttons are left to the browser so that
+/// opening links in new tabs/windows keeps working.
+pub(crate) fn is_plain_click(event: &web_sys::MouseEvent) -> bool {
+    event.button() == 0
+        && !event.ctrl_key()
+        && !event.meta_key()
+        && !event.shift_key()
+        && !event.alt_key()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_internal;
+
+    #[test]
+    fn internal_paths() {
+        assert!(is_internal("/"));
+        assert!(is_internal("/foo/bar"));
+    }
+
+    #[test]
+    fn external_paths() {
+        assert!(!is_internal("//example.com"));
+        assert!(!is_internal("https://example.com"));
+        assert!(!is_internal("mailto:hello@example.com"));
+    }
+}
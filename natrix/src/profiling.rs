@@ -0,0 +1,120 @@
+//! Counters for finding reactivity hotspots, enabled by the `profiling` feature.
+//!
+//! Every component field records how many hook runs read it, how many updates wrote to it, and
+//! how many hook reruns those writes caused. The counters are global for the whole session, use
+//! [`reset`] to only measure a specific interaction.
+//!
+//! ```rust
+//! let report = natrix::profiling::report();
+//! if let Some(worst) = report.first() {
+//!     // For example "app::App.items caused 12 hook reruns (4 reads, 3 writes)"
+//!     let message = worst.to_string();
+//! }
+//! ```
+#![cfg(feature = "profiling")]
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+
+/// The counters of a single component field
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldReport {
+    /// The type name of the component
+    pub component: &'static str,
+    /// The name of the field
+    pub field: &'static str,
+    /// The number of hook runs that read the field
+    pub reads: u64,
+    /// The number of updates that wrote to the field
+    pub writes: u64,
+    /// The number of hook reruns caused by writes to the field
+    pub hook_reruns: u64,
+}
+
+impl fmt::Display for FieldReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{} caused {} hook reruns ({} reads, {} writes)",
+            self.component, self.field, self.hook_reruns, self.reads, self.writes
+        )
+    }
+}
+
+/// The `reads`, `writes` and `hook_reruns` counters of a field
+type Counters = (u64, u64, u64);
+
+thread_local! {
+    /// The counters of every field, keyed by component and field name
+    static FIELDS: RefCell<HashMap<(&'static str, &'static str), Counters>> =
+        RefCell::new(HashMap::new());
+    /// The total number of hook runs
+    static HOOK_RUNS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Modify the counters of the given field
+fn with_counters(component: &'static str, field: &'static str, func: impl FnOnce(&mut Counters)) {
+    FIELDS.with_borrow_mut(|fields| func(fields.entry((component, field)).or_default()));
+}
+
+/// Record a hook run reading the field
+pub(crate) fn record_read(component: &'static str, field: &'static str) {
+    with_counters(component, field, |(reads, _, _)| {
+        *reads = reads.saturating_add(1);
+    });
+}
+
+/// Record a update writing to the field, which caused `hook_reruns` hooks to rerun
+pub(crate) fn record_write(component: &'static str, field: &'static str, reruns: usize) {
+    with_counters(component, field, |(_, writes, hook_reruns)| {
+        *writes = writes.saturating_add(1);
+        *hook_reruns = hook_reruns.saturating_add(u64::try_from(reruns).unwrap_or(u64::MAX));
+    });
+}
+
+/// Record a hook being run
+pub(crate) fn record_hook_run() {
+    HOOK_RUNS.set(HOOK_RUNS.get().saturating_add(1));
+}
+
+/// The counters of every field that has been read or written, sorted by the number of hook
+/// reruns they caused (most first).
+#[must_use]
+pub fn report() -> Vec<FieldReport> {
+    let mut report: Vec<FieldReport> = FIELDS.with_borrow(|fields| {
+        fields
+            .iter()
+            .map(
+                |(&(component, field), &(reads, writes, hook_reruns))| FieldReport {
+                    component,
+                    field,
+                    reads,
+                    writes,
+                    hook_reruns,
+                },
+            )
+            .collect()
+    });
+    report.sort_by(|a, b| {
+        b.hook_reruns
+            .cmp(&a.hook_reruns)
+            .then(b.writes.cmp(&a.writes))
+            .then(a.component.cmp(b.component))
+            .then(a.field.cmp(b.field))
+    });
+    report
+}
+
+/// The total number of reactive hooks that have run, including those not caused by a field write
+/// (such as [`State::force_update`](crate::state::State::force_update)).
+#[must_use]
+pub fn hook_runs() -> u64 {
+    HOOK_RUNS.get()
+}
+
+/// Reset all counters
+pub fn reset() {
+    FIELDS.with_borrow_mut(HashMap::clear);
+    HOOK_RUNS.set(0);
+}
@@ -1,8 +1,13 @@
 //! Implements the reactive hooks for updating the dom in response to signal changessz.
 
+use std::any::Any;
+use std::rc::Rc;
+
+use wasm_bindgen::{JsCast, JsValue};
+
 use crate::component::Component;
-use crate::element::{Element, generate_fallback_node};
-use crate::html_elements::ToAttribute;
+use crate::element::{Element, ElementKey, generate_fallback_node};
+use crate::html_elements::{AttributeName, AttributeValue, ToAttribute, ToInnerText, ToProperty};
 use crate::signal::{ReactiveHook, RenderingState, UpdateResult};
 use crate::state::{HookKey, KeepAlive, RenderCtx, State};
 use crate::utils::debug_expect;
@@ -26,18 +31,36 @@ pub(crate) struct ReactiveNode<C: Component, E> {
     callback: Box<dyn Fn(&mut RenderCtx<C>) -> E>,
     /// The current rendered node to replace
     target_node: web_sys::Node,
+    /// If the rendered node was a fragment this is the comment marking its start, in which case
+    /// `target_node` is the comment marking its end.
+    range_start: Option<web_sys::Node>,
     /// Vector of various objects to be kept alive for the duration of the rendered content
     keep_alive: Vec<KeepAlive>,
     /// Hooks that are a child of this
     hooks: Vec<HookKey>,
+    /// The list item key this was created under, restored when re-rendering
+    item_key: Option<Rc<dyn Any>>,
+    /// Should focus inside the rendered content be moved to the new content when re-rendering
+    preserve_focus: bool,
+    /// The key of the rendered element, if it was given one with `with_key`
+    key: Option<Rc<dyn ElementKey>>,
+    /// Did the last render create no hooks, event listeners or pinned elements, in which case the
+    /// rendered element can be updated in place
+    is_static: bool,
 }
 
 impl<C: Component, E: Element<C>> ReactiveNode<C, E> {
-    /// Render this hook and simply return the node
+    /// Render this hook and simply return the node, or `None` if the returned element has the
+    /// same key as the currently rendered one.
     ///
     /// IMPORTANT: This function works with the assumption what it returns will be put in its
     /// `target_node` field. This function is split out to facilitate `Self::create_initial`
-    fn render(&mut self, ctx: &mut State<C>, you: HookKey) -> web_sys::Node {
+    fn render(&mut self, ctx: &mut State<C>, you: HookKey) -> Option<web_sys::Node> {
+        ctx.with_item_key(self.item_key.clone(), |ctx| self.render_inner(ctx, you))
+    }
+
+    /// Render with the item key set, see `render`
+    fn render_inner(&mut self, ctx: &mut State<C>, you: HookKey) -> Option<web_sys::Node> {
         ctx.clear();
 
         let element = (self.callback)(&mut RenderCtx {
@@ -46,17 +69,59 @@ impl<C: Component, E: Element<C>> ReactiveNode<C, E> {
                 keep_alive: &mut self.keep_alive,
                 hooks: &mut self.hooks,
                 parent_dep: you,
+                pinned: false,
             },
         });
         ctx.reg_dep(you);
 
+        let key = element.key();
+        if let (Some(key), Some(current)) = (&key, &self.key)
+            && key.key_eq(&**current)
+        {
+            return None;
+        }
+        self.key = key;
+
         let mut state = RenderingState {
             keep_alive: &mut self.keep_alive,
             hooks: &mut self.hooks,
             parent_dep: you,
+            pinned: false,
+        };
+
+        let node = element.render(ctx, &mut state);
+        self.is_static = !state.pinned && self.hooks.is_empty() && self.keep_alive.is_empty();
+        Some(node)
+    }
+
+    /// Set the target to the newly rendered node, returning the node to insert.
+    ///
+    /// A fragment is empty once inserted, so its content is wrapped in comment markers which are
+    /// used as the bounds of the content to replace instead.
+    fn set_target(&mut self, node: web_sys::Node) -> web_sys::Node {
+        self.range_start = None;
+        self.target_node = node.clone();
+
+        if !node.has_type::<web_sys::DocumentFragment>() {
+            return node;
+        }
+        let (Ok(start), Ok(end)) = (web_sys::Comment::new(), web_sys::Comment::new()) else {
+            debug_assert!(false, "Failed to create comment node");
+            return node;
         };
 
-        element.render(ctx, &mut state)
+        debug_expect!(
+            node.insert_before(&start, node.first_child().as_ref()),
+            "Failed to insert fragment start marker"
+        );
+        debug_expect!(
+            node.append_child(&end),
+            "Failed to insert fragment end marker"
+        );
+        self.range_start = Some(start.into());
+        self.target_node = end.into();
+
+        node
     }
 
     /// Create a new `ReactiveNode` registering the initial dependencies and returning both the `Rc`
@@ -64,6 +129,24 @@ impl<C: Component, E: Element<C>> ReactiveNode<C, E> {
     pub(crate) fn create_initial(
         callback: Box<dyn Fn(&mut RenderCtx<C>) -> E>,
         ctx: &mut State<C>,
+    ) -> (HookKey, web_sys::Node) {
+        Self::create(callback, false, ctx)
+    }
+
+    /// Same as `create_initial`, but moves focus within the content to the same position in the
+    /// new content when re-rendering.
+    pub(crate) fn create_initial_preserving_focus(
+        callback: Box<dyn Fn(&mut RenderCtx<C>) -> E>,
+        ctx: &mut State<C>,
+    ) -> (HookKey, web_sys::Node) {
+        Self::create(callback, true, ctx)
+    }
+
+    /// Create the hook, see `create_initial`
+    fn create(
+        callback: Box<dyn Fn(&mut RenderCtx<C>) -> E>,
+        preserve_focus: bool,
+        ctx: &mut State<C>,
     ) -> (HookKey, web_sys::Node) {
         let me = ctx.insert_hook(Box::new(DummyHook));
 
@@ -76,11 +159,17 @@ impl<C: Component, E: Element<C>> ReactiveNode<C, E> {
         let mut this = Self {
             callback,
             target_node: dummy_node,
+            range_start: None,
             keep_alive: Vec::new(),
             hooks: Vec::new(),
+            item_key: ctx.item_key(),
+            preserve_focus,
+            key: None,
+            is_static: false,
         };
-        let node = this.render(ctx, me);
-        this.target_node = node.clone();
+        // There is no current key, so this always renders
+        let node = this.render(ctx, me).unwrap_or_else(generate_fallback_node);
+        let node = this.set_target(node);
         ctx.set_hook(me, Box::new(this));
 
         (me, node)
@@ -89,23 +178,210 @@ impl<C: Component, E: Element<C>> ReactiveNode<C, E> {
     /// Pulled out update method to facilite marking it as `default` on nightly
     fn update(&mut self, ctx: &mut State<C>, you: HookKey) -> UpdateResult {
         let hooks = std::mem::take(&mut self.hooks);
-        let new_node = self.render(ctx, you);
+        let keep_alive = std::mem::take(&mut self.keep_alive);
+        let old_start = self.range_start.take();
+        let old_target = self.target_node.clone();
+        let focus = if self.preserve_focus {
+            FocusPath::save(old_start.as_ref().unwrap_or(&old_target), &old_target)
+        } else {
+            None
+        };
 
-        let Some(parent) = self.target_node.parent_node() else {
+        let was_static = self.is_static;
+        let Some(new_node) = self.render(ctx, you) else {
+            // The same element is kept, so only the hooks and objects created by this run are
+            // dropped
+            self.range_start = old_start;
+            self.keep_alive = keep_alive;
+            let new_hooks = std::mem::replace(&mut self.hooks, hooks);
+            return UpdateResult::DropHooks(new_hooks);
+        };
+
+        // Neither render attached anything to the element, so it can be updated in place
+        if was_static
+            && self.is_static
+            && old_start.is_none()
+            && update_in_place(&old_target, &new_node)
+        {
+            ctx.update_root_node(&old_target, &old_target);
+            if let Some(focus) = focus {
+                focus.restore(&self.target_node);
+            }
+            self.keep_alive = keep_alive;
+            return UpdateResult::DropHooks(hooks);
+        }
+
+        let new_node = self.set_target(new_node);
+
+        let Some(parent) = old_target.parent_node() else {
             debug_assert!(false, "Parent node of target node not found.");
             return UpdateResult::DropHooks(hooks);
         };
 
+        // Remove the old fragment content, leaving the end marker to be replaced below.
+        let mut current = old_start;
+        while let Some(node) = current {
+            if node.is_same_node(Some(&old_target)) {
+                break;
+            }
+            current = node.next_sibling();
+            debug_expect!(
+                parent.remove_child(&node),
+                "Failed to remove old fragment node"
+            );
+        }
+
         debug_expect!(
-            parent.replace_child(&new_node, &self.target_node),
+            parent.replace_child(&new_node, &old_target),
             "Failed to replace parent"
         );
-        self.target_node = new_node;
+        // The old content is gone, so anything it kept alive can go as well
+        drop(keep_alive);
+        ctx.update_root_node(&old_target, &new_node);
+
+        if let Some(focus) = focus {
+            focus.restore(self.range_start.as_ref().unwrap_or(&self.target_node));
+        }
 
         UpdateResult::DropHooks(hooks)
     }
 }
 
+/// Update `old` to match `new` if both are text nodes or elements of the same kind, returning
+/// `false` if it has to be replaced instead.
+///
+/// The attributes of `old` are synced with `new`, and its children are replaced by the children of
+/// `new`. This keeps transitions, scroll position and other state of `old`. This is only valid if
+/// neither element has hooks, event listeners, properties or node refs attached to it, as those are
+/// not transferred.
+/// Form controls are always replaced, as their current value is not reflected in their attributes.
+fn update_in_place(old: &web_sys::Node, new: &web_sys::Node) -> bool {
+    if old.has_type::<web_sys::Text>() && new.has_type::<web_sys::Text>() {
+        old.set_text_content(new.text_content().as_deref());
+        return true;
+    }
+
+    let (Some(old), Some(new)) = (
+        old.dyn_ref::<web_sys::Element>(),
+        new.dyn_ref::<web_sys::Element>(),
+    ) else {
+        return false;
+    };
+    let tag = old.tag_name();
+    if tag != new.tag_name()
+        || old.namespace_uri() != new.namespace_uri()
+        || matches!(tag.as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+    {
+        return false;
+    }
+
+    for name in old.get_attribute_names().iter() {
+        if let Some(name) = name.as_string()
+            && !new.has_attribute(&name)
+        {
+            debug_expect!(old.remove_attribute(&name), "Failed to remove attribute");
+        }
+    }
+    for name in new.get_attribute_names().iter() {
+        let Some(name) = name.as_string() else {
+            continue;
+        };
+        let value = new.get_attribute(&name);
+        if let Some(value) = value
+            && old.get_attribute(&name).as_ref() != Some(&value)
+        {
+            debug_expect!(old.set_attribute(&name, &value), "Failed to set attribute");
+        }
+    }
+
+    old.set_text_content(None);
+    while let Some(child) = new.first_child() {
+        debug_expect!(old.append_child(&child), "Failed to move child");
+    }
+    true
+}
+
+/// The position of the focused element within the content of a `ReactiveNode`
+struct FocusPath {
+    /// The index of the top level node in the content, followed by the child indices leading to
+    /// the focused element
+    path: Vec<u32>,
+    /// The focused element
+    element: web_sys::Element,
+}
+
+impl FocusPath {
+    /// Save the position of the focused element if it is within the nodes `first..=last`
+    fn save(first: &web_sys::Node, last: &web_sys::Node) -> Option<Self> {
+        let element = get_document().active_element()?;
+        let parent = first.parent_node()?;
+
+        let mut path = Vec::new();
+        let mut current: web_sys::Node = element.clone().into();
+        loop {
+            let up = current.parent_node()?;
+            if up.is_same_node(Some(&parent)) {
+                break;
+            }
+            let children = up.child_nodes();
+            let index = (0..children.length()).find(|index| {
+                children
+                    .item(*index)
+                    .is_some_and(|child| child.is_same_node(Some(&current)))
+            })?;
+            path.push(index);
+            current = up;
+        }
+
+        let mut index: u32 = 0;
+        let mut node = first.clone();
+        while !node.is_same_node(Some(&current)) {
+            if node.is_same_node(Some(last)) {
+                return None;
+            }
+            node = node.next_sibling()?;
+            index = index.checked_add(1)?;
+        }
+        path.push(index);
+        path.reverse();
+
+        Some(Self { path, element })
+    }
+
+    /// Focus the element at the same position in the content starting at `first`, if it has the
+    /// same tag as the previously focused element.
+    fn restore(self, first: &web_sys::Node) -> Option<()> {
+        let mut path = self.path.into_iter();
+
+        let mut node = first.clone();
+        for _ in 0..path.next()? {
+            node = node.next_sibling()?;
+        }
+        for index in path {
+            node = node.child_nodes().item(index)?;
+        }
+
+        let element = node.dyn_into::<web_sys::HtmlElement>().ok()?;
+        if element.tag_name() != self.element.tag_name() {
+            return None;
+        }
+        debug_expect!(element.focus(), "Failed to focus element");
+
+        // Keep the cursor position of text inputs, this fails harmlessly for other elements
+        for property in ["selectionStart", "selectionEnd"] {
+            let property = JsValue::from_str(property);
+            if let Ok(value) = web_sys::js_sys::Reflect::get(&self.element, &property)
+                && !value.is_null()
+                && !value.is_undefined()
+            {
+                let _ = web_sys::js_sys::Reflect::set(&element, &property, &value);
+            }
+        }
+
+        Some(())
+    }
+}
+
 impl<C: Component, E: Element<C>> ReactiveHook<C> for ReactiveNode<C, E> {
     #[cfg(not(nightly))]
     fn update(&mut self, ctx: &mut State<C>, you: HookKey) -> UpdateResult {
@@ -122,43 +398,53 @@ impl<C: Component, E: Element<C>> ReactiveHook<C> for ReactiveNode<C, E> {
     }
 }
 
-#[cfg(nightly)]
-impl<C: Component> ReactiveHook<C> for ReactiveNode<C, String> {
-    fn update(&mut self, ctx: &mut State<C>, you: HookKey) -> UpdateResult {
-        use wasm_bindgen::JsCast;
+/// generate nightly only optimization for string types, writing the `&str` view into the
+/// existing text node
+macro_rules! node_specialize_string {
+    ($type:ty) => {
+        #[cfg(nightly)]
+        impl<C: Component> ReactiveHook<C> for ReactiveNode<C, $type> {
+            fn update(&mut self, ctx: &mut State<C>, you: HookKey) -> UpdateResult {
+                let hooks = std::mem::take(&mut self.hooks);
 
-        let hooks = std::mem::take(&mut self.hooks);
+                ctx.clear();
+                self.keep_alive.clear();
+                let element = (self.callback)(&mut RenderCtx {
+                    ctx,
+                    render_state: RenderingState {
+                        keep_alive: &mut self.keep_alive,
+                        hooks: &mut self.hooks,
+                        parent_dep: you,
+                        pinned: false,
+                    },
+                });
+                ctx.reg_dep(you);
 
-        ctx.clear();
-        self.keep_alive.clear();
-        let element = (self.callback)(&mut RenderCtx {
-            ctx,
-            render_state: RenderingState {
-                keep_alive: &mut self.keep_alive,
-                hooks: &mut self.hooks,
-                parent_dep: you,
-            },
-        });
-        ctx.reg_dep(you);
+                if let Some(target_node) = self.target_node.dyn_ref::<web_sys::Text>() {
+                    target_node.set_text_content(Some(&*element));
+                } else {
+                    debug_assert!(false, "String Node wasnt a text node");
+                }
 
-        if let Some(target_node) = self.target_node.dyn_ref::<web_sys::Text>() {
-            target_node.set_text_content(Some(&element));
-        } else {
-            debug_assert!(false, "`String` Node wasnt a text node");
+                UpdateResult::DropHooks(hooks)
+            }
         }
-
-        UpdateResult::DropHooks(hooks)
-    }
+    };
 }
 
+// Specializing on a `'static` lifetime is not allowed, so `&'static str` and `Cow<'static, str>`
+// use the generic update, which updates text nodes in place as well.
+node_specialize_string!(String);
+node_specialize_string!(std::rc::Rc<str>);
+node_specialize_string!(std::sync::Arc<str>);
+node_specialize_string!(Box<str>);
+
 /// generate nightly only optimization for ints and floats
 macro_rules! node_specialize_int {
     ($type:ty, $fmt:ident) => {
         #[cfg(nightly)]
         impl<C: Component> ReactiveHook<C> for ReactiveNode<C, $type> {
             fn update(&mut self, ctx: &mut State<C>, you: HookKey) -> UpdateResult {
-                use wasm_bindgen::JsCast;
-
                 let hooks = std::mem::take(&mut self.hooks);
 
                 ctx.clear();
@@ -169,6 +455,7 @@ macro_rules! node_specialize_int {
                         keep_alive: &mut self.keep_alive,
                         hooks: &mut self.hooks,
                         parent_dep: you,
+                        pinned: false,
                     },
                 });
                 ctx.reg_dep(you);
@@ -193,12 +480,18 @@ type_macros::numerics!(node_specialize_int);
 /// A trait to allow `SimpleReactive` to deduplicate common reactive logic for attributes, classes,
 /// styles, etc
 pub(crate) trait ReactiveValue<C: Component> {
+    /// A description of the applied value, used to skip applying the same value twice
+    type Cache: PartialEq;
+
     /// Actually apply the change
     fn apply(self, ctx: &mut State<C>, render_state: &mut RenderingState, node: &web_sys::Element);
+
+    /// Get the cache value for this value, `None` means it should always be applied
+    fn cache(&self) -> Option<Self::Cache>;
 }
 
 /// A common wrapper for simple reactive operations to deduplicate dependency tracking code
-pub(crate) struct SimpleReactive<C: Component, K> {
+pub(crate) struct SimpleReactive<C: Component, K: ReactiveValue<C>> {
     /// The callback to call, takes state and returns the needed data for the reactive
     /// transformation
     callback: Box<dyn Fn(&mut RenderCtx<C>) -> K>,
@@ -208,6 +501,8 @@ pub(crate) struct SimpleReactive<C: Component, K> {
     keep_alive: Vec<KeepAlive>,
     /// Hooks to use
     hooks: Vec<HookKey>,
+    /// The cache of the last applied value
+    last: Option<K::Cache>,
 }
 
 impl<C: Component, K: ReactiveValue<C>> ReactiveHook<C> for SimpleReactive<C, K> {
@@ -224,16 +519,24 @@ impl<C: Component, K: ReactiveValue<C>> ReactiveHook<C> for SimpleReactive<C, K>
                 keep_alive: &mut self.keep_alive,
                 hooks: &mut self.hooks,
                 parent_dep: you,
+                pinned: false,
             },
         });
         ctx.reg_dep(you);
 
+        let cache = value.cache();
+        if cache.is_some() && cache == self.last {
+            return UpdateResult::Nothing;
+        }
+        self.last = cache;
+
         value.apply(
             ctx,
             &mut RenderingState {
                 keep_alive: &mut self.keep_alive,
                 hooks: &mut self.hooks,
                 parent_dep: you,
+                pinned: false,
             },
             &self.node,
         );
@@ -256,6 +559,7 @@ impl<C: Component, K: ReactiveValue<C> + 'static> SimpleReactive<C, K> {
             node,
             keep_alive: Vec::new(),
             hooks: Vec::new(),
+            last: None,
         };
         this.update(ctx, me);
 
@@ -268,13 +572,88 @@ impl<C: Component, K: ReactiveValue<C> + 'static> SimpleReactive<C, K> {
 /// Reactivly set a element attribute
 pub(crate) struct ReactiveAttribute<T> {
     /// The attribute name to set
-    pub(crate) name: &'static str,
+    pub(crate) name: AttributeName,
     /// The attribute value to apply
     pub(crate) data: T,
 }
 
 impl<C: Component, T: ToAttribute<C>> ReactiveValue<C> for ReactiveAttribute<T> {
+    type Cache = AttributeValue;
+
     fn apply(self, ctx: &mut State<C>, render_state: &mut RenderingState, node: &web_sys::Element) {
         Box::new(self.data).apply_attribute(self.name, node, ctx, render_state);
     }
+
+    fn cache(&self) -> Option<Self::Cache> {
+        self.data.attribute_value()
+    }
+}
+
+/// Reactivly add or remove a single class
+pub(crate) struct ReactiveClassToggle {
+    /// The class to toggle
+    pub(crate) class: &'static str,
+    /// Should the class be present
+    pub(crate) enabled: bool,
+}
+
+impl<C: Component> ReactiveValue<C> for ReactiveClassToggle {
+    type Cache = bool;
+
+    fn apply(
+        self,
+        _ctx: &mut State<C>,
+        _render_state: &mut RenderingState,
+        node: &web_sys::Element,
+    ) {
+        debug_expect!(
+            node.class_list()
+                .toggle_with_force(self.class, self.enabled),
+            "Failed to toggle class {}",
+            self.class
+        );
+    }
+
+    fn cache(&self) -> Option<Self::Cache> {
+        Some(self.enabled)
+    }
+}
+
+/// Reactivly set a property of a element
+pub(crate) struct ReactiveProperty<T> {
+    /// The property name to set
+    pub(crate) name: &'static str,
+    /// The property value to apply
+    pub(crate) data: T,
+}
+
+impl<C: Component, T: ToProperty<C>> ReactiveValue<C> for ReactiveProperty<T> {
+    type Cache = ();
+
+    fn apply(self, ctx: &mut State<C>, render_state: &mut RenderingState, node: &web_sys::Element) {
+        Box::new(self.data).apply_property(self.name, node, ctx, render_state);
+    }
+
+    // Properties are live state that might have been changed by the user, so always reapply them
+    fn cache(&self) -> Option<Self::Cache> {
+        None
+    }
+}
+
+/// Reactivly set the text content of a element
+pub(crate) struct ReactiveInnerText<T> {
+    /// The text to apply
+    pub(crate) data: T,
+}
+
+impl<C: Component, T: ToInnerText<C>> ReactiveValue<C> for ReactiveInnerText<T> {
+    type Cache = ();
+
+    fn apply(self, ctx: &mut State<C>, render_state: &mut RenderingState, node: &web_sys::Element) {
+        Box::new(self.data).apply_inner_text(node, ctx, render_state);
+    }
+
+    fn cache(&self) -> Option<Self::Cache> {
+        None
+    }
 }
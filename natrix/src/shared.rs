@@ -0,0 +1,190 @@
+//! State shared between components
+
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+
+use crate::component::Component;
+use crate::state::{HookKey, State};
+
+/// A component state that can run hooks on behalf of a `SharedSignal`
+pub(crate) trait SharedSubscriber {
+    /// Run the given hooks
+    fn run_hooks(&self, hooks: Vec<HookKey>);
+}
+
+impl<C: Component> SharedSubscriber for RefCell<State<C>> {
+    fn run_hooks(&self, hooks: Vec<HookKey>) {
+        crate::return_if_panic!();
+
+        let Ok(mut state) = self.try_borrow_mut() else {
+            debug_assert!(
+                false,
+                "Component state borrowed while updating a shared signal"
+            );
+            return;
+        };
+        state.run_hooks(hooks);
+    }
+}
+
+/// A shared signal that has been read, and should be subscribed to once the hook is known.
+pub(crate) trait SharedRead {
+    /// Subscribe the hook of the given component state to this signal
+    fn subscribe(&self, state: Weak<dyn SharedSubscriber>, hook: HookKey);
+}
+
+/// The data behind a `SharedSignal`
+struct SharedInner<T> {
+    /// The value
+    value: RefCell<T>,
+    /// The hooks depending on this signal, these are drained on every change and re-registered
+    /// when the hooks re-run.
+    subscribers: RefCell<Vec<(Weak<dyn SharedSubscriber>, HookKey)>>,
+    /// Is a update already scheduled
+    scheduled: Cell<bool>,
+}
+
+impl<T> SharedRead for SharedInner<T> {
+    fn subscribe(&self, state: Weak<dyn SharedSubscriber>, hook: HookKey) {
+        self.subscribers.borrow_mut().push((state, hook));
+    }
+}
+
+/// A signal that can be shared between multiple components.
+///
+/// Regular fields are local to a component, a `SharedSignal` can be cloned and passed to multiple
+/// components (usually as a `#[natrix(skip)]` field), and every component reading it will be
+/// updated when it is written to.
+///
+/// ```rust
+/// # use natrix::prelude::*;
+/// use natrix::shared::SharedSignal;
+///
+/// #[derive(Component)]
+/// struct Counter {
+///     #[natrix(skip)]
+///     count: SharedSignal<u32>,
+/// }
+///
+/// impl Component for Counter {
+///     # type EmitMessage = NoMessages;
+///     # type ReceiveMessage = NoMessages;
+///     fn render() -> impl Element<Self> {
+///         e::button()
+///             .text(|ctx: R<Self>| ctx.count.get(ctx))
+///             .on::<events::Click>(|ctx: E<Self>, _| ctx.count.update(|count| *count += 1))
+///     }
+/// }
+///
+/// #[derive(Component)]
+/// struct Parent {
+///     #[natrix(skip)]
+///     count: SharedSignal<u32>,
+/// }
+///
+/// impl Component for Parent {
+///     # type EmitMessage = NoMessages;
+///     # type ReceiveMessage = NoMessages;
+///     fn render() -> impl Element<Self> {
+///         e::div().child(|ctx: R<Self>| {
+///             e::div()
+///                 .child(C::new(Counter { count: ctx.count.clone() }))
+///                 .child(C::new(Counter { count: ctx.count.clone() }))
+///         })
+///     }
+/// }
+/// ```
+///
+/// # Updates
+/// Writes are propagated to the subscribed components in a microtask, similar to messages.
+/// This means a write will not be visible in the dom until the current event handler is done, but
+/// will still be applied before the next browser reflow, and multiple writes are batched.
+pub struct SharedSignal<T> {
+    /// The shared data
+    inner: Rc<SharedInner<T>>,
+}
+
+impl<T> Clone for SharedSignal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Rc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T: 'static> SharedSignal<T> {
+    /// Create a new shared signal
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Rc::new(SharedInner {
+                value: RefCell::new(value),
+                subscribers: RefCell::new(Vec::new()),
+                scheduled: Cell::new(false),
+            }),
+        }
+    }
+
+    /// Run the function with a reference to the value, subscribing the current hook of `ctx`
+    /// to changes.
+    pub fn with<C: Component, R>(&self, ctx: &State<C>, func: impl FnOnce(&T) -> R) -> R {
+        ctx.read_shared(Rc::clone(&self.inner) as Rc<dyn SharedRead>);
+        func(&self.inner.value.borrow())
+    }
+
+    /// Get a clone of the value, subscribing the current hook of `ctx` to changes.
+    pub fn get<C: Component>(&self, ctx: &State<C>) -> T
+    where
+        T: Clone,
+    {
+        self.with(ctx, T::clone)
+    }
+
+    /// Run the function with a reference to the value, without subscribing to changes.
+    pub fn with_untracked<R>(&self, func: impl FnOnce(&T) -> R) -> R {
+        func(&self.inner.value.borrow())
+    }
+
+    /// Set the value, updating all subscribed components
+    pub fn set(&self, value: T) {
+        self.update(|current| *current = value);
+    }
+
+    /// Modify the value, updating all subscribed components
+    pub fn update(&self, func: impl FnOnce(&mut T)) {
+        func(&mut self.inner.value.borrow_mut());
+        self.schedule();
+    }
+
+    /// Schedule a update of the subscribers, unless one is already pending.
+    fn schedule(&self) {
+        if self.inner.scheduled.replace(true) {
+            return;
+        }
+
+        let inner = Rc::clone(&self.inner);
+        wasm_bindgen_futures::spawn_local(async move {
+            inner.scheduled.set(false);
+            let subscribers = std::mem::take(&mut *inner.subscribers.borrow_mut());
+
+            // Group the hooks by component so each state is only borrowed once
+            let mut grouped: Vec<(Weak<dyn SharedSubscriber>, Vec<HookKey>)> = Vec::new();
+            for (state, hook) in subscribers {
+                if let Some((_, hooks)) = grouped
+                    .iter_mut()
+                    .find(|(existing, _)| Weak::ptr_eq(existing, &state))
+                {
+                    hooks.push(hook);
+                } else {
+                    grouped.push((state, vec![hook]));
+                }
+            }
+
+            // Upgrading is done as late as possible as earlier updates might unmount components
+            for (state, hooks) in grouped {
+                if let Some(state) = state.upgrade() {
+                    state.run_hooks(hooks);
+                }
+            }
+        });
+    }
+}
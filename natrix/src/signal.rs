@@ -4,7 +4,7 @@ use std::cell::Cell;
 use std::ops::{Deref, DerefMut};
 
 use crate::component::Component;
-use crate::state::{HookKey, KeepAlive, State};
+use crate::state::{ComponentData, HookKey, KeepAlive, State};
 
 /// State passed to rendering callbacks
 pub(crate) struct RenderingState<'s> {
@@ -14,6 +14,10 @@ pub(crate) struct RenderingState<'s> {
     pub(crate) hooks: &'s mut Vec<HookKey>,
     /// The parent render context, can be used to register it as a dependency of yourself
     pub(crate) parent_dep: HookKey,
+    /// Set if a rendered element holds state that can not be copied to another element, such as
+    /// properties or a node ref, so it has to replace the old content instead of being merged
+    /// into it.
+    pub(crate) pinned: bool,
 }
 
 /// A signal tracks reads and writes, as well as
@@ -37,9 +41,9 @@ pub struct Signal<T> {
 #[derive(Copy, Clone)]
 pub struct SignalState {
     /// Was the signal written to
-    written: bool,
+    pub(crate) written: bool,
     /// Was the signal read
-    read: bool,
+    pub(crate) read: bool,
 }
 
 impl<T: std::fmt::Debug> std::fmt::Debug for Signal<T> {
@@ -59,6 +63,19 @@ impl<T> Signal<T> {
         }
     }
 
+    /// Set the value of the signal, only marking it as changed if the new value is different.
+    ///
+    /// This avoids triggering reactive updates for writes that do not actually change anything.
+    pub fn set_if_changed(&mut self, value: T)
+    where
+        T: PartialEq,
+    {
+        if self.data != value {
+            self.data = value;
+            self.written = true;
+        }
+    }
+
     #[doc(hidden)]
     pub fn pop_state(&mut self) -> SignalState {
         let result = SignalState {
@@ -76,6 +93,100 @@ impl<T> Signal<T> {
     }
 }
 
+impl Signal<bool> {
+    /// Flip the value of the signal.
+    pub fn toggle(&mut self) {
+        self.data = !self.data;
+        self.written = true;
+    }
+}
+
+impl<T> Signal<Option<T>> {
+    /// Get the value, initializing it with `func` if it is `None`.
+    ///
+    /// This only marks the signal as changed when it is initialized, so dependents of a lazily
+    /// populated field are only notified the first time it is set.
+    pub fn get_or_insert_with(&mut self, func: impl FnOnce() -> T) -> &T {
+        self.read.set(true);
+        if self.data.is_none() {
+            self.written = true;
+        }
+        self.data.get_or_insert_with(func)
+    }
+}
+
+/// Generate the arithmetic helpers for a integer signal.
+macro_rules! int_signal_helpers {
+    ($($T:ident),*) => {
+        $(
+            impl Signal<$T> {
+                /// Increment the value by one.
+                /// Saturates at the numeric bounds instead of overflowing.
+                pub fn increment(&mut self) {
+                    self.increment_by(1);
+                }
+
+                /// Decrement the value by one.
+                /// Saturates at the numeric bounds instead of overflowing.
+                pub fn decrement(&mut self) {
+                    self.decrement_by(1);
+                }
+
+                /// Increment the value by `amount`, only marking it as changed if the value
+                /// actually changed.
+                /// Saturates at the numeric bounds instead of overflowing.
+                pub fn increment_by(&mut self, amount: $T) {
+                    self.set_if_changed(self.data.saturating_add(amount));
+                }
+
+                /// Decrement the value by `amount`, only marking it as changed if the value
+                /// actually changed.
+                /// Saturates at the numeric bounds instead of overflowing.
+                pub fn decrement_by(&mut self, amount: $T) {
+                    self.set_if_changed(self.data.saturating_sub(amount));
+                }
+            }
+        )*
+    };
+}
+
+int_signal_helpers!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+/// Generate the arithmetic helpers for a float signal.
+macro_rules! float_signal_helpers {
+    ($($T:ident),*) => {
+        $(
+            impl Signal<$T> {
+                /// Increment the value by one.
+                pub fn increment(&mut self) {
+                    self.increment_by(1.0);
+                }
+
+                /// Decrement the value by one.
+                pub fn decrement(&mut self) {
+                    self.decrement_by(1.0);
+                }
+
+                /// Increment the value by `amount`, only marking it as changed if the value
+                /// actually changed.
+                pub fn increment_by(&mut self, amount: $T) {
+                    self.set_if_changed(self.data + amount);
+                }
+
+                /// Decrement the value by `amount`, only marking it as changed if the value
+                /// actually changed.
+                pub fn decrement_by(&mut self, amount: $T) {
+                    self.set_if_changed(self.data - amount);
+                }
+            }
+        )*
+    };
+}
+
+float_signal_helpers!(f32, f64);
+
 /// Methods for signals that arent generic over the contained data.
 ///
 /// The use case of this trait is allowing the `State` struct
@@ -89,14 +200,23 @@ pub trait SignalMethods {
     fn clear(&mut self);
     /// Adds the given dependency to the hashset if the `read` flag is set.
     fn register_dep(&mut self, dep: HookKey);
-    /// Return a mutable reference to the dependencies to facilitate efficient cleanup and
-    /// deduplication in the `State struct`
+    /// If the `written` flag is set move the dependencies into `hooks`.
     ///
     /// We are doing the cleaning in the `State` struct because it lets us deduplicate the changed
-    /// hooks in `.update` without looping over the hashset twice.
-    fn deps(&mut self) -> std::vec::Drain<'_, HookKey>;
+    /// hooks in `.update` without looping over the dependencies twice.
+    fn drain_changed_deps(&mut self, hooks: &mut Vec<HookKey>);
+    /// Return the `read` and `written` flags, used for profiling
+    #[cfg(feature = "profiling")]
+    #[doc(hidden)]
+    fn flags(&mut self) -> SignalState;
+}
+
+#[cfg(test)]
+impl<T> Signal<T> {
     /// Return the value of the `written` field
-    fn changed(&self) -> bool;
+    pub(crate) fn changed(&self) -> bool {
+        self.written
+    }
 }
 
 impl<T> SignalMethods for Signal<T> {
@@ -111,12 +231,55 @@ impl<T> SignalMethods for Signal<T> {
         }
     }
 
-    fn changed(&self) -> bool {
-        self.written
+    fn drain_changed_deps(&mut self, hooks: &mut Vec<HookKey>) {
+        if self.written {
+            hooks.append(&mut self.deps);
+        }
+    }
+
+    #[cfg(feature = "profiling")]
+    fn flags(&mut self) -> SignalState {
+        SignalState {
+            written: self.written,
+            read: self.read.get(),
+        }
+    }
+}
+
+/// Nested reactive structs (`#[nested]` fields) act as a signal by delegating to their own
+/// signals, this means only the dependencies of the inner fields that actually changed are
+/// triggered.
+impl<D: ComponentData> SignalMethods for D {
+    fn clear(&mut self) {
+        for signal in self.signals_mut() {
+            signal.clear();
+        }
+    }
+
+    fn register_dep(&mut self, dep: HookKey) {
+        for signal in self.signals_mut() {
+            signal.register_dep(dep);
+        }
     }
 
-    fn deps(&mut self) -> std::vec::Drain<'_, HookKey> {
-        self.deps.drain(..)
+    fn drain_changed_deps(&mut self, hooks: &mut Vec<HookKey>) {
+        for signal in self.signals_mut() {
+            signal.drain_changed_deps(hooks);
+        }
+    }
+
+    #[cfg(feature = "profiling")]
+    fn flags(&mut self) -> SignalState {
+        let mut result = SignalState {
+            written: false,
+            read: false,
+        };
+        for signal in self.signals_mut() {
+            let flags = signal.flags();
+            result.written |= flags.written;
+            result.read |= flags.read;
+        }
+        result
     }
 }
 
@@ -213,10 +376,28 @@ mod ergonomin_ops {
 #[cfg(test)]
 mod tests {
     use super::{Signal, SignalMethods};
+    use crate::state::HookKey;
     // We put signals in a struct to simulate the real usage pattern where they are always fields
     // in a &ref
     struct Holder<T>(Signal<T>);
 
+    #[test]
+    fn drain_changed_deps() {
+        let foo = &mut Holder(Signal::new(10));
+        let dep = HookKey::default();
+
+        let _ = *foo.0;
+        foo.0.register_dep(dep);
+
+        let mut hooks = Vec::new();
+        foo.0.drain_changed_deps(&mut hooks);
+        assert_eq!(hooks, Vec::new());
+
+        *foo.0 = 20;
+        foo.0.drain_changed_deps(&mut hooks);
+        assert_eq!(hooks, vec![dep]);
+    }
+
     #[test]
     fn reading() {
         let foo = &Holder(Signal::new(10));
@@ -243,6 +424,79 @@ mod tests {
         assert!(foo.0.read.get());
     }
 
+    #[test]
+    fn get_or_insert_with() {
+        let foo = &mut Holder(Signal::new(None));
+        assert_eq!(*foo.0.get_or_insert_with(|| 10), 10);
+        assert!(foo.0.changed());
+
+        foo.0.clear();
+        assert_eq!(*foo.0.get_or_insert_with(|| 20), 10);
+        assert!(!foo.0.changed());
+    }
+
+    #[test]
+    fn set_if_changed_same() {
+        let foo = &mut Holder(Signal::new(10));
+        foo.0.set_if_changed(10);
+
+        assert!(!foo.0.changed());
+    }
+
+    #[test]
+    fn set_if_changed_different() {
+        let foo = &mut Holder(Signal::new(10));
+        foo.0.set_if_changed(20);
+
+        assert!(foo.0.changed());
+        assert_eq!(*foo.0, 20);
+    }
+
+    #[test]
+    fn toggle() {
+        let foo = &mut Holder(Signal::new(false));
+        foo.0.toggle();
+
+        assert!(foo.0.changed());
+        assert!(*foo.0);
+    }
+
+    #[test]
+    fn increment() {
+        let foo = &mut Holder(Signal::new(10_u8));
+        foo.0.increment();
+        assert_eq!(*foo.0, 11);
+
+        foo.0.increment_by(4);
+        assert_eq!(*foo.0, 15);
+
+        foo.0.decrement_by(5);
+        assert_eq!(*foo.0, 10);
+
+        foo.0.decrement();
+        assert_eq!(*foo.0, 9);
+
+        assert!(foo.0.changed());
+    }
+
+    #[test]
+    fn increment_saturates() {
+        let foo = &mut Holder(Signal::new(u8::MAX));
+        foo.0.increment();
+
+        assert!(!foo.0.changed());
+        assert_eq!(*foo.0, u8::MAX);
+    }
+
+    #[test]
+    fn increment_float() {
+        let foo = &mut Holder(Signal::new(1.5_f32));
+        foo.0.increment_by(1.0);
+
+        assert!(foo.0.changed());
+        assert!((*foo.0 - 2.5).abs() < f32::EPSILON);
+    }
+
     #[cfg(feature = "ergonomic_ops")]
     mod ergonomic_ops {
         use super::*;
@@ -1,18 +1,40 @@
 //! Types for handling the component state
 
-use std::cell::{RefCell, RefMut};
+use std::any::Any;
+use std::borrow::Cow;
+use std::cell::{Cell, Ref, RefCell, RefMut};
+use std::fmt::Display;
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
 use std::rc::{Rc, Weak};
+use std::task::{Context, Poll};
+use std::time::Duration;
 
 use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
 use ouroboros::self_referencing;
 use slotmap::{SlotMap, new_key_type};
-
-use crate::component::Component;
+use wasm_bindgen::JsCast;
+
+use crate::async_utils::{ClipboardError, Clock, WebSocket, WebSocketConnection};
+use crate::callbacks::EventHandler;
+use crate::component::{Component, ComponentBase};
+use crate::events::Event;
+use crate::html_elements::NodeRef;
+use crate::navigation::{QueryParam, QueryParamListener, UnloadGuard};
 use crate::render_callbacks::DummyHook;
-use crate::signal::{ReactiveHook, RenderingState, SignalMethods, UpdateResult};
+use crate::shared::{SharedRead, SharedSignal, SharedSubscriber};
+use crate::signal::{ReactiveHook, RenderingState, SignalMethods, SignalState, UpdateResult};
+use crate::title::TitleEntry;
 use crate::utils::{self, SmallAny, debug_expect};
+use crate::watchers::{
+    FocusTrap,
+    Intersection,
+    IntersectionOptions,
+    IntersectionWatcher,
+    ResizeWatcher,
+    ScrollWatcher,
+};
 
 /// Trait implemented on the reactive struct generated by the derive macro
 pub trait ComponentData: Sized + 'static {
@@ -25,6 +47,10 @@ pub trait ComponentData: Sized + 'static {
     /// Returns mutable references to the signals
     #[doc(hidden)]
     fn signals_mut(&mut self) -> Self::FieldRef<'_>;
+    /// Returns the names of the fields, in the same order as `signals_mut`.
+    ///
+    /// Tuple structs use the field index as the name.
+    fn field_names() -> &'static [&'static str];
     /// Clear signals and return the current state
     fn pop_signals(&mut self) -> Self::SignalState;
     /// Set signals to the given state
@@ -37,6 +63,10 @@ pub(crate) type KeepAlive = Box<dyn SmallAny>;
 
 new_key_type! { pub(crate) struct HookKey; }
 
+/// Passes a message to the parent handler immediately, returning the message if the parent
+/// state is currently borrowed.
+pub(crate) type SyncDelivery<M> = Rc<dyn Fn(M) -> Option<M>>;
+
 /// The core component state, stores all framework data
 pub struct State<T: Component> {
     /// The user (macro) defined reactive struct
@@ -50,6 +80,30 @@ pub struct State<T: Component> {
     next_insertion_order_value: u64,
     /// The sender for the parent listning to this
     send_to_parent: Option<UnboundedSender<T::EmitMessage>>,
+    /// Passes a message to the parent handler immediately, returning it if the parent is busy
+    deliver_to_parent: Option<SyncDelivery<T::EmitMessage>>,
+    /// Cancel handles for tasks started with `spawn`, dropping these cancels the tasks
+    tasks: Vec<futures_channel::oneshot::Sender<()>>,
+    /// The root element of the component, set once it has been rendered
+    root: Option<web_sys::Element>,
+    /// The clock started by `use_clock`
+    clock: Option<Clock>,
+    /// Shared signals read since the last `clear`, these are subscribed to in `reg_dep`
+    shared_reads: RefCell<Vec<Rc<dyn SharedRead>>>,
+    /// The key of the list item currently being rendered, used by `on_item` handlers
+    item_key: Option<Rc<dyn Any>>,
+    /// Observers, listeners and sockets started by the `use_*` methods, these clean up after
+    /// themselves when dropped with the component
+    guards: Vec<KeepAlive>,
+    /// Passes errors not handled by this component on to the parent component
+    error_parent: Option<Rc<dyn Fn(String)>>,
+    /// The app wide context given to `mount_with_context`, shared by the whole tree
+    context: Option<Rc<dyn Any>>,
+    /// Has `on_mount` been called, and `on_unmount` not yet
+    pub(crate) mounted: bool,
+    /// Did creating part of the dom fail during the current render, in which case the
+    /// `render_fallback` of the component is rendered instead
+    pub(crate) render_failed: bool,
 }
 
 impl<T: Component> Deref for State<T> {
@@ -83,6 +137,17 @@ impl<T: Component> State<T> {
             hooks: SlotMap::default(),
             next_insertion_order_value: 0,
             send_to_parent: None,
+            deliver_to_parent: None,
+            tasks: Vec::new(),
+            root: None,
+            clock: None,
+            shared_reads: RefCell::new(Vec::new()),
+            item_key: None,
+            guards: Vec::new(),
+            error_parent: None,
+            context: None,
+            mounted: false,
+            render_failed: false,
         };
         let this = Rc::new(RefCell::new(this));
 
@@ -91,6 +156,65 @@ impl<T: Component> State<T> {
         this
     }
 
+    /// Get the root element of this component.
+    ///
+    /// This is `None` until the component has been rendered (so it is always `None` in
+    /// `on_mount`), or if the component does not render to a element (for example a text node).
+    ///
+    /// If the root of the component is a reactive closure this is updated to the newly rendered
+    /// element when it re-runs, so avoid holding on to it across updates.
+    #[must_use]
+    pub fn root_node(&self) -> Option<web_sys::Element> {
+        self.root.clone()
+    }
+
+    /// Find the first element matching the css `selector` inside the root node of this component.
+    ///
+    /// Unlike `document.querySelector` this only searches this instance of the component, so it
+    /// does not collide with other instances. The root node itself is not matched.
+    ///
+    /// Returns `None` if there is no match, the selector is invalid, or the component has not been
+    /// rendered yet (see [`root_node`](Self::root_node)).
+    #[must_use]
+    pub fn query_selector(&self, selector: &str) -> Option<web_sys::Element> {
+        self.root.as_ref()?.query_selector(selector).ok().flatten()
+    }
+
+    /// Set the root node from the rendered component node
+    pub(crate) fn set_root_node(&mut self, node: &web_sys::Node) {
+        self.root = node.dyn_ref::<web_sys::Element>().cloned();
+    }
+
+    /// If `old` is the root node of this component, make `new` the root node and apply the style
+    /// scope to it, as a reactive root re-rendering produces a element without it.
+    pub(crate) fn update_root_node(&mut self, old: &web_sys::Node, new: &web_sys::Node) {
+        if self
+            .root
+            .as_ref()
+            .is_some_and(|root| root.is_same_node(Some(old)))
+        {
+            self.set_root_node(new);
+            crate::component::apply_style_scope::<T>(new);
+        }
+    }
+
+    /// The key of the list item currently being rendered
+    pub(crate) fn item_key(&self) -> Option<Rc<dyn Any>> {
+        self.item_key.clone()
+    }
+
+    /// Run `func` with `key` as the current list item key, restoring the previous one afterwards
+    pub(crate) fn with_item_key<R>(
+        &mut self,
+        key: Option<Rc<dyn Any>>,
+        func: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let outer = std::mem::replace(&mut self.item_key, key);
+        let result = func(self);
+        self.item_key = outer;
+        result
+    }
+
     /// Get a weak reference to this state
     #[expect(clippy::expect_used, reason = "This is always set in the `new` method")]
     fn weak(&self) -> Weak<RefCell<Self>> {
@@ -102,6 +226,31 @@ impl<T: Component> State<T> {
         for signal in self.data.signals_mut() {
             signal.clear();
         }
+        if let Some(clock) = &mut self.clock {
+            clock.now.clear();
+        }
+        self.shared_reads.get_mut().clear();
+    }
+
+    /// Clear all signals and return their current state, including the clock and shared signals.
+    pub(crate) fn pop_all_signals(&mut self) -> AllSignalState<T> {
+        let clock = self.clock.as_mut().map(|clock| clock.now.pop_state());
+        let shared = std::mem::take(self.shared_reads.get_mut());
+        (self.data.pop_signals(), clock, shared)
+    }
+
+    /// Restore the state returned by `pop_all_signals`
+    pub(crate) fn set_all_signals(&mut self, (data, clock, shared): AllSignalState<T>) {
+        self.data.set_signals(data);
+        if let (Some(clock), Some(state)) = (&mut self.clock, clock) {
+            clock.now.set_state(state);
+        }
+        *self.shared_reads.get_mut() = shared;
+    }
+
+    /// Mark a shared signal as read
+    pub(crate) fn read_shared(&self, signal: Rc<dyn SharedRead>) {
+        self.shared_reads.borrow_mut().push(signal);
     }
 
     /// Insert a hook and keep track of insertion order
@@ -124,9 +273,30 @@ impl<T: Component> State<T> {
 
     /// Register a dependency for all read signals
     pub(crate) fn reg_dep(&mut self, dep: HookKey) {
+        #[cfg(feature = "profiling")]
+        {
+            let fields = T::Data::field_names().iter().copied();
+            for (signal, field) in self.data.signals_mut().into_iter().zip(fields) {
+                if signal.flags().read {
+                    crate::profiling::record_read(std::any::type_name::<T>(), field);
+                }
+            }
+        }
+
         for signal in self.data.signals_mut() {
             signal.register_dep(dep);
         }
+        if let Some(clock) = &mut self.clock {
+            clock.now.register_dep(dep);
+        }
+
+        let shared_reads = std::mem::take(self.shared_reads.get_mut());
+        if !shared_reads.is_empty() {
+            let weak: Weak<dyn SharedSubscriber> = self.weak();
+            for shared in shared_reads {
+                shared.subscribe(weak.clone(), dep);
+            }
+        }
     }
 
     /// Remove the hook from the slotmap, runs the function on it, then puts it back.
@@ -151,17 +321,73 @@ impl<T: Component> State<T> {
     /// Loop over signals and update any depdant hooks for changed signals
     pub(crate) fn update(&mut self) {
         let mut hooks = Vec::new();
-        for signal in self.data.signals_mut() {
-            if signal.changed() {
-                hooks.extend(signal.deps());
+        #[cfg(feature = "profiling")]
+        {
+            // Drain the written signals one at a time to attribute the reruns to them
+            let fields = T::Data::field_names().iter().copied();
+            for (signal, field) in self.data.signals_mut().into_iter().zip(fields) {
+                if signal.flags().written {
+                    let before = hooks.len();
+                    signal.drain_changed_deps(&mut hooks);
+                    let reruns = hooks.len().saturating_sub(before);
+                    crate::profiling::record_write(std::any::type_name::<T>(), field, reruns);
+                }
             }
         }
+        for signal in self.data.signals_mut() {
+            signal.drain_changed_deps(&mut hooks);
+        }
+        if let Some(clock) = &mut self.clock {
+            clock.now.drain_changed_deps(&mut hooks);
+        }
+
+        self.run_hooks(hooks);
+    }
+
+    /// Re-run every reactive callback of this component, regardless of what it depends on.
+    ///
+    /// This is a escape hatch for when the reactive system can not observe a change, such as
+    /// modifying a `#[natrix(skip)]` field or state owned by js. It is a blunt instrument, as
+    /// it re-renders the whole component (but not its sub components), prefer storing the state in
+    /// regular fields (or a [`SharedSignal`]) so only the callbacks actually depending on it
+    /// re-run.
+    /// ```rust
+    /// # use std::cell::Cell;
+    /// # use std::rc::Rc;
+    /// # use natrix::prelude::*;
+    /// #[derive(Component)]
+    /// struct Interop {
+    ///     #[natrix(skip)]
+    ///     external: Rc<Cell<u32>>,
+    /// }
+    ///
+    /// impl Component for Interop {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         e::button()
+    ///             .text(|ctx: R<Self>| ctx.external.get())
+    ///             .on::<events::Click>(|ctx: E<Self>, _| ctx.force_update())
+    ///     }
+    /// }
+    /// ```
+    pub fn force_update(&mut self) {
+        let hooks = self.hooks.keys().collect();
+        self.run_hooks(hooks);
+    }
 
+    /// Run the given hooks in insertion order, as well as any hooks they in turn trigger.
+    ///
+    /// This is used by `update`, but can also be used to run hooks that are triggered by something
+    /// other than a signal change (for example a async stream)
+    pub(crate) fn run_hooks(&mut self, mut hooks: Vec<HookKey>) {
         hooks.sort_by_key(|hook_key| Some(self.hooks.get(*hook_key)?.1));
         hooks.dedup_by_key(|hook_key| Some(self.hooks.get(*hook_key)?.1));
         hooks.reverse();
 
         while let Some(hook_key) = hooks.pop() {
+            #[cfg(feature = "profiling")]
+            crate::profiling::record_hook_run();
             self.run_with_hook_and_self(hook_key, |ctx, hook| match hook.update(ctx, hook_key) {
                 UpdateResult::Nothing => {}
                 UpdateResult::RunHook(dep) => {
@@ -176,6 +402,13 @@ impl<T: Component> State<T> {
         }
     }
 
+    /// Drop the given hooks, as well as their child hooks
+    pub(crate) fn drop_hooks(&mut self, hooks: Vec<HookKey>) {
+        for hook in hooks {
+            drop_hook(self, hook);
+        }
+    }
+
     /// Get the unwrapped data referenced by this guard
     pub fn get<'s, F, R>(&'s self, guard: &Guard<F>) -> &'s R
     where
@@ -192,170 +425,849 @@ impl<T: Component> State<T> {
         (guard.getter)(self)
     }
 
-    /// Emit a message to the parent component
-    pub fn emit(&mut self, msg: T::EmitMessage) {
-        if let Some(sender) = self.send_to_parent.as_ref() {
-            debug_expect!(
-                sender.unbounded_send(msg),
-                "Failed to send message on channel, parent likely dropped"
-            );
+    /// Focus the element behind `node_ref`.
+    ///
+    /// If the element is not in the dom yet (for example in `on_mount`) the focus is deferred to
+    /// the next microtask, after the component has been rendered.
+    pub fn focus(&self, node_ref: &NodeRef) {
+        if node_ref.try_focus() {
+            return;
         }
-    }
 
-    /// Register a new sender from the parent component
-    pub(crate) fn register_parent(&mut self, sender: UnboundedSender<T::EmitMessage>) {
-        self.send_to_parent = Some(sender);
-    }
-
-    /// Spawn the listening task with the given callback
-    pub(crate) fn spawn_listening_task<F, M>(&mut self, handler: F, mut rx: UnboundedReceiver<M>)
-    where
-        M: 'static,
-        F: Fn(&mut Self, M) + 'static,
-    {
-        let this = self.deferred_borrow();
+        let node_ref = node_ref.clone();
         wasm_bindgen_futures::spawn_local(async move {
-            while let Some(messages) = utils::recv_all(&mut rx).await {
-                let Some(mut this) = this.borrow_mut() else {
-                    break;
-                };
-                for message in messages {
-                    handler(&mut this, message);
-                }
-            }
+            node_ref.try_focus();
         });
     }
 
-    /// Spawn a async task to recv messages from the parent
-    pub(crate) fn spawn_recivier_task(&mut self, mut rx: UnboundedReceiver<T::ReceiveMessage>) {
-        let this = self.deferred_borrow();
-        wasm_bindgen_futures::spawn_local(async move {
-            while let Some(messages) = utils::recv_all(&mut rx).await {
-                let Some(mut this) = this.borrow_mut() else {
-                    break;
-                };
-                for message in messages {
-                    T::handle_message(&mut this, message);
-                }
-            }
-        });
+    /// Read the current value of the `input`, `textarea` or `select` behind `node_ref`.
+    ///
+    /// This is useful for uncontrolled inputs that are only read when needed, such as when a form
+    /// is submitted, instead of updating a field on every keystroke.
+    /// Returns `None` if the element is not rendered, or is not one of the above.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// use natrix::html_elements::NodeRef;
+    ///
+    /// #[derive(Component)]
+    /// struct Search {
+    ///     #[natrix(skip)]
+    ///     input: NodeRef,
+    ///     query: String,
+    /// }
+    ///
+    /// impl Component for Search {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         e::div()
+    ///             .child(|ctx: R<Self>| e::input().node_ref(&ctx.input))
+    ///             .child(e::button().text("Search").on::<events::Click>(|ctx: E<Self>, _| {
+    ///                 if let Some(query) = ctx.input_value(&ctx.input) {
+    ///                     *ctx.query = query;
+    ///                 }
+    ///             }))
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn input_value(&self, node_ref: &NodeRef) -> Option<String> {
+        node_ref.input_value()
     }
-}
 
-/// Drop all children of the hook
-fn drop_hook<T: Component>(ctx: &mut State<T>, hook: HookKey) {
-    if let Some(hook) = ctx.hooks.remove(hook) {
-        let mut hooks = hook.0.drop_us();
-        for hook in hooks.drain(..) {
-            drop_hook(ctx, hook);
-        }
+    /// The page's `document`, for interop with apis natrix does not wrap.
+    ///
+    /// This is the same cached document natrix uses internally, so it avoids going through
+    /// `window().document()` on every use.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     referrer: String,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::button().text("Where did I come from?").on::<events::Click>(|ctx: E<Self>, _| {
+    ///     *ctx.referrer = ctx.document().referrer();
+    /// })
+    /// # }}
+    /// ```
+    #[must_use]
+    pub fn document(&self) -> web_sys::Document {
+        crate::get_document()
     }
-}
-
-/// Wrapper around a mutable state that only allows read-only access
-///
-/// This holds a mutable state to facilitate a few rendering features such as `.watch`
-pub struct RenderCtx<'c, C: Component> {
-    /// The inner context
-    pub(crate) ctx: &'c mut State<C>,
-    /// The render state for this state
-    pub(crate) render_state: RenderingState<'c>,
-}
 
-impl<C: Component> Deref for RenderCtx<'_, C> {
-    type Target = State<C>;
+    /// Get the value of the cookie `name`, see [`cookies::get_cookie`](crate::cookies::get_cookie).
+    ///
+    /// Cookies are not reactive, so this should be read in event handlers or `on_mount`.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     show_banner: bool,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// # e::div()
+    /// # }
+    /// fn on_mount(ctx: E<Self>) {
+    ///     *ctx.show_banner = ctx.get_cookie("consent").is_none();
+    /// }
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn get_cookie(&self, name: &str) -> Option<String> {
+        crate::cookies::get_cookie(name)
+    }
 
-    fn deref(&self) -> &Self::Target {
-        self.ctx
+    /// Set the cookie `name` to `value`, see [`cookies::set_cookie`](crate::cookies::set_cookie).
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use natrix::prelude::*;
+    /// use natrix::cookies::{CookieOptions, SameSite};
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     show_banner: bool,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::button().text("Accept").on::<events::Click>(|ctx: E<Self>, _| {
+    ///     let options = CookieOptions::new()
+    ///         .max_age(Duration::from_secs(60 * 60 * 24 * 365))
+    ///         .path("/")
+    ///         .same_site(SameSite::Lax);
+    ///     ctx.set_cookie("consent", "all", &options);
+    ///     *ctx.show_banner = false;
+    /// })
+    /// # }}
+    /// ```
+    pub fn set_cookie(&self, name: &str, value: &str, options: &crate::cookies::CookieOptions) {
+        crate::cookies::set_cookie(name, value, options);
     }
-}
 
-impl<C: Component> RenderCtx<'_, C> {
-    /// Calculate the value using the function and cache it using `clone`.
-    /// Then whenever any signals read in the function are modified re-run the function and check
-    /// if the new result is different.
-    /// Only reruns the caller when the item is different.
+    /// Open `url` in the browsing context named `target`, such as `_blank` for a new tab.
     ///
-    /// # Example
+    /// Returns the opened window, or `None` if it was blocked by a popup blocker.
+    /// Browsers usually only allow this in response to user input, so call it from a event
+    /// handler. See [`navigation::open_url`](crate::navigation::open_url).
     /// ```rust
     /// # use natrix::prelude::*;
     /// # #[derive(Component)]
-    /// # struct MyComponent {value: u32}
-    /// #
+    /// # struct MyComponent {
+    /// #     blocked: bool,
+    /// # }
     /// # impl Component for MyComponent {
     /// # type EmitMessage = NoMessages;
     /// # type ReceiveMessage = NoMessages;
     /// # fn render() -> impl Element<Self> {
-    /// # |ctx: R<Self>| {
-    /// if ctx.watch(|ctx| *ctx.value > 2) {
-    ///     e::div().text(|ctx: R<Self>| *ctx.value)
-    /// } else {
-    ///     e::div().text("Value is too low")
-    /// }
-    /// # }}}
+    /// e::button().text("Docs").on::<events::Click>(|ctx: E<Self>, _| {
+    ///     *ctx.blocked = ctx.open_url("https://example.com", "_blank").is_none();
+    /// })
+    /// # }}
     /// ```
-    pub fn watch<T, F>(&mut self, func: F) -> T
-    where
-        F: Fn(&State<C>) -> T + 'static,
-        T: PartialEq + Clone + 'static,
-    {
-        let signal_state = self.ctx.pop_signals();
+    #[must_use = "Use `let _ =` if you dont care if the window was opened"]
+    pub fn open_url(&self, url: &str, target: &str) -> Option<web_sys::Window> {
+        crate::navigation::open_url(url, target)
+    }
 
-        let result = func(self.ctx);
+    /// Reload the current page, see [`navigation::reload`](crate::navigation::reload).
+    pub fn reload(&self) {
+        crate::navigation::reload();
+    }
 
-        let hook = WatchState {
-            calc_value: Box::new(func),
-            last_value: result.clone(),
-            dep: self.render_state.parent_dep,
+    /// Observe the content box size of the element behind `node_ref`, as `(width, height)`.
+    ///
+    /// The returned signal is `(0.0, 0.0)` until the element has been rendered and measured, and
+    /// updates every time the element is resized.
+    /// If the element is not in the dom yet (for example in `on_mount`) observing it is deferred
+    /// to the next microtask. The observer is disconnected once the component is unmounted.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// use natrix::html_elements::NodeRef;
+    /// use natrix::shared::SharedSignal;
+    ///
+    /// #[derive(Component)]
+    /// struct Panel {
+    ///     #[natrix(skip)]
+    ///     panel: NodeRef,
+    ///     #[natrix(skip)]
+    ///     size: Option<SharedSignal<(f64, f64)>>,
+    /// }
+    ///
+    /// impl Component for Panel {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         e::div().child(|ctx: R<Self>| {
+    ///             let width = match &ctx.size {
+    ///                 Some(size) => size.get(ctx).0,
+    ///                 None => 0.0,
+    ///             };
+    ///             e::div()
+    ///                 .node_ref(&ctx.panel)
+    ///                 .text(if width > 600.0 { "Wide" } else { "Narrow" })
+    ///         })
+    ///     }
+    ///
+    ///     fn on_mount(ctx: E<Self>) {
+    ///         let panel = ctx.panel.clone();
+    ///         ctx.size = Some(ctx.use_resize_observer(&panel));
+    ///     }
+    /// }
+    /// ```
+    pub fn use_resize_observer(&mut self, node_ref: &NodeRef) -> SharedSignal<(f64, f64)> {
+        let size = SharedSignal::new((0.0, 0.0));
+        let Some(watcher) = ResizeWatcher::new(size.clone()) else {
+            return size;
         };
-        let me = self.ctx.insert_hook(Box::new(hook));
-        self.ctx.reg_dep(me);
-        self.render_state.hooks.push(me);
-
-        self.ctx.set_signals(signal_state);
+        let watcher = Rc::new(watcher);
+
+        if !watcher.try_observe(node_ref) {
+            let watcher = Rc::downgrade(&watcher);
+            let node_ref = node_ref.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Some(watcher) = watcher.upgrade() {
+                    watcher.try_observe(&node_ref);
+                }
+            });
+        }
 
-        result
+        self.keep_alive(watcher);
+        size
     }
-}
-
-/// The wather hook / signal
-struct WatchState<F, T> {
-    /// Function to calculate the state
-    calc_value: F,
-    /// The previous cached value
-    last_value: T,
-    /// The dependency that owns us.
-    dep: HookKey,
-}
-
-impl<C, F, T> ReactiveHook<C> for WatchState<F, T>
-where
-    C: Component,
-    T: PartialEq,
-    F: Fn(&State<C>) -> T,
-{
-    fn update(&mut self, ctx: &mut State<C>, you: HookKey) -> UpdateResult {
-        ctx.clear();
-        let new_value = (self.calc_value)(ctx);
-        ctx.reg_dep(you);
 
-        if new_value == self.last_value {
-            UpdateResult::Nothing
-        } else {
-            UpdateResult::RunHook(self.dep)
+    /// Call `handler` whenever the element behind `node_ref` enters or leaves the viewport, for
+    /// example to lazy load images or load more items of a infinite list.
+    ///
+    /// The handler is also called once the element is first observed, with its initial
+    /// visibility. If the element is not in the dom yet (for example in `on_mount`) observing it
+    /// is deferred to the next microtask. The observer is disconnected once the component is
+    /// unmounted.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// use natrix::html_elements::NodeRef;
+    /// use natrix::watchers::IntersectionOptions;
+    ///
+    /// #[derive(Component)]
+    /// struct Feed {
+    ///     items: u32,
+    ///     #[natrix(skip)]
+    ///     sentinel: NodeRef,
+    /// }
+    ///
+    /// impl Component for Feed {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         e::div()
+    ///             .child(|ctx: R<Self>| format!("{} items", *ctx.items))
+    ///             .child(|ctx: R<Self>| e::div().node_ref(&ctx.sentinel))
+    ///     }
+    ///
+    ///     fn on_mount(ctx: E<Self>) {
+    ///         let sentinel = ctx.sentinel.clone();
+    ///         let options = IntersectionOptions::new().root_margin("200px");
+    ///         ctx.use_intersection_observer(&sentinel, &options, |ctx: E<Self>, entry| {
+    ///             if entry.is_intersecting {
+    ///                 *ctx.items += 20;
+    ///             }
+    ///         });
+    ///     }
+    /// }
+    /// ```
+    pub fn use_intersection_observer(
+        &mut self,
+        node_ref: &NodeRef,
+        options: &IntersectionOptions,
+        handler: impl Fn(&mut Self, Intersection) + 'static,
+    ) {
+        let deferred = self.deferred_borrow();
+        let watcher = IntersectionWatcher::new(options, move |entry| {
+            if let Some(mut ctx) = deferred.borrow_mut() {
+                handler(&mut ctx, entry);
+            }
+        });
+        let Some(watcher) = watcher else {
+            return;
+        };
+        let watcher = Rc::new(watcher);
+
+        if !watcher.try_observe(node_ref) {
+            let watcher = Rc::downgrade(&watcher);
+            let node_ref = node_ref.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Some(watcher) = watcher.upgrade() {
+                    watcher.try_observe(&node_ref);
+                }
+            });
         }
-    }
 
-    fn drop_us(self: Box<Self>) -> Vec<HookKey> {
-        Vec::new()
+        self.keep_alive(watcher);
     }
-}
 
-/// This guard ensures that when it is in scope the data it was created for is `Some`
-#[cfg_attr(feature = "nightly", must_not_suspend)]
-#[derive(Clone, Copy)]
-#[must_use]
-pub struct Guard<F> {
+    /// The scroll position of the page, as `(x, y)`.
+    ///
+    /// The returned signal is updated at most once per animation frame while the page is
+    /// scrolled, so closures reading it dont re-run on every `scroll` event. The listener is
+    /// removed once the component is unmounted.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// use natrix::shared::SharedSignal;
+    ///
+    /// #[derive(Component)]
+    /// struct Header {
+    ///     #[natrix(skip)]
+    ///     scroll: Option<SharedSignal<(f64, f64)>>,
+    /// }
+    ///
+    /// impl Component for Header {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         e::header()
+    ///             .class("header")
+    ///             .class_toggle("shadow", |ctx: R<Self>| match &ctx.scroll {
+    ///                 Some(scroll) => scroll.get(ctx).1 > 0.0,
+    ///                 None => false,
+    ///             })
+    ///     }
+    ///
+    ///     fn on_mount(ctx: E<Self>) {
+    ///         ctx.scroll = Some(ctx.use_scroll_position());
+    ///     }
+    /// }
+    /// ```
+    pub fn use_scroll_position(&mut self) -> SharedSignal<(f64, f64)> {
+        let position = SharedSignal::new((0.0, 0.0));
+        let watcher = ScrollWatcher::new(position.clone(), || {
+            let window = crate::get_window();
+            Some((window.scroll_x().ok()?, window.scroll_y().ok()?))
+        });
+        watcher.attach(crate::get_window().into());
+        self.keep_alive(watcher);
+        position
+    }
+
+    /// The scroll position of the element behind `node_ref`, as `(x, y)`.
+    ///
+    /// Like [`use_scroll_position`](Self::use_scroll_position), but for a scrollable element.
+    /// If the element is not in the dom yet (for example in `on_mount`) listening is deferred to
+    /// the next microtask.
+    pub fn use_scroll_position_of(&mut self, node_ref: &NodeRef) -> SharedSignal<(f64, f64)> {
+        let position = SharedSignal::new((0.0, 0.0));
+        let element = node_ref.clone();
+        let watcher = Rc::new(ScrollWatcher::new(position.clone(), move || {
+            let element = element.get()?;
+            Some((
+                f64::from(element.scroll_left()),
+                f64::from(element.scroll_top()),
+            ))
+        }));
+
+        if !watcher.try_attach(node_ref) {
+            let watcher = Rc::downgrade(&watcher);
+            let node_ref = node_ref.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Some(watcher) = watcher.upgrade() {
+                    watcher.try_attach(&node_ref);
+                }
+            });
+        }
+
+        self.keep_alive(watcher);
+        position
+    }
+
+    /// Trap keyboard focus inside the element behind `container`, for example for a modal dialog.
+    ///
+    /// While this component is mounted Tab and Shift+Tab cycle through the focusable elements of
+    /// the container instead of leaving it, and focus is moved into the container if it isnt
+    /// already. Once the component is unmounted the element that was focused when this was called
+    /// is focused again.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// use natrix::html_elements::NodeRef;
+    ///
+    /// #[derive(Component)]
+    /// struct Modal {
+    ///     #[natrix(skip)]
+    ///     dialog: NodeRef,
+    /// }
+    ///
+    /// impl Component for Modal {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         e::div().child(|ctx: R<Self>| {
+    ///             e::div()
+    ///                 .node_ref(&ctx.dialog)
+    ///                 .role("dialog")
+    ///                 .child(e::input())
+    ///                 .child(e::button().text("Close"))
+    ///         })
+    ///     }
+    ///
+    ///     fn on_mount(ctx: E<Self>) {
+    ///         let dialog = ctx.dialog.clone();
+    ///         ctx.use_focus_trap(&dialog);
+    ///     }
+    /// }
+    /// ```
+    pub fn use_focus_trap(&mut self, container: &NodeRef) {
+        self.keep_alive(FocusTrap::new(container.clone()));
+    }
+
+    /// Ask the user to confirm leaving the page while `predicate` returns `true`, for example
+    /// when there are unsaved changes.
+    ///
+    /// The predicate is evaluated when the user tries to leave, rather than reactively. Browsers
+    /// show their own generic message, and might skip the prompt if the user never interacted
+    /// with the page. The listener is removed when this component is unmounted.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Editor { dirty: bool }
+    /// # impl Component for Editor {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> { e::div() }
+    /// fn on_mount(ctx: E<Self>) {
+    ///     ctx.use_before_unload(|ctx| *ctx.dirty);
+    /// }
+    /// # }
+    /// ```
+    pub fn use_before_unload(&mut self, predicate: impl Fn(&Self) -> bool + 'static) {
+        let deferred = self.deferred_borrow();
+        self.keep_alive(UnloadGuard::new(move || {
+            deferred.borrow().is_some_and(|ctx| predicate(&ctx))
+        }));
+    }
+
+    /// Bind the url query parameter `name` (as in `?name=value`), returning a handle to read and
+    /// write it.
+    ///
+    /// Reading it gives the value in the current url, or `default` if it is not set, and closures
+    /// reading it re-run when it changes (including on `popstate`, such as the back button).
+    /// Writing it updates the url with `history.replaceState`, so the page is not reloaded and
+    /// other parameters are kept.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// use natrix::navigation::QueryParam;
+    ///
+    /// #[derive(Component)]
+    /// struct Settings {
+    ///     #[natrix(skip)]
+    ///     tab: Option<QueryParam>,
+    /// }
+    ///
+    /// impl Component for Settings {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         e::div()
+    ///             .child(|ctx: R<Self>| match &ctx.tab {
+    ///                 Some(tab) => tab.get(ctx),
+    ///                 None => String::new(),
+    ///             })
+    ///             .child(e::button().text("Privacy").on::<events::Click>(|ctx: E<Self>, _| {
+    ///                 if let Some(tab) = &ctx.tab {
+    ///                     tab.set("privacy");
+    ///                 }
+    ///             }))
+    ///     }
+    ///
+    ///     fn on_mount(ctx: E<Self>) {
+    ///         ctx.tab = Some(ctx.use_query_param("tab", "general"));
+    ///     }
+    /// }
+    /// ```
+    pub fn use_query_param(&mut self, name: &str, default: &str) -> QueryParam {
+        let (listener, param) = QueryParamListener::bind(name, default);
+        self.keep_alive(listener);
+        param
+    }
+
+    /// Open a websocket connection to `url`, which is closed when this component is unmounted.
+    ///
+    /// The returned handle exposes the last received message and the connection status as
+    /// [`SharedSignal`]s, which update the components reading them.
+    ///
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// use natrix::async_utils::{WebSocket, WebSocketStatus};
+    ///
+    /// #[derive(Component)]
+    /// struct Chat {
+    ///     #[natrix(skip)]
+    ///     socket: Option<WebSocket>,
+    /// }
+    ///
+    /// impl Component for Chat {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         e::div()
+    ///             .child(|ctx: R<Self>| match &ctx.socket {
+    ///                 Some(socket) if socket.status().get(ctx) == WebSocketStatus::Open => {
+    ///                     socket.last_message().get(ctx).unwrap_or_default()
+    ///                 }
+    ///                 _ => String::from("Connecting..."),
+    ///             })
+    ///             .child(e::button().text("Ping").on::<events::Click>(|ctx: E<Self>, _| {
+    ///                 if let Some(socket) = &ctx.socket {
+    ///                     let _ = socket.send("ping");
+    ///                 }
+    ///             }))
+    ///     }
+    ///
+    ///     fn on_mount(ctx: E<Self>) {
+    ///         ctx.socket = Some(ctx.use_websocket("wss://example.com/chat"));
+    ///     }
+    /// }
+    /// ```
+    pub fn use_websocket(&mut self, url: &str) -> WebSocket {
+        let (connection, socket) = WebSocketConnection::connect(url);
+        if let Some(connection) = connection {
+            self.keep_alive(connection);
+        }
+        socket
+    }
+
+    /// Keep `guard` alive until this component is dropped
+    fn keep_alive(&mut self, guard: impl SmallAny + 'static) {
+        self.guards.push(Box::new(guard));
+    }
+
+    /// Set the template for the document title, `%s` in it is replaced with the part set by
+    /// [`set_title_part`](Self::set_title_part).
+    ///
+    /// If no part is set the document keeps the title it had before any template or part was
+    /// set, usually the `<title>` from the html file.
+    /// The template is removed when this component is unmounted, and if multiple components set
+    /// a template the most recently set one is used.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct App;
+    /// # impl Component for App {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> { e::div() }
+    /// fn on_mount(ctx: E<Self>) {
+    ///     ctx.set_title_template("%s | MyApp");
+    /// }
+    /// # }
+    /// ```
+    pub fn set_title_template(&mut self, template: impl Into<Cow<'static, str>>) {
+        self.keep_alive(TitleEntry::template(template.into()));
+    }
+
+    /// Set the part of the document title inserted into the template set by
+    /// [`set_title_template`](Self::set_title_template), or the whole title if there is no
+    /// template.
+    ///
+    /// The part is reactive, and is removed when this component is unmounted.
+    /// Nested components mount after their parents, so the part of the innermost component is
+    /// used.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Profile { name: String }
+    /// # impl Component for Profile {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> { e::div() }
+    /// fn on_mount(ctx: E<Self>) {
+    ///     ctx.set_title_part(|ctx| format!("Profile of {}", *ctx.name));
+    /// }
+    /// # }
+    /// ```
+    pub fn set_title_part(&mut self, part: impl Fn(&Self) -> String + 'static) {
+        let signal_state = self.pop_all_signals();
+        let entry = TitleEntry::part();
+        entry.set_part(part(self));
+        let me = self.insert_hook(Box::new(TitleHook { part, entry }));
+        self.reg_dep(me);
+        self.set_all_signals(signal_state);
+    }
+
+    /// Emit a message to the parent component
+    pub fn emit(&mut self, msg: T::EmitMessage) {
+        if let Some(sender) = self.send_to_parent.as_ref() {
+            debug_expect!(
+                sender.unbounded_send(msg),
+                "Failed to send message on channel, parent likely dropped"
+            );
+        }
+    }
+
+    /// Emit a message to the parent component, handling it immediately if possible.
+    ///
+    /// Messages sent with [`emit`](Self::emit) are handled by the parent in a microtask, so the
+    /// parent renders its reaction one tick after the child, which can cause a visible flicker.
+    /// This instead calls the parent handler right away, so both updates happen in the same
+    /// cycle.
+    ///
+    /// If the parent is busy, such as when this is called while the parent is rendering or is
+    /// itself handling a message, this falls back to `emit` to avoid reentrancy. A message
+    /// delivered immediately can arrive before earlier messages that are still queued.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Tab;
+    /// # impl Component for Tab {
+    /// # type EmitMessage = &'static str;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::button().on::<events::Click>(|ctx: E<Self>, _| {
+    ///     ctx.emit_sync("settings");
+    /// })
+    /// # }}
+    /// ```
+    pub fn emit_sync(&mut self, msg: T::EmitMessage) {
+        let msg = match &self.deliver_to_parent {
+            Some(deliver) => deliver(msg),
+            None => Some(msg),
+        };
+        if let Some(msg) = msg {
+            self.emit(msg);
+        }
+    }
+
+    /// Register a new sender from the parent component, and the function delivering messages
+    /// to it immediately
+    pub(crate) fn register_parent(
+        &mut self,
+        sender: UnboundedSender<T::EmitMessage>,
+        deliver: SyncDelivery<T::EmitMessage>,
+    ) {
+        self.send_to_parent = Some(sender);
+        self.deliver_to_parent = Some(deliver);
+    }
+
+    /// Pass a error to [`Component::handle_error`], which might pass it on to the parent
+    /// components.
+    pub fn raise_error(&mut self, error: impl Display) {
+        let Err(error) = T::handle_error(self, error.to_string()) else {
+            return;
+        };
+
+        if let Some(parent) = &self.error_parent {
+            parent(error);
+        } else {
+            #[cfg(feature = "panic_hook")]
+            web_sys::console::error_1(
+                &format!(
+                    "Unhandled error in component {}: {error}",
+                    std::any::type_name::<T>()
+                )
+                .into(),
+            );
+        }
+    }
+
+    /// Register the function passing errors on to the parent component
+    pub(crate) fn register_error_parent(&mut self, parent: Rc<dyn Fn(String)>) {
+        self.error_parent = Some(parent);
+    }
+
+    /// Get the app wide context given to
+    /// [`mount_with_context`](crate::component::mount_with_context).
+    ///
+    /// Returns `None` if the app was mounted without a context, or the context is not a `Ctx`.
+    #[must_use]
+    pub fn context<Ctx: 'static>(&self) -> Option<&Ctx> {
+        self.context.as_deref()?.downcast_ref()
+    }
+
+    /// Set the app wide context, which is passed on to sub components
+    pub(crate) fn register_context(&mut self, context: Option<Rc<dyn Any>>) {
+        self.context = context;
+    }
+
+    /// Get the app wide context to pass on to sub components
+    pub(crate) fn shared_context(&self) -> Option<Rc<dyn Any>> {
+        self.context.clone()
+    }
+
+    /// Create a function raising errors in this component, used by sub components.
+    ///
+    /// The error is raised in a microtask, as the sub component is borrowed while raising it.
+    pub(crate) fn error_forwarder(&mut self) -> Rc<dyn Fn(String)> {
+        let deferred = self.deferred_borrow();
+        Rc::new(move |error| {
+            let deferred = deferred.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let _ = deferred.with_mut(|ctx| ctx.raise_error(error));
+            });
+        })
+    }
+
+    /// Spawn the listening task with the given callback
+    pub(crate) fn spawn_listening_task<F, M>(&mut self, handler: F, mut rx: UnboundedReceiver<M>)
+    where
+        M: 'static,
+        F: Fn(&mut Self, M) + 'static,
+    {
+        let this = self.deferred_borrow();
+        wasm_bindgen_futures::spawn_local(async move {
+            while let Some(messages) = utils::recv_all(&mut rx).await {
+                let Some(mut this) = this.borrow_mut() else {
+                    break;
+                };
+                for message in messages {
+                    handler(&mut this, message);
+                }
+            }
+        });
+    }
+
+    /// Spawn a async task to recv messages from the parent
+    pub(crate) fn spawn_recivier_task(&mut self, mut rx: UnboundedReceiver<T::ReceiveMessage>) {
+        let this = self.deferred_borrow();
+        wasm_bindgen_futures::spawn_local(async move {
+            while let Some(messages) = utils::recv_all(&mut rx).await {
+                let Some(mut this) = this.borrow_mut() else {
+                    break;
+                };
+                for message in messages {
+                    T::handle_message(&mut this, message);
+                }
+            }
+        });
+    }
+}
+
+/// Drop all children of the hook
+fn drop_hook<T: Component>(ctx: &mut State<T>, hook: HookKey) {
+    if let Some(hook) = ctx.hooks.remove(hook) {
+        let mut hooks = hook.0.drop_us();
+        for hook in hooks.drain(..) {
+            drop_hook(ctx, hook);
+        }
+    }
+}
+
+/// Wrapper around a mutable state that only allows read-only access
+///
+/// This holds a mutable state to facilitate a few rendering features such as `.watch`
+pub struct RenderCtx<'c, C: Component> {
+    /// The inner context
+    pub(crate) ctx: &'c mut State<C>,
+    /// The render state for this state
+    pub(crate) render_state: RenderingState<'c>,
+}
+
+impl<C: Component> Deref for RenderCtx<'_, C> {
+    type Target = State<C>;
+
+    fn deref(&self) -> &Self::Target {
+        self.ctx
+    }
+}
+
+impl<C: Component> RenderCtx<'_, C> {
+    /// Calculate the value using the function and cache it using `clone`.
+    /// Then whenever any signals read in the function are modified re-run the function and check
+    /// if the new result is different.
+    /// Only reruns the caller when the item is different.
+    ///
+    /// Multiple conditions can be fused into one hook by returning a tuple, in which case the
+    /// caller reruns only when the tuple as a whole changes.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {value: u32}
+    /// #
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// # |ctx: R<Self>| {
+    /// if ctx.watch(|ctx| *ctx.value > 2) {
+    ///     e::div().text(|ctx: R<Self>| *ctx.value)
+    /// } else {
+    ///     e::div().text("Value is too low")
+    /// }
+    /// # }}}
+    /// ```
+    pub fn watch<T, F>(&mut self, func: F) -> T
+    where
+        F: Fn(&State<C>) -> T + 'static,
+        T: PartialEq + Clone + 'static,
+    {
+        let signal_state = self.ctx.pop_all_signals();
+
+        let result = func(self.ctx);
+
+        let hook = WatchState {
+            calc_value: Box::new(func),
+            last_value: result.clone(),
+            dep: self.render_state.parent_dep,
+        };
+        let me = self.ctx.insert_hook(Box::new(hook));
+        self.ctx.reg_dep(me);
+        self.render_state.hooks.push(me);
+
+        self.ctx.set_all_signals(signal_state);
+
+        result
+    }
+
+    /// Get the current time from the shared clock of this component, starting it if needed.
+    ///
+    /// See [`State::use_clock`]
+    pub fn use_clock(&mut self, tick: Duration) -> f64 {
+        self.ctx.use_clock(tick)
+    }
+}
+
+/// The wather hook / signal
+struct WatchState<F, T> {
+    /// Function to calculate the state
+    calc_value: F,
+    /// The previous cached value
+    last_value: T,
+    /// The dependency that owns us.
+    dep: HookKey,
+}
+
+impl<C, F, T> ReactiveHook<C> for WatchState<F, T>
+where
+    C: Component,
+    T: PartialEq,
+    F: Fn(&State<C>) -> T,
+{
+    fn update(&mut self, ctx: &mut State<C>, you: HookKey) -> UpdateResult {
+        ctx.clear();
+        let new_value = (self.calc_value)(ctx);
+        ctx.reg_dep(you);
+
+        if new_value == self.last_value {
+            UpdateResult::Nothing
+        } else {
+            UpdateResult::RunHook(self.dep)
+        }
+    }
+
+    fn drop_us(self: Box<Self>) -> Vec<HookKey> {
+        Vec::new()
+    }
+}
+
+/// This guard ensures that when it is in scope the data it was created for is `Some`
+#[cfg_attr(feature = "nightly", must_not_suspend)]
+#[derive(Clone, Copy)]
+#[must_use]
+pub struct Guard<F> {
     /// The closure for getting the value from a ctx
     getter: F,
 }
@@ -448,6 +1360,29 @@ pub struct Guard<F> {
 /// }
 /// # }}}
 /// ```
+///
+/// ## Chains
+/// For values nested in multiple `Option`s use the `@chain` version, which allows using `?` in
+/// the expression. The guard is `None` if any link of the chain is `None`, and the surrounding
+/// closure re-runs whenever this changes.
+/// ```rust
+/// # use natrix::prelude::*;
+/// # struct Address {city: Option<String>}
+/// # #[derive(Component)]
+/// # struct MyComponent {address: Option<Address>}
+/// # impl Component for MyComponent {
+/// # type EmitMessage = NoMessages;
+/// # type ReceiveMessage = NoMessages;
+/// # fn render() -> impl Element<Self> {
+/// # |ctx: R<Self>| {
+/// if let Some(city_guard) = guard_option!(@chain |ctx| ctx.address.as_ref()?.city.as_ref()) {
+///    e::div().text(move |ctx: R<Self>| ctx.get(&city_guard).clone())
+/// } else {
+///    e::div().text("No city")
+/// }
+/// # }}}
+/// ```
+/// `@owned @chain` is the same for owned values.
 #[macro_export]
 macro_rules! guard_option {
     (| $ctx:ident | $expr:expr) => {
@@ -468,6 +1403,24 @@ macro_rules! guard_option {
             None
         }
     };
+    (@chain | $ctx:ident | $expr:expr) => {
+        if $ctx.watch(move |$ctx| (move || $expr)().is_some()) {
+            Some(::natrix::macro_ref::Guard::new::<Self, _>(move |$ctx| {
+                (move || $expr)().expect("Guard used on None value")
+            }))
+        } else {
+            None
+        }
+    };
+    (@owned @chain | $ctx:ident | $expr:expr) => {
+        if $ctx.watch(move |$ctx| (move || $expr)().is_some()) {
+            Some(::natrix::macro_ref::Guard::new_owned::<Self, _>(
+                move |$ctx| (move || $expr)().expect("Guard used on None value"),
+            ))
+        } else {
+            None
+        }
+    };
 }
 
 /// Get a guard handle that can be used to retrieve the `Ok` variant of a option without having to
@@ -498,6 +1451,100 @@ macro_rules! guard_result {
     };
 }
 
+/// Render one of two elements depending on if a `Result` is `Ok` or `Err`, giving each branch a
+/// guard for the value.
+///
+/// This is `guard_result!` combined with the `match`, the branches are boxed so they can return
+/// different element types. Like `guard_result!` the surrounding closure only reruns when the
+/// result switches between `Ok` and `Err`.
+/// ```rust
+/// # use natrix::prelude::*;
+/// #[derive(Component)]
+/// struct Profile {
+///     user: Result<String, String>,
+/// }
+///
+/// impl Component for Profile {
+///     # type EmitMessage = NoMessages;
+///     # type ReceiveMessage = NoMessages;
+///     fn render() -> impl Element<Self> {
+///         e::div().child(|ctx: R<Self>| {
+///             match_guard!(|ctx| ctx.user.as_ref(),
+///                 Ok(user) => e::h1().text(move |ctx: R<Self>| ctx.get(&user).clone()),
+///                 Err(error) => e::p()
+///                     .class("error")
+///                     .text(move |ctx: R<Self>| ctx.get(&error).clone()),
+///             )
+///         })
+///     }
+/// }
+/// ```
+/// Like `guard_result` a `@owned` version is also available.
+#[macro_export]
+macro_rules! match_guard {
+    (| $ctx:ident | $expr:expr, Ok($ok:pat) => $ok_body:expr, Err($err:pat) => $err_body:expr $(,)?) => {
+        match ::natrix::guard_result!(|$ctx| $expr) {
+            Ok($ok) => ::natrix::element::Element::into_box($ok_body),
+            Err($err) => ::natrix::element::Element::into_box($err_body),
+        }
+    };
+    (@owned | $ctx:ident | $expr:expr, Ok($ok:pat) => $ok_body:expr, Err($err:pat) => $err_body:expr $(,)?) => {
+        match ::natrix::guard_result!(@owned |$ctx| $expr) {
+            Ok($ok) => ::natrix::element::Element::into_box($ok_body),
+            Err($err) => ::natrix::element::Element::into_box($err_body),
+        }
+    };
+}
+
+/// Get a tuple of guard handles when all of the given options are `Some`.
+///
+/// This fuses the checks into a single `ctx.watch`, so the surrounding closure only reruns when
+/// whether *all* the values are `Some` changes, rather than creating a hook per condition.
+/// ```rust
+/// # use natrix::prelude::*;
+/// # #[derive(Component)]
+/// # struct MyComponent {name: Option<String>, age: Option<u8>}
+/// # impl Component for MyComponent {
+/// # type EmitMessage = NoMessages;
+/// # type ReceiveMessage = NoMessages;
+/// # fn render() -> impl Element<Self> {
+/// # |ctx: R<Self>| {
+/// if let Some((name, age)) = guard_tuple!(|ctx| ctx.name.as_ref(), ctx.age.as_ref()) {
+///     e::div()
+///         .text(move |ctx: R<Self>| ctx.get(&name).clone())
+///         .text(move |ctx: R<Self>| *ctx.get(&age))
+/// } else {
+///     e::div().text("Missing data")
+/// }
+/// # }}}
+/// ```
+/// Like `guard_option` a `@owned` version is also available.
+#[macro_export]
+macro_rules! guard_tuple {
+    (| $ctx:ident | $($expr:expr),+ $(,)?) => {
+        if $ctx.watch(move |$ctx| [$($expr.is_some()),+].into_iter().all(|is_some| is_some)) {
+            Some(($(
+                ::natrix::macro_ref::Guard::new::<Self, _>(move |$ctx| {
+                    $expr.expect("Guard used on None value")
+                }),
+            )+))
+        } else {
+            None
+        }
+    };
+    (@owned | $ctx:ident | $($expr:expr),+ $(,)?) => {
+        if $ctx.watch(move |$ctx| [$($expr.is_some()),+].into_iter().all(|is_some| is_some)) {
+            Some(($(
+                ::natrix::macro_ref::Guard::new_owned::<Self, _>(
+                    move |$ctx| $expr.expect("Guard used on None value"),
+                ),
+            )+))
+        } else {
+            None
+        }
+    };
+}
+
 impl<F> Guard<F> {
     #[doc(hidden)]
     pub fn new<C, R>(getter: F) -> Self
@@ -543,6 +1590,22 @@ struct DeferredRefInner<'p, T: Component> {
 #[must_use]
 pub struct DeferredRef<'p, T: Component>(DeferredRefInner<'p, T>);
 
+/// The read-only version of `DeferredRefInner`
+#[self_referencing]
+struct DeferredReadRefInner<'p, T: Component> {
+    rc: Rc<RefCell<State<T>>>,
+    lifetime: PhantomData<&'p ()>,
+    #[borrows(rc)]
+    #[covariant]
+    reference: Ref<'this, State<T>>,
+}
+
+/// a `Ref` that also holds a `Rc`.
+/// See `DeferredCtx::borrow` for the semantics and safety
+#[cfg_attr(feature = "nightly", must_not_suspend)]
+#[must_use]
+pub struct DeferredReadRef<'p, T: Component>(DeferredReadRefInner<'p, T>);
+
 impl<T: Component> DeferredCtx<T> {
     /// Borrow this `Weak<RefCell<...>>`, this will create a `Rc` for as long as the borrow is
     /// active. Returns `None` if the component was dropped. Its recommended to use the
@@ -586,23 +1649,134 @@ impl<T: Component> DeferredCtx<T> {
         )
     )]
     #[must_use]
-    pub fn borrow_mut(&self) -> Option<DeferredRef<'_, T>> {
+    pub fn borrow_mut(&self) -> Option<DeferredRef<'_, T>> {
+        #[cfg(feature = "panic_hook")]
+        assert!(!crate::panics::has_panicked());
+
+        let rc = self.inner.upgrade()?;
+        let borrow = DeferredRefInner::try_new(rc, PhantomData, |rc| rc.try_borrow_mut());
+
+        let Ok(mut borrow) = borrow else {
+            debug_assert!(
+                false,
+                "Deferred state borrowed while already borrowed. This might happen due to holding it across a yield point"
+            );
+            return None;
+        };
+
+        borrow.with_reference_mut(|ctx| ctx.clear());
+        Some(DeferredRef(borrow))
+    }
+
+    /// Borrow this `Weak<RefCell<...>>` like [`borrow_mut`](Self::borrow_mut), but return
+    /// `None` instead of asserting if it is already borrowed.
+    pub(crate) fn try_borrow_mut(&self) -> Option<DeferredRef<'_, T>> {
+        crate::return_if_panic!(None);
+
+        let rc = self.inner.upgrade()?;
+        let mut borrow =
+            DeferredRefInner::try_new(rc, PhantomData, |rc| rc.try_borrow_mut()).ok()?;
+        borrow.with_reference_mut(|ctx| ctx.clear());
+        Some(DeferredRef(borrow))
+    }
+
+    /// Borrow this `Weak<RefCell<...>>` read-only, returning `None` if the component was dropped.
+    ///
+    /// Unlike [`borrow_mut`](Self::borrow_mut) this does not clear the reactive flags, nor trigger
+    /// a reactive update when dropped. This is useful for async code that only needs to read the
+    /// state, for example to log it or take a snapshot to send to a server.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # use natrix::state::DeferredCtx;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     draft: String,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> { e::div() }
+    /// # }
+    /// # async fn save(_draft: String) {}
+    /// async fn autosave(ctx: DeferredCtx<MyComponent>) -> Option<()> {
+    ///     let draft = ctx.borrow()?.draft.clone();
+    ///     save(draft).await;
+    ///     Some(())
+    /// }
+    /// ```
+    ///
+    /// # Borrow Safety
+    /// The same rules as for [`borrow_mut`](Self::borrow_mut) apply, this should *NOT* be held
+    /// across `.await` points. Multiple read-only borrows can be active at once, but not alongside
+    /// a mutable borrow.
+    #[cfg_attr(
+        feature = "panic_hook",
+        expect(
+            clippy::missing_panics_doc,
+            reason = "This happens when we already are in a panic"
+        )
+    )]
+    #[must_use]
+    pub fn borrow(&self) -> Option<DeferredReadRef<'_, T>> {
         #[cfg(feature = "panic_hook")]
         assert!(!crate::panics::has_panicked());
 
         let rc = self.inner.upgrade()?;
-        let borrow = DeferredRefInner::try_new(rc, PhantomData, |rc| rc.try_borrow_mut());
+        let borrow = DeferredReadRefInner::try_new(rc, PhantomData, |rc| rc.try_borrow());
 
-        let Ok(mut borrow) = borrow else {
+        let Ok(borrow) = borrow else {
             debug_assert!(
                 false,
-                "Deferred state borrowed while already borrowed. This might happen due to holding it across a yield point"
+                "Deferred state borrowed while mutably borrowed. This might happen due to holding it across a yield point"
             );
             return None;
         };
 
-        borrow.with_reference_mut(|ctx| ctx.clear());
-        Some(DeferredRef(borrow))
+        Some(DeferredReadRef(borrow))
+    }
+
+    /// Run the given function with a mutable borrow of the state, triggering a reactive update
+    /// once it returns.
+    ///
+    /// This is the same wrapping the framework does for event handlers, and is useful for
+    /// bridging callbacks created outside the framework (for example a js `Closure`) into the
+    /// reactive system.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # use wasm_bindgen::prelude::Closure;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     resized: u32,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> { e::div() }
+    /// # }
+    /// # fn example(ctx: E<MyComponent>) {
+    /// let deferred = ctx.deferred_borrow();
+    /// let on_resize = Closure::<dyn Fn()>::new(move || {
+    ///     deferred.with_mut(|ctx| *ctx.resized += 1);
+    /// });
+    /// # }
+    /// ```
+    ///
+    /// Returns `None` if the component was dropped, a panic has happened, or the state is already
+    /// borrowed. The same borrow rules as [`borrow_mut`](Self::borrow_mut) apply, calling this
+    /// from within a event handler or another `with_mut` is a bug.
+    pub fn with_mut<R>(&self, func: impl FnOnce(&mut State<T>) -> R) -> Option<R> {
+        crate::return_if_panic!(None);
+
+        let mut borrow = self.borrow_mut()?;
+        Some(func(&mut borrow))
+    }
+}
+
+impl<T: Component> Clone for DeferredCtx<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
     }
 }
 
@@ -619,6 +1793,14 @@ impl<T: Component> DerefMut for DeferredRef<'_, T> {
     }
 }
 
+impl<T: Component> Deref for DeferredReadRef<'_, T> {
+    type Target = State<T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.borrow_reference()
+    }
+}
+
 impl<T: Component> Drop for DeferredRef<'_, T> {
     fn drop(&mut self) {
         self.0.with_reference_mut(|ctx| {
@@ -648,4 +1830,700 @@ impl<T: Component> State<T> {
             let _ = future.await;
         });
     }
+
+    /// Write `text` to the clipboard.
+    ///
+    /// The write is started immediately, so calling this directly in a event handler keeps the
+    /// user activation browsers require for clipboard access, the returned future only waits for
+    /// it to finish.
+    /// If the clipboard is unavailable or access is denied the future resolves to a
+    /// [`ClipboardError`].
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     copied: bool,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::button()
+    ///     .text(|ctx: R<Self>| if *ctx.copied { "Copied!" } else { "Copy" })
+    ///     .on::<events::Click>(|ctx: E<Self>, _| {
+    ///         let write = ctx.write_clipboard("Hello World");
+    ///         ctx.use_async(async |ctx| {
+    ///             let copied = write.await.is_ok();
+    ///             *ctx.borrow_mut()?.copied = copied;
+    ///             Some(())
+    ///         });
+    ///     })
+    /// # }}
+    /// ```
+    pub fn write_clipboard(
+        &self,
+        text: &str,
+    ) -> impl Future<Output = Result<(), ClipboardError>> + 'static {
+        crate::async_utils::write_clipboard(text)
+    }
+
+    /// Read text from the clipboard.
+    ///
+    /// Like [`write_clipboard`](Self::write_clipboard) the read is started immediately, and the
+    /// future resolves to a [`ClipboardError`] if the clipboard is unavailable or access is
+    /// denied.
+    pub fn read_clipboard(&self) -> impl Future<Output = Result<String, ClipboardError>> + 'static {
+        crate::async_utils::read_clipboard()
+    }
+
+    /// Spawn a async task in the local event loop, returning a handle to its result.
+    ///
+    /// This works like [`use_async`](Self::use_async), but the value returned by the future can
+    /// be retrieved by awaiting the [`TaskHandle`]. The task is cancelled when the component is
+    /// unmounted, dropping the handle does *not* cancel it.
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # async fn fetch_count() -> u8 { 0 }
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     count: u8,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::button().on::<events::Click>(|ctx: E<Self>, _| {
+    ///     let task = ctx.spawn(async |_ctx| Some(fetch_count().await));
+    ///     ctx.use_async(async move |ctx| {
+    ///         let count = task.await?;
+    ///         *ctx.borrow_mut()?.count = count;
+    ///         Some(())
+    ///     });
+    /// })
+    /// # }}
+    /// ```
+    // This is `&mut` to make sure it cant be called in render callbacks.
+    pub fn spawn<C, F, R>(&mut self, func: C) -> TaskHandle<R>
+    where
+        C: FnOnce(DeferredCtx<T>) -> F,
+        F: Future<Output = Option<R>> + 'static,
+        R: 'static,
+    {
+        self.tasks.retain(|cancel| !cancel.is_canceled());
+        let (cancel_tx, cancel_rx) = futures_channel::oneshot::channel::<()>();
+        self.tasks.push(cancel_tx);
+
+        let (result_tx, result_rx) = futures_channel::oneshot::channel();
+        let future = func(self.deferred_borrow());
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let future = std::pin::pin!(future);
+            if let futures_util::future::Either::Right((Some(result), _)) =
+                futures_util::future::select(cancel_rx, future).await
+            {
+                let _ = result_tx.send(result);
+            }
+        });
+
+        TaskHandle { result: result_rx }
+    }
+
+    /// Run the `fetch` future for the value returned by `key`, storing the result in the
+    /// [`Resource`] returned by `target`.
+    /// Whenever any signals read in `key` change and the key is different the previous fetch is
+    /// cancelled and a new one is started.
+    ///
+    /// The resource is set to `Resource::Loading` at the start of every fetch, and responses from
+    /// cancelled fetches are discarded.
+    ///
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// # use natrix::state::Resource;
+    /// # async fn fetch_user(id: u32) -> Result<String, String> { Ok(String::new()) }
+    /// #[derive(Component)]
+    /// struct Profile {
+    ///     id: u32,
+    ///     user: Resource<String, String>,
+    /// }
+    ///
+    /// impl Component for Profile {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         e::div().child(|ctx: R<Self>| match &*ctx.user {
+    ///             Resource::Loading => "Loading...".to_string(),
+    ///             Resource::Ready(user) => user.clone(),
+    ///             Resource::Error(err) => err.clone(),
+    ///         })
+    ///     }
+    ///
+    ///     fn on_mount(ctx: E<Self>) {
+    ///         ctx.use_resource(|ctx| *ctx.id, fetch_user, |ctx| &mut *ctx.user);
+    ///     }
+    /// }
+    /// ```
+    pub fn use_resource<K, F, Fut, V, Er, S>(
+        &mut self,
+        key: impl Fn(&Self) -> K + 'static,
+        fetch: F,
+        target: S,
+    ) where
+        K: PartialEq + Clone + 'static,
+        F: Fn(K) -> Fut + 'static,
+        Fut: Future<Output = Result<V, Er>> + 'static,
+        S: Fn(&mut Self) -> &mut Resource<V, Er> + 'static,
+        V: 'static,
+        Er: 'static,
+    {
+        let signal_state = self.pop_all_signals();
+        let initial_key = key(self);
+
+        let mut hook = ResourceHook {
+            key,
+            last_key: initial_key.clone(),
+            fetch: Rc::new(fetch),
+            target: Rc::new(target),
+            cancel: None,
+        };
+        hook.start(self, initial_key);
+
+        let me = self.insert_hook(Box::new(hook));
+        self.reg_dep(me);
+        self.set_all_signals(signal_state);
+    }
+
+    /// Mirror the value returned by `source` into the field returned by `target`, once it hasnt
+    /// changed for `delay`.
+    ///
+    /// Every change of the source restarts the delay, so dependents of the target only re-run
+    /// once the value settles. This is useful for expensive work depending on user input, such as
+    /// filtering a list after the user stops typing.
+    /// The target is set to the current value of the source immediately.
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use natrix::prelude::*;
+    /// #[derive(Component)]
+    /// struct Search {
+    ///     query: String,
+    ///     debounced_query: String,
+    /// }
+    ///
+    /// impl Component for Search {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         e::div()
+    ///             .child(e::input().on::<events::Input>(
+    ///                 |ctx: E<Self>, event: web_sys::InputEvent| {
+    ///                     if let Some(value) = event.data() {
+    ///                         ctx.query.push_str(&value);
+    ///                     }
+    ///                 },
+    ///             ))
+    ///             .child(|ctx: R<Self>| format!("Results for {}", *ctx.debounced_query))
+    ///     }
+    ///
+    ///     fn on_mount(ctx: E<Self>) {
+    ///         ctx.use_debounced(
+    ///             |ctx| ctx.query.clone(),
+    ///             Duration::from_millis(300),
+    ///             |ctx| &mut *ctx.debounced_query,
+    ///         );
+    ///     }
+    /// }
+    /// ```
+    pub fn use_debounced<V, S>(
+        &mut self,
+        source: impl Fn(&Self) -> V + 'static,
+        delay: Duration,
+        target: S,
+    ) where
+        V: PartialEq + Clone + 'static,
+        S: Fn(&mut Self) -> &mut V + 'static,
+    {
+        let target = Rc::new(target);
+
+        let signal_state = self.pop_all_signals();
+        let initial = source(self);
+        let hook = DebounceHook {
+            source,
+            last: initial.clone(),
+            delay,
+            target: Rc::clone(&target),
+            cancel: None,
+        };
+        let me = self.insert_hook(Box::new(hook));
+        self.reg_dep(me);
+        self.set_all_signals(signal_state);
+
+        *target(self) = initial;
+    }
+
+    /// Keep `target` set to the value `source` had before it last changed.
+    ///
+    /// This is useful for logic that needs to know what a value changed from, such as animating a
+    /// number from its old value to the new one. Callbacks reading both fields see the new value of
+    /// the source together with its previous value. Until the source first changes the target is
+    /// set to its initial value.
+    ///
+    /// ```rust
+    /// # use natrix::prelude::*;
+    /// #[derive(Component)]
+    /// struct Score {
+    ///     score: u32,
+    ///     previous_score: u32,
+    /// }
+    ///
+    /// impl Component for Score {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         e::div()
+    ///             .child(|ctx: R<Self>| format!("{} -> {}", *ctx.previous_score, *ctx.score))
+    ///             .child(e::button().text("+10").on::<events::Click>(|ctx: E<Self>, _| {
+    ///                 *ctx.score += 10;
+    ///             }))
+    ///     }
+    ///
+    ///     fn on_mount(ctx: E<Self>) {
+    ///         ctx.use_previous(|ctx| *ctx.score, |ctx| &mut *ctx.previous_score);
+    ///     }
+    /// }
+    /// ```
+    pub fn use_previous<V, S>(&mut self, source: impl Fn(&Self) -> V + 'static, target: S)
+    where
+        V: PartialEq + Clone + 'static,
+        S: Fn(&mut Self) -> &mut V + 'static,
+    {
+        let target = Rc::new(target);
+
+        let signal_state = self.pop_all_signals();
+        let initial = source(self);
+        let hook = PreviousHook {
+            source,
+            last: initial.clone(),
+            target: Rc::clone(&target),
+        };
+        let me = self.insert_hook(Box::new(hook));
+        self.reg_dep(me);
+        self.set_all_signals(signal_state);
+
+        *target(self) = initial;
+    }
+
+    /// Create a event handler that runs `function` at most once per `interval`.
+    ///
+    /// Clones of the returned handler share the same throttle, so it can be registered on many
+    /// elements and events while rapid triggers from all of them are coalesced. The first trigger
+    /// runs immediately, triggers during the interval result in a single call once it has passed.
+    /// Once every element it is registered on has been unmounted, a pending call is dropped.
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use natrix::prelude::*;
+    /// #[derive(Component)]
+    /// struct Activity {
+    ///     last_active: f64,
+    /// }
+    ///
+    /// impl Component for Activity {
+    ///     # type EmitMessage = NoMessages;
+    ///     # type ReceiveMessage = NoMessages;
+    ///     fn render() -> impl Element<Self> {
+    ///         |ctx: R<Self>| {
+    ///             let on_activity = ctx.throttled(Duration::from_millis(500), |ctx: E<Self>| {
+    ///                 let now = ctx.now();
+    ///                 *ctx.last_active = now;
+    ///             });
+    ///             e::div()
+    ///                 .on::<events::PointerMove>(on_activity.clone())
+    ///                 .on::<events::KeyDown>(on_activity.clone())
+    ///                 .on::<events::Scroll>(on_activity)
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn throttled(
+        &self,
+        interval: Duration,
+        function: impl Fn(&mut Self) + 'static,
+    ) -> Throttled<T> {
+        Throttled {
+            inner: Rc::new(ThrottleState {
+                function: Box::new(function),
+                interval: interval.as_secs_f64() * 1000.0,
+                last_call: Cell::new(f64::NEG_INFINITY),
+                scheduled: Cell::new(false),
+                deferred: DeferredCtx { inner: self.weak() },
+            }),
+        }
+    }
+
+    /// Get the current time from a clock that updates every `tick`, in milliseconds since the
+    /// unix epoch (i.e `Date.now()`).
+    ///
+    /// The clock is a reactive value, so callbacks calling this re-run every tick.
+    /// All callers in a component share a single interval, using the shortest tick requested.
+    /// The interval is stopped once the component is unmounted.
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use natrix::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct MyComponent {
+    /// #     posted_at: f64,
+    /// # }
+    /// # impl Component for MyComponent {
+    /// # type EmitMessage = NoMessages;
+    /// # type ReceiveMessage = NoMessages;
+    /// # fn render() -> impl Element<Self> {
+    /// e::span().text(|ctx: R<Self>| {
+    ///     let now = ctx.use_clock(Duration::from_secs(1));
+    ///     let seconds = ((now - *ctx.posted_at) / 1000.0).floor();
+    ///     format!("{seconds} seconds ago")
+    /// })
+    /// # }}
+    /// ```
+    pub fn use_clock(&mut self, tick: Duration) -> f64 {
+        let deferred = self.deferred_borrow();
+        let clock = self.clock.get_or_insert_with(|| Clock::new(deferred, tick));
+        clock.request_tick(tick);
+        *clock.now
+    }
+
+    /// Get the current time from the clock started by [`use_clock`](Self::use_clock).
+    ///
+    /// This is reactive like `use_clock`, but can be used where only a shared reference is
+    /// available, such as in [`watch`](RenderCtx::watch).
+    /// If the clock hasnt been started this returns the current time, but wont re-run on ticks.
+    #[must_use]
+    pub fn now(&self) -> f64 {
+        match &self.clock {
+            Some(clock) => *clock.now,
+            None => web_sys::js_sys::Date::now(),
+        }
+    }
+
+    /// Update the clock to the current time, called by its interval
+    pub(crate) fn tick_clock(&mut self) {
+        if let Some(clock) = &mut self.clock {
+            *clock.now = web_sys::js_sys::Date::now();
+        }
+    }
+}
+
+/// The signal state of the component data, the clock, and the read shared signals.
+type AllSignalState<T> = (
+    <<T as ComponentBase>::Data as ComponentData>::SignalState,
+    Option<SignalState>,
+    Vec<Rc<dyn SharedRead>>,
+);
+
+/// The hook watching the source of [`State::use_debounced`]
+struct DebounceHook<T, Sf, S> {
+    /// Function to calculate the source value
+    source: Sf,
+    /// The latest value of the source
+    last: T,
+    /// How long the source has to be unchanged before it is applied
+    delay: Duration,
+    /// Function to get the field to update
+    target: Rc<S>,
+    /// Dropping this cancels the pending update
+    cancel: Option<futures_channel::oneshot::Sender<()>>,
+}
+
+impl<C, T, Sf, S> ReactiveHook<C> for DebounceHook<T, Sf, S>
+where
+    C: Component,
+    T: PartialEq + Clone + 'static,
+    Sf: Fn(&State<C>) -> T,
+    S: Fn(&mut State<C>) -> &mut T + 'static,
+{
+    fn update(&mut self, ctx: &mut State<C>, you: HookKey) -> UpdateResult {
+        ctx.clear();
+        let value = (self.source)(ctx);
+        ctx.reg_dep(you);
+
+        if value == self.last {
+            return UpdateResult::Nothing;
+        }
+        self.last = value.clone();
+
+        // Replacing the sender cancels the previously scheduled update
+        let (cancel_tx, cancel_rx) = futures_channel::oneshot::channel::<()>();
+        self.cancel = Some(cancel_tx);
+
+        let delay = self.delay;
+        let target = Rc::clone(&self.target);
+        let deferred = ctx.deferred_borrow();
+        wasm_bindgen_futures::spawn_local(async move {
+            let sleep = std::pin::pin!(crate::async_utils::sleep(delay));
+            let futures_util::future::Either::Right(((), _)) =
+                futures_util::future::select(cancel_rx, sleep).await
+            else {
+                return;
+            };
+
+            let Some(mut ctx) = deferred.borrow_mut() else {
+                return;
+            };
+            *target(&mut ctx) = value;
+        });
+
+        UpdateResult::Nothing
+    }
+
+    fn drop_us(self: Box<Self>) -> Vec<HookKey> {
+        Vec::new()
+    }
+}
+
+/// The hook watching the source of [`State::use_previous`]
+struct PreviousHook<T, Sf, S> {
+    /// Function to calculate the source value
+    source: Sf,
+    /// The latest value of the source
+    last: T,
+    /// Function to get the field holding the previous value
+    target: Rc<S>,
+}
+
+impl<C, T, Sf, S> ReactiveHook<C> for PreviousHook<T, Sf, S>
+where
+    C: Component,
+    T: PartialEq + Clone + 'static,
+    Sf: Fn(&State<C>) -> T,
+    S: Fn(&mut State<C>) -> &mut T + 'static,
+{
+    fn update(&mut self, ctx: &mut State<C>, you: HookKey) -> UpdateResult {
+        ctx.clear();
+        let value = (self.source)(ctx);
+        ctx.reg_dep(you);
+
+        if value == self.last {
+            return UpdateResult::Nothing;
+        }
+        let previous = std::mem::replace(&mut self.last, value);
+        *(self.target)(ctx) = previous;
+
+        // Writes made while running hooks are cleared by the next hook, so the readers of the
+        // target are updated right away.
+        ctx.update();
+        UpdateResult::Nothing
+    }
+
+    fn drop_us(self: Box<Self>) -> Vec<HookKey> {
+        Vec::new()
+    }
+}
+
+/// The hook keeping the part of [`State::set_title_part`] up to date
+struct TitleHook<F> {
+    /// Function to calculate the part
+    part: F,
+    /// The registered part, removed when the hook is dropped
+    entry: TitleEntry,
+}
+
+impl<C, F> ReactiveHook<C> for TitleHook<F>
+where
+    C: Component,
+    F: Fn(&State<C>) -> String,
+{
+    fn update(&mut self, ctx: &mut State<C>, you: HookKey) -> UpdateResult {
+        ctx.clear();
+        self.entry.set_part((self.part)(ctx));
+        ctx.reg_dep(you);
+        UpdateResult::Nothing
+    }
+
+    fn drop_us(self: Box<Self>) -> Vec<HookKey> {
+        Vec::new()
+    }
+}
+
+/// A event handler that runs at most once per interval, see [`State::throttled`]
+///
+/// Clones share the same throttle.
+#[must_use = "Register the handler with `.on`"]
+pub struct Throttled<C: Component> {
+    /// The throttle shared between clones
+    inner: Rc<ThrottleState<C>>,
+}
+
+/// The shared state of a [`Throttled`] handler
+struct ThrottleState<C: Component> {
+    /// The function to run
+    function: Box<dyn Fn(&mut State<C>)>,
+    /// The minimum time between calls in milliseconds
+    interval: f64,
+    /// The time of the last call in milliseconds since the unix epoch
+    last_call: Cell<f64>,
+    /// Is a call scheduled for the end of the interval
+    scheduled: Cell<bool>,
+    /// Used to borrow the component for the scheduled call
+    deferred: DeferredCtx<C>,
+}
+
+impl<C: Component> Throttled<C> {
+    /// Run the function now if the interval has passed, otherwise schedule it for when it has
+    fn trigger(&self, ctx: &mut State<C>) {
+        if self.inner.scheduled.get() {
+            return;
+        }
+
+        let now = web_sys::js_sys::Date::now();
+        let elapsed = now - self.inner.last_call.get();
+        if elapsed >= self.inner.interval {
+            self.inner.last_call.set(now);
+            (self.inner.function)(ctx);
+            return;
+        }
+
+        self.inner.scheduled.set(true);
+        let throttle = Rc::downgrade(&self.inner);
+        let remaining = Duration::from_secs_f64((self.inner.interval - elapsed) / 1000.0);
+        wasm_bindgen_futures::spawn_local(async move {
+            crate::async_utils::sleep(remaining).await;
+
+            // Every element the handler was registered on was unmounted in the meantime
+            let Some(throttle) = throttle.upgrade() else {
+                return;
+            };
+            throttle.scheduled.set(false);
+            throttle.last_call.set(web_sys::js_sys::Date::now());
+            if let Some(mut ctx) = throttle.deferred.borrow_mut() {
+                (throttle.function)(&mut ctx);
+            }
+        });
+    }
+}
+
+impl<C: Component> Clone for Throttled<C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Rc::clone(&self.inner),
+        }
+    }
+}
+
+impl<C: Component, E: Event> EventHandler<C, E> for Throttled<C> {
+    fn func(self) -> impl Fn(&mut State<C>, E::JsEvent) + 'static {
+        move |ctx, _| self.trigger(ctx)
+    }
+}
+
+/// A handle to the result of a task started with [`State::spawn`]
+///
+/// Awaiting this resolves to `None` if the task was cancelled or returned `None` itself.
+#[must_use = "Dropping the handle discards the result of the task"]
+pub struct TaskHandle<T> {
+    /// Receiver for the task result
+    result: futures_channel::oneshot::Receiver<T>,
+}
+
+impl<T> Future for TaskHandle<T> {
+    type Output = Option<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.result).poll(cx).map(Result::ok)
+    }
+}
+
+/// The state of a async resource, see [`State::use_resource`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Resource<T, E> {
+    /// The resource is being fetched
+    #[default]
+    Loading,
+    /// The resource was fetched successfully
+    Ready(T),
+    /// Fetching the resource failed
+    Error(E),
+}
+
+/// The hook watching the key of a resource
+struct ResourceHook<K, Kf, F, S> {
+    /// Function to calculate the key
+    key: Kf,
+    /// The key of the current fetch
+    last_key: K,
+    /// Function to create the fetch future
+    fetch: Rc<F>,
+    /// Function to get the resource to update
+    target: Rc<S>,
+    /// Dropping this cancels the current fetch
+    cancel: Option<futures_channel::oneshot::Sender<()>>,
+}
+
+impl<K, Kf, F, S> ResourceHook<K, Kf, F, S> {
+    /// Cancel the current fetch and start a new one for the given key
+    fn start<C, Fut, V, Er>(&mut self, ctx: &mut State<C>, key: K)
+    where
+        C: Component,
+        K: 'static,
+        F: Fn(K) -> Fut + 'static,
+        Fut: Future<Output = Result<V, Er>> + 'static,
+        S: Fn(&mut State<C>) -> &mut Resource<V, Er> + 'static,
+    {
+        let (cancel_tx, cancel_rx) = futures_channel::oneshot::channel::<()>();
+        self.cancel = Some(cancel_tx);
+
+        let fetch = Rc::clone(&self.fetch);
+        let target = Rc::clone(&self.target);
+        let deferred = ctx.deferred_borrow();
+
+        wasm_bindgen_futures::spawn_local(async move {
+            {
+                let Some(mut ctx) = deferred.borrow_mut() else {
+                    return;
+                };
+                *target(&mut ctx) = Resource::Loading;
+            }
+
+            let future = std::pin::pin!(fetch(key));
+            // The cancel receiver is polled first so a cancelled fetch is discarded even if it has
+            // also completed.
+            let futures_util::future::Either::Right((result, _)) =
+                futures_util::future::select(cancel_rx, future).await
+            else {
+                return;
+            };
+
+            let Some(mut ctx) = deferred.borrow_mut() else {
+                return;
+            };
+            *target(&mut ctx) = match result {
+                Ok(value) => Resource::Ready(value),
+                Err(err) => Resource::Error(err),
+            };
+        });
+    }
+}
+
+impl<C, K, Kf, F, Fut, S, V, Er> ReactiveHook<C> for ResourceHook<K, Kf, F, S>
+where
+    C: Component,
+    K: PartialEq + Clone + 'static,
+    Kf: Fn(&State<C>) -> K,
+    F: Fn(K) -> Fut + 'static,
+    Fut: Future<Output = Result<V, Er>> + 'static,
+    S: Fn(&mut State<C>) -> &mut Resource<V, Er> + 'static,
+{
+    fn update(&mut self, ctx: &mut State<C>, you: HookKey) -> UpdateResult {
+        ctx.clear();
+        let new_key = (self.key)(ctx);
+        ctx.reg_dep(you);
+
+        if new_key != self.last_key {
+            self.last_key = new_key.clone();
+            self.start(ctx, new_key);
+        }
+        UpdateResult::Nothing
+    }
+
+    fn drop_us(self: Box<Self>) -> Vec<HookKey> {
+        Vec::new()
+    }
 }
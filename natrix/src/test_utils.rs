@@ -5,7 +5,7 @@
 use wasm_bindgen::JsCast;
 use web_sys::HtmlElement;
 
-use crate::component::mount_at;
+use crate::component::{RenderResult, mount_at, mount_at_with_context, render_component};
 use crate::get_document;
 use crate::prelude::Component;
 
@@ -23,6 +23,73 @@ pub fn mount_test<C: Component>(component: C) {
     mount_at(component, MOUNT_POINT).expect("Failed to mount");
 }
 
+/// Mount a component at the test location (creating/resetting it if needed), with `context`
+/// available through `State::context`.
+/// # Panics
+/// If the js is in a invalid state or the element is not found
+pub fn mount_test_with_context<C: Component, T: 'static>(component: C, context: T) {
+    setup();
+    mount_at_with_context(component, context, MOUNT_POINT).expect("Failed to mount");
+}
+
+/// Mount a component at the test location (creating/resetting it if needed), returning a handle
+/// that keeps the component alive and provides helpers for interacting with it.
+///
+/// Unlike `mount_test` the component is not leaked, and dropping the handle removes the component
+/// from the dom.
+/// # Panics
+/// If the js is in a invalid state or the element is not found
+pub fn mount_for_test<C: Component>(component: C) -> TestHandle<C> {
+    setup();
+    let result = render_component(component, MOUNT_POINT).expect("Failed to mount");
+    TestHandle { _result: result }
+}
+
+/// A mounted test component.
+///
+/// Dropping this removes the component from the dom.
+#[must_use = "Dropping this unmounts the component"]
+pub struct TestHandle<C: Component> {
+    /// The rendered component, kept alive for as long as the handle.
+    _result: RenderResult<C>,
+}
+
+impl<C: Component> TestHandle<C> {
+    /// Get a html element based on id
+    ///
+    /// # Panics
+    /// If js is in a invalid state or the element isnt found
+    #[must_use]
+    pub fn get_by_id(&self, id: &str) -> HtmlElement {
+        get(id)
+    }
+
+    /// Click the element with the given id
+    ///
+    /// # Panics
+    /// If js is in a invalid state or the element isnt found
+    pub fn click(&self, id: &str) {
+        self.get_by_id(id).click();
+    }
+
+    /// Get the text content of the element with the given id
+    ///
+    /// # Panics
+    /// If js is in a invalid state or the element isnt found
+    #[must_use]
+    pub fn text(&self, id: &str) -> Option<String> {
+        self.get_by_id(id).text_content()
+    }
+}
+
+impl<C: Component> Drop for TestHandle<C> {
+    fn drop(&mut self) {
+        if let Some(element) = get_document().get_element_by_id(MOUNT_PARENT) {
+            element.remove();
+        }
+    }
+}
+
 /// Setup `MOUNT_POINt` as a valid mount location
 ///
 /// # Panics
@@ -60,7 +127,7 @@ pub fn setup() {
 /// # Panics
 /// If js is in a invalid state or the element isnt found
 #[must_use]
-pub fn get(id: &'static str) -> HtmlElement {
+pub fn get(id: &str) -> HtmlElement {
     let document = get_document();
 
     document
@@ -0,0 +1,263 @@
+//! `Element` implementations for time types.
+//!
+//! [`Duration`] renders as a short human readable string, such as `1h 5m 30s`.
+//! With the `chrono` feature the common `chrono` date and time types are supported too.
+//!
+//! Use [`Formatted`] to render a value with a different format:
+//!
+//! ```rust
+//! # use natrix::prelude::*;
+//! # use std::time::Duration;
+//! use natrix::time::Formatted;
+//!
+//! #[derive(Component)]
+//! struct Timer {
+//!     elapsed: Duration,
+//! }
+//!
+//! impl Component for Timer {
+//!     # type EmitMessage = NoMessages;
+//!     # type ReceiveMessage = NoMessages;
+//!     fn render() -> impl Element<Self> {
+//!         e::div()
+//!             // "1m 5s"
+//!             .child(|ctx: R<Self>| *ctx.elapsed)
+//!             // "01:05"
+//!             .child(|ctx: R<Self>| Formatted::new(*ctx.elapsed, "%M:%S"))
+//!     }
+//! }
+//! ```
+#![cfg(feature = "time")]
+
+use std::fmt::{self, Display, Write};
+use std::time::Duration;
+
+use crate::component::Component;
+use crate::element::{Element, text_node};
+use crate::signal::RenderingState;
+use crate::state::State;
+
+/// A time type that can be rendered as a `Element`, either using its default format or
+/// a format string with [`Formatted`].
+pub trait TimeDisplay: 'static {
+    /// Write the value in the default human readable format
+    ///
+    /// # Errors
+    /// If the formatter errors
+    fn fmt_default(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// Write the value using the given format string
+    ///
+    /// # Errors
+    /// If the formatter errors
+    fn fmt_with(&self, format: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+/// Render a time value using a custom format string.
+///
+/// For [`Duration`] the format string supports the following specifiers:
+///
+/// | Specifier | Meaning                         |
+/// |-----------|---------------------------------|
+/// | `%D`      | Whole days                      |
+/// | `%H`      | Hours of the day, `00`-`23`     |
+/// | `%M`      | Minutes of the hour, `00`-`59`  |
+/// | `%S`      | Seconds of the minute, `00`-`59`|
+/// | `%L`      | Milliseconds, `000`-`999`       |
+/// | `%h`      | Total hours                     |
+/// | `%m`      | Total minutes                   |
+/// | `%s`      | Total seconds                   |
+/// | `%%`      | A literal `%`                   |
+///
+/// For the `chrono` types the format string is passed to
+/// [`format`](https://docs.rs/chrono/latest/chrono/format/strftime/index.html).
+///
+/// This also implements `Display`, and as such can be used with
+/// [`text_display`](crate::html_elements::HtmlElement::text_display).
+#[derive(Clone, Copy)]
+pub struct Formatted<T> {
+    /// The value to format
+    value: T,
+    /// The format string
+    format: &'static str,
+}
+
+impl<T: TimeDisplay> Formatted<T> {
+    /// Format `value` using `format`
+    pub fn new(value: T, format: &'static str) -> Self {
+        Self { value, format }
+    }
+}
+
+impl<T: TimeDisplay> Display for Formatted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt_with(self.format, f)
+    }
+}
+
+/// Formats the value using its default format
+struct DefaultFormat<'a, T>(&'a T);
+
+impl<T: TimeDisplay> Display for DefaultFormat<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_default(f)
+    }
+}
+
+/// Create a text node with the formatted value
+fn render_text(value: impl Display) -> web_sys::Node {
+    text_node(&value.to_string())
+}
+
+impl<T: TimeDisplay, C: Component> Element<C> for Formatted<T> {
+    fn render_box(
+        self: Box<Self>,
+        _ctx: &mut State<C>,
+        _render_state: &mut RenderingState,
+    ) -> web_sys::Node {
+        render_text(*self)
+    }
+}
+
+/// Generate a `Element` implementation using the default format of a `TimeDisplay` type
+macro_rules! time_element {
+    ($t:ty) => {
+        impl<C: Component> Element<C> for $t {
+            fn render_box(
+                self: Box<Self>,
+                _ctx: &mut State<C>,
+                _render_state: &mut RenderingState,
+            ) -> web_sys::Node {
+                render_text(DefaultFormat(&*self))
+            }
+        }
+    };
+}
+
+time_element!(Duration);
+
+impl TimeDisplay for Duration {
+    fn fmt_default(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total_seconds = self.as_secs();
+        if total_seconds == 0 {
+            return if self.as_millis() == 0 {
+                write!(f, "{}µs", self.as_micros())
+            } else {
+                write!(f, "{}ms", self.as_millis())
+            };
+        }
+
+        let parts = [
+            (total_seconds / 86_400, "d"),
+            (total_seconds / 3600 % 24, "h"),
+            (total_seconds / 60 % 60, "m"),
+            (total_seconds % 60, "s"),
+        ];
+        let mut first = true;
+        for (amount, unit) in parts {
+            if amount == 0 {
+                continue;
+            }
+            if !first {
+                f.write_char(' ')?;
+            }
+            write!(f, "{amount}{unit}")?;
+            first = false;
+        }
+        Ok(())
+    }
+
+    fn fmt_with(&self, format: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total_seconds = self.as_secs();
+        let mut chars = format.chars();
+        while let Some(character) = chars.next() {
+            if character != '%' {
+                f.write_char(character)?;
+                continue;
+            }
+
+            match chars.next() {
+                Some('D') => write!(f, "{}", total_seconds / 86_400)?,
+                Some('H') => write!(f, "{:02}", total_seconds / 3600 % 24)?,
+                Some('M') => write!(f, "{:02}", total_seconds / 60 % 60)?,
+                Some('S') => write!(f, "{:02}", total_seconds % 60)?,
+                Some('L') => write!(f, "{:03}", self.subsec_millis())?,
+                Some('h') => write!(f, "{}", total_seconds / 3600)?,
+                Some('m') => write!(f, "{}", total_seconds / 60)?,
+                Some('s') => write!(f, "{total_seconds}")?,
+                Some('%') | None => f.write_char('%')?,
+                Some(other) => {
+                    f.write_char('%')?;
+                    f.write_char(other)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "chrono")]
+/// Impls of `TimeDisplay` and `Element` for the `chrono` types
+mod chrono_element {
+    use std::fmt;
+
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+
+    use super::{
+        Component,
+        DefaultFormat,
+        Element,
+        RenderingState,
+        State,
+        TimeDisplay,
+        render_text,
+    };
+
+    /// Generate a `TimeDisplay` and `Element` implementation for a `chrono` type with the given
+    /// default format
+    macro_rules! chrono_element {
+        ($t:ty, $default:literal) => {
+            impl TimeDisplay for $t {
+                fn fmt_default(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "{}", self.format($default))
+                }
+
+                fn fmt_with(&self, format: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "{}", self.format(format))
+                }
+            }
+
+            time_element!($t);
+        };
+    }
+
+    chrono_element!(NaiveDate, "%Y-%m-%d");
+    chrono_element!(NaiveTime, "%H:%M:%S");
+    chrono_element!(NaiveDateTime, "%Y-%m-%d %H:%M:%S");
+
+    impl<Tz: TimeZone + 'static> TimeDisplay for DateTime<Tz>
+    where
+        Tz::Offset: fmt::Display,
+    {
+        fn fmt_default(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.format("%Y-%m-%d %H:%M:%S %Z"))
+        }
+
+        fn fmt_with(&self, format: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.format(format))
+        }
+    }
+
+    impl<Tz: TimeZone + 'static, C: Component> Element<C> for DateTime<Tz>
+    where
+        Tz::Offset: fmt::Display,
+    {
+        fn render_box(
+            self: Box<Self>,
+            _ctx: &mut State<C>,
+            _render_state: &mut RenderingState,
+        ) -> web_sys::Node {
+            render_text(DefaultFormat(&*self))
+        }
+    }
+}
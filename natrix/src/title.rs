@@ -0,0 +1,114 @@
+//! The document title registry used by
+//! [`State::set_title_template`](crate::state::State::set_title_template) and
+//! [`State::set_title_part`](crate::state::State::set_title_part).
+//!
+//! The most recently registered template and part are composed into `document.title`, so nested
+//! components (which mount after their parents) take priority. Unmounting a component removes its
+//! entries and recomputes the title.
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+
+use crate::get_document;
+
+/// The placeholder in a template that is replaced with the title part
+const PLACEHOLDER: &str = "%s";
+
+/// The registered templates and parts
+#[derive(Default)]
+struct Registry {
+    /// The id to use for the next entry
+    next_id: u64,
+    /// The title the document had before any entry was registered
+    original: Option<String>,
+    /// The registered templates in registration order
+    templates: Vec<(u64, Cow<'static, str>)>,
+    /// The registered parts in registration order, `None` if not computed yet
+    parts: Vec<(u64, Option<String>)>,
+}
+
+thread_local! {
+    /// The title registry of the page
+    static REGISTRY: RefCell<Registry> = RefCell::new(Registry::default());
+}
+
+impl Registry {
+    /// Allocate a new entry id, remembering the original title on the first registration
+    fn new_id(&mut self) -> u64 {
+        if self.original.is_none() {
+            self.original = Some(get_document().title());
+        }
+        let id = self.next_id;
+        self.next_id = self.next_id.saturating_add(1);
+        id
+    }
+
+    /// Compute the title from the latest template and part
+    fn compose(&self) -> Option<String> {
+        let part = self.parts.iter().rev().find_map(|(_, part)| part.as_ref());
+        let template = self.templates.last().map(|(_, template)| template);
+
+        match (template, part) {
+            (Some(template), Some(part)) => Some(template.replace(PLACEHOLDER, part)),
+            (None, Some(part)) => Some(part.clone()),
+            (_, None) => self.original.clone(),
+        }
+    }
+
+    /// Write the composed title to the document
+    fn apply(&self) {
+        if let Some(title) = self.compose() {
+            let document = get_document();
+            if document.title() != title {
+                document.set_title(&title);
+            }
+        }
+    }
+}
+
+/// A registered template or part, which is removed from the registry when dropped.
+pub(crate) struct TitleEntry {
+    /// The id of the entry
+    id: u64,
+}
+
+impl TitleEntry {
+    /// Register a template, `%s` in it is replaced with the title part.
+    pub(crate) fn template(template: Cow<'static, str>) -> Self {
+        REGISTRY.with_borrow_mut(|registry| {
+            let id = registry.new_id();
+            registry.templates.push((id, template));
+            registry.apply();
+            Self { id }
+        })
+    }
+
+    /// Register a part, which has no value until [`set_part`](Self::set_part) is called.
+    pub(crate) fn part() -> Self {
+        REGISTRY.with_borrow_mut(|registry| {
+            let id = registry.new_id();
+            registry.parts.push((id, None));
+            Self { id }
+        })
+    }
+
+    /// Set the value of this part
+    pub(crate) fn set_part(&self, value: String) {
+        REGISTRY.with_borrow_mut(|registry| {
+            if let Some((_, part)) = registry.parts.iter_mut().find(|(id, _)| *id == self.id) {
+                *part = Some(value);
+            }
+            registry.apply();
+        });
+    }
+}
+
+impl Drop for TitleEntry {
+    fn drop(&mut self) {
+        REGISTRY.with_borrow_mut(|registry| {
+            registry.templates.retain(|(id, _)| *id != self.id);
+            registry.parts.retain(|(id, _)| *id != self.id);
+            registry.apply();
+        });
+    }
+}
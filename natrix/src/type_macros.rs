@@ -32,4 +32,5 @@ macro_rules! numerics {
     };
 }
 
-pub(crate) use {numerics, strings};
+pub(crate) use numerics;
+pub(crate) use strings;
@@ -0,0 +1,423 @@
+//! Observers and listeners started by the `use_*` methods of [`State`](crate::state::State).
+
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::{JsCast, JsValue, intern};
+
+use crate::get_document;
+use crate::html_elements::NodeRef;
+use crate::shared::SharedSignal;
+use crate::utils::debug_expect;
+
+/// A `ResizeObserver` started by
+/// [`State::use_resize_observer`](crate::state::State::use_resize_observer), disconnected once
+/// dropped.
+pub(crate) struct ResizeWatcher {
+    /// The observer
+    observer: web_sys::ResizeObserver,
+    /// The callback of the observer, kept alive until it is disconnected
+    _callback: Closure<dyn Fn(web_sys::js_sys::Array)>,
+}
+
+impl ResizeWatcher {
+    /// Create a observer writing the content box size of the observed element to `size`
+    pub(crate) fn new(size: SharedSignal<(f64, f64)>) -> Option<Self> {
+        let callback = Closure::<dyn Fn(web_sys::js_sys::Array)>::new(
+            move |entries: web_sys::js_sys::Array| {
+                // Only the latest size is relevant
+                let Ok(entry) = entries.at(-1).dyn_into::<web_sys::ResizeObserverEntry>() else {
+                    return;
+                };
+                let rect = entry.content_rect();
+                size.set((rect.width(), rect.height()));
+            },
+        );
+        let observer = web_sys::ResizeObserver::new(callback.as_ref().unchecked_ref());
+        let observer = debug_expect!(
+            observer.ok().map(Some),
+            or(None),
+            "Failed to create ResizeObserver"
+        )?;
+
+        Some(Self {
+            observer,
+            _callback: callback,
+        })
+    }
+
+    /// Observe the element behind `node_ref`, returning if it has been rendered.
+    pub(crate) fn try_observe(&self, node_ref: &NodeRef) -> bool {
+        let Some(element) = node_ref.get() else {
+            return false;
+        };
+        self.observer.observe(&element);
+        true
+    }
+}
+
+impl Drop for ResizeWatcher {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+/// A change in the visibility of a element observed with
+/// [`State::use_intersection_observer`](crate::state::State::use_intersection_observer).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Intersection {
+    /// Is the element intersecting the viewport (or crossing one of the thresholds into it)
+    pub is_intersecting: bool,
+    /// How much of the element is visible, from `0.0` to `1.0`
+    pub ratio: f64,
+}
+
+/// The options of
+/// [`State::use_intersection_observer`](crate::state::State::use_intersection_observer).
+///
+/// By default the handler is called as soon as a single pixel of the element becomes visible,
+/// or the element leaves the viewport entirely.
+/// ```rust
+/// # use natrix::watchers::IntersectionOptions;
+/// // Start loading slightly before the element scrolls into view
+/// let options = IntersectionOptions::new().root_margin("200px");
+/// // Be notified at every quarter of the element that is visible
+/// let options = IntersectionOptions::new().thresholds([0.0, 0.25, 0.5, 0.75, 1.0]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IntersectionOptions {
+    /// The `rootMargin` grows or shrinks the viewport, in css margin syntax
+    root_margin: Option<Cow<'static, str>>,
+    /// The ratios at which the handler is called
+    thresholds: Vec<f64>,
+}
+
+impl IntersectionOptions {
+    /// The default options
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Grow (or with negative values shrink) the viewport used for the intersection, using css
+    /// margin syntax such as `"100px 0px"`.
+    #[must_use]
+    pub fn root_margin(mut self, margin: impl Into<Cow<'static, str>>) -> Self {
+        self.root_margin = Some(margin.into());
+        self
+    }
+
+    /// Call the handler when the visible ratio of the element crosses `threshold`
+    #[must_use]
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.thresholds.push(threshold);
+        self
+    }
+
+    /// Call the handler when the visible ratio of the element crosses any of the `thresholds`
+    #[must_use]
+    pub fn thresholds(mut self, thresholds: impl IntoIterator<Item = f64>) -> Self {
+        self.thresholds.extend(thresholds);
+        self
+    }
+
+    /// Convert to the options of the js observer
+    fn to_init(&self) -> web_sys::IntersectionObserverInit {
+        let init = web_sys::IntersectionObserverInit::new();
+        if let Some(margin) = &self.root_margin {
+            init.set_root_margin(margin);
+        }
+        if !self.thresholds.is_empty() {
+            let thresholds = self
+                .thresholds
+                .iter()
+                .map(|threshold| JsValue::from_f64(*threshold))
+                .collect::<web_sys::js_sys::Array>();
+            init.set_threshold(&thresholds);
+        }
+        init
+    }
+}
+
+/// A `IntersectionObserver` started by
+/// [`State::use_intersection_observer`](crate::state::State::use_intersection_observer),
+/// disconnected once dropped.
+pub(crate) struct IntersectionWatcher {
+    /// The observer
+    observer: web_sys::IntersectionObserver,
+    /// The callback of the observer, kept alive until it is disconnected
+    _callback: Closure<dyn Fn(web_sys::js_sys::Array)>,
+}
+
+impl IntersectionWatcher {
+    /// Create a observer calling `handler` with the latest intersection of every batch of entries
+    pub(crate) fn new(
+        options: &IntersectionOptions,
+        handler: impl Fn(Intersection) + 'static,
+    ) -> Option<Self> {
+        let callback = Closure::<dyn Fn(web_sys::js_sys::Array)>::new(
+            move |entries: web_sys::js_sys::Array| {
+                // Only a single element is observed, so only the latest entry is relevant
+                let Ok(entry) = entries
+                    .at(-1)
+                    .dyn_into::<web_sys::IntersectionObserverEntry>()
+                else {
+                    return;
+                };
+                handler(Intersection {
+                    is_intersecting: entry.is_intersecting(),
+                    ratio: entry.intersection_ratio(),
+                });
+            },
+        );
+        let observer = web_sys::IntersectionObserver::new_with_options(
+            callback.as_ref().unchecked_ref(),
+            &options.to_init(),
+        );
+        let observer = debug_expect!(
+            observer.ok().map(Some),
+            or(None),
+            "Failed to create IntersectionObserver"
+        )?;
+
+        Some(Self {
+            observer,
+            _callback: callback,
+        })
+    }
+
+    /// Observe the element behind `node_ref`, returning if it has been rendered.
+    pub(crate) fn try_observe(&self, node_ref: &NodeRef) -> bool {
+        let Some(element) = node_ref.get() else {
+            return false;
+        };
+        self.observer.observe(&element);
+        true
+    }
+}
+
+impl Drop for IntersectionWatcher {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+/// A `scroll` listener started by
+/// [`State::use_scroll_position`](crate::state::State::use_scroll_position) or
+/// [`State::use_scroll_position_of`](crate::state::State::use_scroll_position_of), removed once
+/// dropped.
+///
+/// Scroll events are throttled to animation frames, the position is read once per frame at most.
+pub(crate) struct ScrollWatcher {
+    /// The window or element the listener is added to, `None` until the element is rendered
+    target: RefCell<Option<web_sys::EventTarget>>,
+    /// Request a animation frame reading the position, if none is pending
+    schedule: Rc<dyn Fn()>,
+    /// The `scroll` listener, calls `schedule`
+    listener: Closure<dyn Fn()>,
+    /// The id of the pending animation frame
+    pending: Rc<Cell<Option<i32>>>,
+}
+
+impl ScrollWatcher {
+    /// Create a listener writing the position returned by `read` to `position` once per frame
+    pub(crate) fn new(
+        position: SharedSignal<(f64, f64)>,
+        read: impl Fn() -> Option<(f64, f64)> + 'static,
+    ) -> Self {
+        let pending = Rc::new(Cell::new(None));
+
+        let frame_pending = Rc::clone(&pending);
+        let frame = Closure::<dyn Fn()>::new(move || {
+            frame_pending.set(None);
+            if let Some(new_position) = read() {
+                position.set(new_position);
+            }
+        });
+
+        let schedule_pending = Rc::clone(&pending);
+        let schedule: Rc<dyn Fn()> = Rc::new(move || {
+            if schedule_pending.get().is_some() {
+                return;
+            }
+            let id = crate::get_window().request_animation_frame(frame.as_ref().unchecked_ref());
+            let id = debug_expect!(
+                id.ok().map(Some),
+                or(None),
+                "Failed to request animation frame"
+            );
+            schedule_pending.set(id);
+        });
+
+        let listener_schedule = Rc::clone(&schedule);
+        let listener = Closure::<dyn Fn()>::new(move || listener_schedule());
+
+        Self {
+            target: RefCell::new(None),
+            schedule,
+            listener,
+            pending,
+        }
+    }
+
+    /// Listen for scrolling of `target`, and read the current position on the next frame
+    pub(crate) fn attach(&self, target: web_sys::EventTarget) {
+        debug_expect!(
+            target.add_event_listener_with_callback(
+                intern("scroll"),
+                self.listener.as_ref().unchecked_ref()
+            ),
+            "Failed to add scroll listener"
+        );
+        *self.target.borrow_mut() = Some(target);
+        (self.schedule)();
+    }
+
+    /// Listen for scrolling of the element behind `node_ref`, returning if it has been rendered.
+    pub(crate) fn try_attach(&self, node_ref: &NodeRef) -> bool {
+        let Some(element) = node_ref.get() else {
+            return false;
+        };
+        self.attach(element.into());
+        true
+    }
+}
+
+impl Drop for ScrollWatcher {
+    fn drop(&mut self) {
+        if let Some(target) = self.target.get_mut().take() {
+            debug_expect!(
+                target.remove_event_listener_with_callback(
+                    intern("scroll"),
+                    self.listener.as_ref().unchecked_ref()
+                ),
+                "Failed to remove scroll listener"
+            );
+        }
+        if let Some(id) = self.pending.take() {
+            debug_expect!(
+                crate::get_window().cancel_animation_frame(id),
+                "Failed to cancel animation frame"
+            );
+        }
+    }
+}
+
+/// Selector for the elements that can receive focus with Tab
+const FOCUSABLE: &str = "a[href], area[href], button:not([disabled]), \
+    input:not([disabled]):not([type=hidden]), select:not([disabled]), textarea:not([disabled]), \
+    iframe, [tabindex]:not([tabindex='-1']), [contenteditable]:not([contenteditable='false'])";
+
+/// The elements inside `container` that can receive focus with Tab, in document order
+fn focusable_elements(container: &web_sys::Element) -> Vec<web_sys::HtmlElement> {
+    let Ok(elements) = container.query_selector_all(FOCUSABLE) else {
+        debug_assert!(false, "Invalid focusable selector");
+        return Vec::new();
+    };
+    (0..elements.length())
+        .filter_map(|index| elements.item(index))
+        .filter_map(|node| node.dyn_into::<web_sys::HtmlElement>().ok())
+        .collect()
+}
+
+/// Keep Tab and Shift+Tab cycling through the focusable elements of the container
+fn trap_tab(container: &NodeRef, event: &web_sys::KeyboardEvent) {
+    if event.key() != "Tab" {
+        return;
+    }
+    let Some(container) = container.get() else {
+        return;
+    };
+
+    let elements = focusable_elements(&container);
+    let (Some(first), Some(last)) = (elements.first(), elements.last()) else {
+        // Nothing to focus inside, so keep focus from leaving
+        event.prevent_default();
+        return;
+    };
+
+    let active = get_document().active_element();
+    let inside = active
+        .as_ref()
+        .is_some_and(|active| container.contains(Some(active)));
+    let is_active = |element: &web_sys::HtmlElement| {
+        active
+            .as_ref()
+            .is_some_and(|active| element.is_same_node(Some(active)))
+    };
+
+    let target = if event.shift_key() {
+        (!inside || is_active(first)).then_some(last)
+    } else {
+        (!inside || is_active(last)).then_some(first)
+    };
+    if let Some(target) = target {
+        event.prevent_default();
+        debug_expect!(target.focus(), "Failed to focus element");
+    }
+}
+
+/// A focus trap started by [`State::use_focus_trap`](crate::state::State::use_focus_trap), which
+/// restores the previous focus once dropped.
+pub(crate) struct FocusTrap {
+    /// The element that was focused before the trap was started
+    previous: Option<web_sys::HtmlElement>,
+    /// The `keydown` listener on the window
+    listener: Closure<dyn Fn(web_sys::KeyboardEvent)>,
+}
+
+impl FocusTrap {
+    /// Trap Tab focus inside the element behind `container`, and move focus into it.
+    pub(crate) fn new(container: NodeRef) -> Self {
+        let previous = get_document()
+            .active_element()
+            .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok());
+
+        let trap_container = container.clone();
+        let listener: Closure<dyn Fn(web_sys::KeyboardEvent)> =
+            Closure::new(move |event: web_sys::KeyboardEvent| trap_tab(&trap_container, &event));
+        debug_expect!(
+            crate::get_window().add_event_listener_with_callback_and_bool(
+                intern("keydown"),
+                listener.as_ref().unchecked_ref(),
+                true,
+            ),
+            "Failed to add focus trap listener"
+        );
+
+        // The container is usually not rendered yet when this is called in `on_mount`
+        wasm_bindgen_futures::spawn_local(async move {
+            let Some(container) = container.get() else {
+                return;
+            };
+            let has_focus = get_document()
+                .active_element()
+                .is_some_and(|active| container.contains(Some(&active)));
+            if !has_focus && let Some(first) = focusable_elements(&container).first() {
+                debug_expect!(first.focus(), "Failed to focus element");
+            }
+        });
+
+        Self { previous, listener }
+    }
+}
+
+impl Drop for FocusTrap {
+    fn drop(&mut self) {
+        debug_expect!(
+            crate::get_window().remove_event_listener_with_callback_and_bool(
+                intern("keydown"),
+                self.listener.as_ref().unchecked_ref(),
+                true,
+            ),
+            "Failed to remove focus trap listener"
+        );
+        if let Some(previous) = &self.previous
+            && previous.is_connected()
+        {
+            debug_expect!(previous.focus(), "Failed to restore focus");
+        }
+    }
+}
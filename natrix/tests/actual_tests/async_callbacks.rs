@@ -1,7 +1,12 @@
 use std::time::Duration;
 
 use natrix::async_utils;
+use natrix::component::InitResult;
+use natrix::html_elements::NodeRef;
 use natrix::prelude::*;
+use natrix::shared::SharedSignal;
+use natrix::state::{DeferredCtx, Resource};
+use natrix::watchers::IntersectionOptions;
 use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
 
 wasm_bindgen_test_configure!(run_in_browser);
@@ -54,3 +59,892 @@ async fn async_multiple() {
     async_utils::sleep(Duration::from_millis(30)).await;
     assert_eq!(button.text_content(), Some("30".to_owned()));
 }
+
+#[derive(Component)]
+struct AsyncHandler {
+    data: u8,
+}
+
+impl Component for AsyncHandler {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(BUTTON_ID)
+            .text(|ctx: R<Self>| *ctx.data)
+            .on_async::<events::Click, _>(|ctx: DeferredCtx<Self>, _| async move {
+                *ctx.borrow_mut()?.data += 1;
+                async_utils::sleep(Duration::from_millis(10)).await;
+                *ctx.borrow_mut()?.data += 10;
+                Some(())
+            })
+    }
+}
+
+#[wasm_bindgen_test]
+async fn on_async_awaits_in_handler() {
+    crate::mount_test(AsyncHandler { data: 0 });
+
+    let button = crate::get(BUTTON_ID);
+
+    button.click();
+    async_utils::sleep(Duration::from_millis(1)).await;
+    assert_eq!(button.text_content(), Some("1".to_owned()));
+
+    async_utils::sleep(Duration::from_millis(20)).await;
+    assert_eq!(button.text_content(), Some("11".to_owned()));
+}
+
+const TEXT_ID: &str = "__TEXT";
+
+async fn double(value: u8) -> Result<u8, &'static str> {
+    async_utils::sleep(Duration::from_millis(10)).await;
+    if value == 0 {
+        Err("zero")
+    } else {
+        Ok(value * 2)
+    }
+}
+
+#[derive(Component)]
+struct ResourceComponent {
+    id: u8,
+    result: Resource<u8, &'static str>,
+}
+
+impl Component for ResourceComponent {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| *ctx.id += 1),
+            )
+            .child(
+                e::div()
+                    .id(TEXT_ID)
+                    .child(|ctx: R<Self>| match *ctx.result {
+                        Resource::Loading => "loading".to_owned(),
+                        Resource::Ready(value) => value.to_string(),
+                        Resource::Error(err) => err.to_owned(),
+                    }),
+            )
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        ctx.use_resource(|ctx| *ctx.id, double, |ctx| &mut *ctx.result);
+    }
+}
+
+#[wasm_bindgen_test]
+async fn resource_refetches_on_key_change() {
+    crate::mount_test(ResourceComponent {
+        id: 0,
+        result: Resource::Loading,
+    });
+
+    let button = crate::get(BUTTON_ID);
+    let text = crate::get(TEXT_ID);
+
+    assert_eq!(text.text_content(), Some("loading".to_owned()));
+    async_utils::sleep(Duration::from_millis(20)).await;
+    assert_eq!(text.text_content(), Some("zero".to_owned()));
+
+    button.click();
+    async_utils::next_animation_frame().await;
+    assert_eq!(text.text_content(), Some("loading".to_owned()));
+    async_utils::sleep(Duration::from_millis(20)).await;
+    assert_eq!(text.text_content(), Some("2".to_owned()));
+}
+
+#[wasm_bindgen_test]
+async fn resource_discards_stale() {
+    crate::mount_test(ResourceComponent {
+        id: 1,
+        result: Resource::Loading,
+    });
+
+    let button = crate::get(BUTTON_ID);
+    let text = crate::get(TEXT_ID);
+
+    button.click();
+    button.click();
+    async_utils::sleep(Duration::from_millis(20)).await;
+    assert_eq!(text.text_content(), Some("6".to_owned()));
+}
+
+#[derive(Component)]
+struct SpawnComponent {
+    data: u8,
+}
+
+impl Component for SpawnComponent {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(BUTTON_ID)
+            .text(|ctx: R<Self>| *ctx.data)
+            .on::<events::Click>(|ctx: E<Self>, _| {
+                let task = ctx.spawn(async |_ctx| {
+                    async_utils::sleep(Duration::from_millis(10)).await;
+                    Some(5)
+                });
+                ctx.use_async(async move |ctx| {
+                    let value = task.await?;
+                    *ctx.borrow_mut()?.data += value;
+                    Some(())
+                });
+            })
+    }
+}
+
+#[wasm_bindgen_test]
+async fn spawn_returns_value() {
+    crate::mount_test(SpawnComponent { data: 0 });
+
+    let button = crate::get(BUTTON_ID);
+
+    button.click();
+    button.click();
+    async_utils::sleep(Duration::from_millis(20)).await;
+    assert_eq!(button.text_content(), Some("10".to_owned()));
+}
+
+thread_local! {
+    static TASK: std::cell::RefCell<Option<natrix::state::TaskHandle<u8>>> = const { std::cell::RefCell::new(None) };
+}
+
+#[derive(Component)]
+struct CancelComponent;
+
+impl Component for CancelComponent {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        let task = ctx.spawn(async |_ctx| {
+            async_utils::sleep(Duration::from_millis(10)).await;
+            Some(1)
+        });
+        TASK.with(|cell| *cell.borrow_mut() = Some(task));
+    }
+}
+
+#[wasm_bindgen_test]
+async fn spawn_cancelled_on_unmount() {
+    let handle = crate::mount_for_test(CancelComponent);
+    drop(handle);
+
+    let task = TASK
+        .with(|cell| cell.borrow_mut().take())
+        .expect("Task not spawned");
+    assert_eq!(task.await, None);
+}
+
+#[derive(Component)]
+struct Bridged {
+    data: u8,
+}
+
+thread_local! {
+    static DEFERRED: std::cell::RefCell<Option<natrix::state::DeferredCtx<Bridged>>> = const { std::cell::RefCell::new(None) };
+}
+
+impl Component for Bridged {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div().id(TEXT_ID).text(|ctx: R<Self>| *ctx.data)
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        let deferred = ctx.deferred_borrow();
+        DEFERRED.with(|cell| *cell.borrow_mut() = Some(deferred));
+    }
+}
+
+#[wasm_bindgen_test]
+fn with_mut_bridges_callbacks() {
+    let handle = crate::mount_for_test(Bridged { data: 0 });
+    let deferred = DEFERRED
+        .with(|cell| cell.borrow_mut().take())
+        .expect("Deferred not stored");
+
+    assert_eq!(deferred.with_mut(|ctx| *ctx.data += 1), Some(()));
+    assert_eq!(handle.text(TEXT_ID), Some("1".to_owned()));
+
+    drop(handle);
+    assert_eq!(deferred.with_mut(|ctx| *ctx.data += 1), None);
+}
+
+#[derive(Component)]
+struct Clock;
+
+impl Component for Clock {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .id(TEXT_ID)
+            .text(|ctx: R<Self>| ctx.use_clock(Duration::from_millis(10)))
+    }
+}
+
+#[wasm_bindgen_test]
+async fn clock_ticks() {
+    let handle = crate::mount_for_test(Clock);
+
+    let first = handle.text(TEXT_ID);
+    async_utils::sleep(Duration::from_millis(50)).await;
+    let second = handle.text(TEXT_ID);
+
+    assert_ne!(first, second);
+}
+
+#[derive(Component)]
+struct Initialized {
+    fail: bool,
+    data: u8,
+}
+
+impl Component for Initialized {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div().id(TEXT_ID).text(|ctx: R<Self>| *ctx.data)
+    }
+
+    fn render_loading() -> impl Element<Self> {
+        e::div().id(TEXT_ID).text("loading")
+    }
+
+    fn on_init(ctx: DeferredCtx<Self>) -> Option<impl Future<Output = InitResult<Self>> + 'static> {
+        Some(async move {
+            async_utils::sleep(Duration::from_millis(10)).await;
+            let Some(mut ctx) = ctx.borrow_mut() else {
+                return Ok(());
+            };
+            if *ctx.fail {
+                return Err(e::div().id(TEXT_ID).text("failed").into_box());
+            }
+            *ctx.data = 10;
+            Ok(())
+        })
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        *ctx.data += 1;
+    }
+}
+
+#[wasm_bindgen_test]
+async fn init_renders_loading_then_component() {
+    let handle = crate::mount_for_test(Initialized {
+        fail: false,
+        data: 0,
+    });
+
+    assert_eq!(handle.text(TEXT_ID), Some("loading".to_owned()));
+    async_utils::sleep(Duration::from_millis(20)).await;
+    assert_eq!(handle.text(TEXT_ID), Some("11".to_owned()));
+}
+
+#[wasm_bindgen_test]
+async fn init_error_renders_error() {
+    let handle = crate::mount_for_test(Initialized {
+        fail: true,
+        data: 0,
+    });
+
+    async_utils::sleep(Duration::from_millis(20)).await;
+    assert_eq!(handle.text(TEXT_ID), Some("failed".to_owned()));
+}
+
+const PANEL_ID: &str = "__PANEL";
+const WIDTH_ID: &str = "__WIDTH";
+
+#[derive(Component)]
+struct Measured {
+    #[natrix(skip)]
+    panel: NodeRef,
+    #[natrix(skip)]
+    size: Option<SharedSignal<(f64, f64)>>,
+}
+
+impl Component for Measured {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(|ctx: R<Self>| {
+                e::div()
+                    .id(PANEL_ID)
+                    .attr("style", "width: 100px")
+                    .node_ref(&ctx.panel)
+            })
+            .child(e::span().id(WIDTH_ID).text(|ctx: R<Self>| {
+                let width = match &ctx.size {
+                    Some(size) => size.get(ctx).0,
+                    None => 0.0,
+                };
+                format!("{width}")
+            }))
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        let panel = ctx.panel.clone();
+        ctx.size = Some(ctx.use_resize_observer(&panel));
+    }
+}
+
+#[wasm_bindgen_test]
+async fn resize_observer_tracks_width() {
+    crate::mount_test(Measured {
+        panel: NodeRef::new(),
+        size: None,
+    });
+
+    async_utils::sleep(Duration::from_millis(100)).await;
+    let width = crate::get(WIDTH_ID);
+    assert_eq!(width.text_content(), Some("100".to_owned()));
+
+    crate::get(PANEL_ID)
+        .set_attribute("style", "width: 200px")
+        .unwrap();
+    async_utils::sleep(Duration::from_millis(100)).await;
+    assert_eq!(width.text_content(), Some("200".to_owned()));
+}
+
+const STATUS_ID: &str = "__STATUS";
+
+#[derive(Component)]
+struct Socket {
+    #[natrix(skip)]
+    url: &'static str,
+    #[natrix(skip)]
+    socket: Option<async_utils::WebSocket>,
+}
+
+impl Component for Socket {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div().id(STATUS_ID).text(|ctx: R<Self>| {
+            let status = ctx.socket.as_ref().map(|socket| socket.status().get(ctx));
+            format!("{status:?}")
+        })
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        let url = ctx.url;
+        ctx.socket = Some(ctx.use_websocket(url));
+    }
+}
+
+#[wasm_bindgen_test]
+async fn websocket_invalid_url_errors() {
+    crate::mount_test(Socket {
+        url: "not a url",
+        socket: None,
+    });
+
+    async_utils::sleep(Duration::from_millis(10)).await;
+    let status = crate::get(STATUS_ID);
+    assert_eq!(status.text_content(), Some("Some(Error)".to_owned()));
+}
+
+#[wasm_bindgen_test]
+async fn websocket_failed_connection_errors() {
+    crate::mount_test(Socket {
+        url: "ws://localhost:1",
+        socket: None,
+    });
+
+    let status = crate::get(STATUS_ID);
+    assert_eq!(status.text_content(), Some("Some(Connecting)".to_owned()));
+
+    async_utils::sleep(Duration::from_millis(1000)).await;
+    assert_eq!(status.text_content(), Some("Some(Error)".to_owned()));
+}
+
+const DEBOUNCED_ID: &str = "__DEBOUNCED";
+
+#[derive(Component)]
+struct Debounced {
+    value: u8,
+    debounced: u8,
+}
+
+impl Component for Debounced {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| *ctx.value += 1),
+            )
+            .child(
+                e::span()
+                    .id(DEBOUNCED_ID)
+                    .text(|ctx: R<Self>| *ctx.debounced),
+            )
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        ctx.use_debounced(
+            |ctx| *ctx.value,
+            Duration::from_millis(50),
+            |ctx| &mut *ctx.debounced,
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+async fn debounced_waits_for_quiet_period() {
+    crate::mount_test(Debounced {
+        value: 1,
+        debounced: 0,
+    });
+
+    let button = crate::get(BUTTON_ID);
+    let debounced = crate::get(DEBOUNCED_ID);
+    assert_eq!(debounced.text_content(), Some("1".to_owned()));
+
+    button.click();
+    async_utils::sleep(Duration::from_millis(30)).await;
+    button.click();
+    async_utils::sleep(Duration::from_millis(30)).await;
+    assert_eq!(debounced.text_content(), Some("1".to_owned()));
+
+    async_utils::sleep(Duration::from_millis(50)).await;
+    assert_eq!(debounced.text_content(), Some("3".to_owned()));
+}
+
+#[derive(Component)]
+struct ReadOnlyBorrow {
+    data: u8,
+}
+
+impl Component for ReadOnlyBorrow {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(BUTTON_ID)
+            .text(|ctx: R<Self>| *ctx.data)
+            .on::<events::Click>(|ctx: E<Self>, _| {
+                ctx.use_async(async |ctx| {
+                    let value = *ctx.borrow()?.data;
+                    async_utils::sleep(Duration::from_millis(10)).await;
+                    *ctx.borrow_mut()?.data = value * 2;
+                    Some(())
+                });
+            })
+    }
+}
+
+#[wasm_bindgen_test]
+async fn deferred_read_only_borrow() {
+    crate::mount_test(ReadOnlyBorrow { data: 5 });
+
+    let button = crate::get(BUTTON_ID);
+    button.click();
+    assert_eq!(button.text_content(), Some("5".to_owned()));
+
+    async_utils::sleep(Duration::from_millis(20)).await;
+    assert_eq!(button.text_content(), Some("10".to_owned()));
+}
+
+const CLIPBOARD_ID: &str = "__CLIPBOARD";
+
+#[derive(Component)]
+struct Clipboard {
+    result: Option<bool>,
+}
+
+impl Component for Clipboard {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(CLIPBOARD_ID)
+            .text(|ctx: R<Self>| format!("{:?}", *ctx.result))
+            .on::<events::Click>(|ctx: E<Self>, _| {
+                let write = ctx.write_clipboard("hello");
+                ctx.use_async(async |ctx| {
+                    let result = write.await;
+                    *ctx.borrow_mut()?.result = Some(result.is_ok());
+                    Some(())
+                });
+            })
+    }
+}
+
+#[wasm_bindgen_test]
+async fn clipboard_write_resolves() {
+    crate::mount_test(Clipboard { result: None });
+
+    let button = crate::get(CLIPBOARD_ID);
+    button.click();
+    async_utils::sleep(Duration::from_millis(100)).await;
+
+    // Depending on the permissions of the test browser this is either a success or a error,
+    // but it should never panic or stay pending.
+    assert_ne!(button.text_content(), Some("None".to_owned()));
+}
+
+const TAB_ID: &str = "__TAB";
+const PAGE_ID: &str = "__PAGE";
+const RESET_ID: &str = "__RESET";
+
+#[derive(Component)]
+struct QueryParams {
+    #[natrix(skip)]
+    tab: Option<natrix::navigation::QueryParam>,
+    #[natrix(skip)]
+    page: Option<natrix::navigation::QueryParam>,
+}
+
+impl Component for QueryParams {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::div()
+                    .id(TEXT_ID)
+                    .text(|ctx: R<Self>| ctx.tab.as_ref().map(|tab| tab.get(ctx))),
+            )
+            .child(
+                e::button()
+                    .id(TAB_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        if let Some(tab) = &ctx.tab {
+                            tab.set("privacy");
+                        }
+                    }),
+            )
+            .child(
+                e::button()
+                    .id(PAGE_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        if let Some(page) = &ctx.page {
+                            page.set("2");
+                        }
+                    }),
+            )
+            .child(
+                e::button()
+                    .id(RESET_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        if let (Some(tab), Some(page)) = (&ctx.tab, &ctx.page) {
+                            tab.set("general");
+                            page.set("1");
+                        }
+                    }),
+            )
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        ctx.tab = Some(ctx.use_query_param("tab", "general"));
+        ctx.page = Some(ctx.use_query_param("page", "1"));
+    }
+}
+
+fn search() -> String {
+    web_sys::window().unwrap().location().search().unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn query_params_sync_with_url() {
+    crate::mount_test(QueryParams {
+        tab: None,
+        page: None,
+    });
+    async_utils::sleep(Duration::from_millis(10)).await;
+    let text = crate::get(TEXT_ID);
+    assert_eq!(text.text_content(), Some("general".to_owned()));
+
+    crate::get(TAB_ID).click();
+    crate::get(PAGE_ID).click();
+    async_utils::sleep(Duration::from_millis(10)).await;
+
+    assert_eq!(text.text_content(), Some("privacy".to_owned()));
+    assert!(search().contains("tab=privacy"));
+    assert!(search().contains("page=2"));
+
+    crate::get(RESET_ID).click();
+    assert!(!search().contains("tab="));
+    assert!(!search().contains("page="));
+}
+
+const SCROLLER_ID: &str = "__SCROLLER";
+
+#[derive(Component)]
+struct ScrollPosition {
+    #[natrix(skip)]
+    scroll: Option<SharedSignal<(f64, f64)>>,
+    #[natrix(skip)]
+    scroller: NodeRef,
+}
+
+impl Component for ScrollPosition {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(e::div().id(TEXT_ID).text(|ctx: R<Self>| {
+                ctx.scroll
+                    .as_ref()
+                    .map(|scroll| format!("{}", scroll.get(ctx).1))
+            }))
+            .child(|ctx: R<Self>| {
+                e::div()
+                    .id(SCROLLER_ID)
+                    .node_ref(&ctx.scroller)
+                    .attr("style", "height: 50px; overflow: auto")
+                    .child(e::div().attr("style", "height: 500px"))
+            })
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        let scroller = ctx.scroller.clone();
+        ctx.scroll = Some(ctx.use_scroll_position_of(&scroller));
+    }
+}
+
+#[wasm_bindgen_test]
+async fn scroll_position_updates_on_frame() {
+    crate::mount_test(ScrollPosition {
+        scroll: None,
+        scroller: NodeRef::new(),
+    });
+    async_utils::sleep(Duration::from_millis(10)).await;
+    async_utils::next_animation_frame().await;
+    let text = crate::get(TEXT_ID);
+    assert_eq!(text.text_content(), Some("0".to_owned()));
+
+    let scroller = crate::get(SCROLLER_ID);
+    scroller.set_scroll_top(20);
+    scroller.set_scroll_top(40);
+    async_utils::sleep(Duration::from_millis(10)).await;
+    async_utils::next_animation_frame().await;
+    async_utils::next_animation_frame().await;
+
+    assert_eq!(text.text_content(), Some("40".to_owned()));
+}
+
+const HIDDEN_ID: &str = "__HIDDEN";
+
+#[derive(Component)]
+struct Visibility {
+    visible: Option<bool>,
+    hidden: Option<bool>,
+    #[natrix(skip)]
+    shown_ref: NodeRef,
+    #[natrix(skip)]
+    hidden_ref: NodeRef,
+}
+
+impl Component for Visibility {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::div()
+                    .id(TEXT_ID)
+                    .text(|ctx: R<Self>| format!("{:?}", *ctx.visible)),
+            )
+            .child(
+                e::div()
+                    .id(HIDDEN_ID)
+                    .text(|ctx: R<Self>| format!("{:?}", *ctx.hidden)),
+            )
+            .child(|ctx: R<Self>| {
+                e::div().node_ref(&ctx.shown_ref).attr(
+                    "style",
+                    "position: fixed; top: 0; height: 10px; width: 10px",
+                )
+            })
+            .child(|ctx: R<Self>| {
+                e::div().node_ref(&ctx.hidden_ref).attr(
+                    "style",
+                    "position: fixed; top: -1000px; height: 10px; width: 10px",
+                )
+            })
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        let shown = ctx.shown_ref.clone();
+        let hidden = ctx.hidden_ref.clone();
+        ctx.use_intersection_observer(
+            &shown,
+            &IntersectionOptions::new(),
+            |ctx: E<Self>, entry| {
+                *ctx.visible = Some(entry.is_intersecting);
+            },
+        );
+        ctx.use_intersection_observer(
+            &hidden,
+            &IntersectionOptions::new().threshold(0.5),
+            |ctx: E<Self>, entry| {
+                *ctx.hidden = Some(entry.is_intersecting);
+            },
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+async fn intersection_observer_reports_visibility() {
+    crate::mount_test(Visibility {
+        visible: None,
+        hidden: None,
+        shown_ref: NodeRef::new(),
+        hidden_ref: NodeRef::new(),
+    });
+    async_utils::sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(
+        crate::get(TEXT_ID).text_content(),
+        Some("Some(true)".to_owned())
+    );
+    assert_eq!(
+        crate::get(HIDDEN_ID).text_content(),
+        Some("Some(false)".to_owned())
+    );
+}
+
+const LIMITED_ID: &str = "__LIMITED";
+
+#[derive(Component)]
+struct RateLimited {
+    count: u8,
+}
+
+impl Component for RateLimited {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(LIMITED_ID)
+            .text_rate_limited(Duration::from_millis(100), |ctx: R<Self>| *ctx.count)
+            .on::<events::Click>(|ctx: E<Self>, _| *ctx.count += 1)
+    }
+}
+
+#[wasm_bindgen_test]
+async fn text_rate_limited_coalesces_updates() {
+    crate::mount_test(RateLimited { count: 0 });
+
+    let button = crate::get(LIMITED_ID);
+    assert_eq!(button.text_content(), Some("0".to_owned()));
+
+    button.click();
+    button.click();
+    button.click();
+    assert_eq!(button.text_content(), Some("0".to_owned()));
+
+    async_utils::sleep(Duration::from_millis(150)).await;
+    async_utils::next_animation_frame().await;
+    assert_eq!(button.text_content(), Some("3".to_owned()));
+}
+
+const THROTTLE_FIRST_ID: &str = "__THROTTLE_FIRST";
+const THROTTLE_SECOND_ID: &str = "__THROTTLE_SECOND";
+
+#[derive(Component)]
+struct Throttle {
+    count: u8,
+}
+
+impl Component for Throttle {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        |ctx: R<Self>| {
+            let increment = ctx.throttled(Duration::from_millis(100), |ctx: E<Self>| {
+                *ctx.count += 1;
+            });
+            e::div()
+                .child(e::span().id(TEXT_ID).text(|ctx: R<Self>| *ctx.count))
+                .child(
+                    e::button()
+                        .id(THROTTLE_FIRST_ID)
+                        .on::<events::Click>(increment.clone()),
+                )
+                .child(
+                    e::button()
+                        .id(THROTTLE_SECOND_ID)
+                        .on::<events::Click>(increment),
+                )
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+async fn throttled_handler_is_shared_between_elements() {
+    crate::mount_test(Throttle { count: 0 });
+
+    let first = crate::get(THROTTLE_FIRST_ID);
+    let second = crate::get(THROTTLE_SECOND_ID);
+
+    first.click();
+    assert_eq!(crate::get(TEXT_ID).text_content(), Some("1".to_owned()));
+
+    second.click();
+    first.click();
+    second.click();
+    assert_eq!(crate::get(TEXT_ID).text_content(), Some("1".to_owned()));
+
+    async_utils::sleep(Duration::from_millis(150)).await;
+    assert_eq!(crate::get(TEXT_ID).text_content(), Some("2".to_owned()));
+}
+
+const IMAGE_ID: &str = "__IMAGE";
+const BROKEN_IMAGE_ID: &str = "__BROKEN_IMAGE";
+const PIXEL: &str =
+    "data:image/gif;base64,R0lGODlhAQABAIAAAAAAAP///yH5BAEAAAAALAAAAAABAAEAAAIBRAA7";
+
+#[derive(Component)]
+struct Images;
+
+impl Component for Images {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(e::img().id(IMAGE_ID).src(PIXEL).placeholder(PIXEL))
+            .child(
+                e::img()
+                    .id(BROKEN_IMAGE_ID)
+                    .src("data:image/png;base64,broken")
+                    .placeholder(PIXEL)
+                    .fallback(PIXEL),
+            )
+    }
+}
+
+#[wasm_bindgen_test]
+async fn image_placeholder_swaps_on_load() {
+    crate::mount_test(Images);
+
+    let image = crate::get(IMAGE_ID);
+    assert!(image.has_attribute("hidden"));
+
+    async_utils::sleep(Duration::from_millis(100)).await;
+    assert!(!image.has_attribute("hidden"));
+    assert!(image.previous_element_sibling().is_none());
+
+    let broken = crate::get(BROKEN_IMAGE_ID);
+    assert_eq!(broken.get_attribute("src"), Some(PIXEL.to_owned()));
+    assert!(!broken.has_attribute("hidden"));
+}
@@ -1,7 +1,7 @@
 #![allow(dead_code)]
 
 use natrix::component::NonReactive;
-use natrix::html_elements::ToAttribute;
+use natrix::html_elements::{NodeRef, ToAttribute, namespaces};
 use natrix::prelude::*;
 use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
 wasm_bindgen_test_configure!(run_in_browser);
@@ -111,6 +111,41 @@ fn reactive_attribute() {
     assert_eq!(button.get_attribute("abc"), Some("4".to_owned()));
 }
 
+#[derive(Component, Default)]
+struct Threshold {
+    value: u8,
+}
+
+impl Component for Threshold {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(ROOT)
+            .attr("abc", |ctx: R<Self>| (*ctx.value > 1).then_some("big"))
+            .on::<events::Click>(|ctx: E<Self>, _| {
+                *ctx.value += 1;
+            })
+    }
+}
+
+#[wasm_bindgen_test]
+fn reactive_attribute_skips_unchanged() {
+    crate::mount_test(Threshold::default());
+
+    let button = crate::get(ROOT);
+    assert_eq!(button.get_attribute("abc"), None);
+
+    // Overwrite the attribute outside the framework to detect if it is written again.
+    button.set_attribute("abc", "outside").unwrap();
+
+    button.click();
+    assert_eq!(button.get_attribute("abc"), Some("outside".to_owned()));
+
+    button.click();
+    assert_eq!(button.get_attribute("abc"), Some("big".to_owned()));
+}
+
 #[derive(Component, Default)]
 struct Toggle {
     value: bool,
@@ -143,3 +178,416 @@ fn reactive_bool() {
     button.click();
     assert_eq!(button.get_attribute("abc"), None);
 }
+
+const INTERCEPTED: &str = "INTERCEPTED";
+
+#[derive(Component, Default)]
+struct RouteLink {
+    intercepted: Option<bool>,
+}
+
+impl Component for RouteLink {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(e::a().id(ROOT).href_route("/hello"))
+            .child(
+                e::div()
+                    .id(INTERCEPTED)
+                    .text(|ctx: R<Self>| format!("{:?}", *ctx.intercepted)),
+            )
+            .on::<events::Click>(|ctx: E<Self>, event: web_sys::PointerEvent| {
+                *ctx.intercepted = Some(event.default_prevented());
+                // Keep the browser from following the link and unloading the tests
+                event.prevent_default();
+            })
+    }
+}
+
+/// Click the route link with the given modifiers, returning if the link handled the click
+fn click_route(init: &web_sys::PointerEventInit) -> bool {
+    crate::mount_test(RouteLink::default());
+
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    let event = web_sys::PointerEvent::new_with_event_init_dict("click", init)
+        .expect("Failed to create event");
+    crate::get(ROOT)
+        .dispatch_event(&event)
+        .expect("Failed to dispatch event");
+
+    match crate::get(INTERCEPTED).text_content().as_deref() {
+        Some("Some(true)") => true,
+        Some("Some(false)") => false,
+        other => panic!("Click did not reach the parent: {other:?}"),
+    }
+}
+
+fn pathname() -> String {
+    web_sys::window().unwrap().location().pathname().unwrap()
+}
+
+#[wasm_bindgen_test]
+fn href_route_sets_href() {
+    crate::mount_test(RouteLink::default());
+
+    let link = crate::get(ROOT);
+    assert_eq!(link.get_attribute("href"), Some("/hello".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn href_route_intercepts_plain_click() {
+    let original = pathname();
+
+    assert!(click_route(&web_sys::PointerEventInit::new()));
+    assert_eq!(pathname(), "/hello");
+
+    natrix::navigation::navigate(&original);
+}
+
+#[wasm_bindgen_test]
+fn href_route_ignores_modified_click() {
+    let init = web_sys::PointerEventInit::new();
+    init.set_ctrl_key(true);
+    assert!(!click_route(&init));
+
+    let init = web_sys::PointerEventInit::new();
+    init.set_meta_key(true);
+    assert!(!click_route(&init));
+
+    let init = web_sys::PointerEventInit::new();
+    init.set_shift_key(true);
+    assert!(!click_route(&init));
+}
+
+#[wasm_bindgen_test]
+fn href_route_ignores_middle_click() {
+    let init = web_sys::PointerEventInit::new();
+    init.set_button(1);
+    assert!(!click_route(&init));
+}
+
+const DISABLED: &str = "DISABLED";
+const TOGGLE: &str = "TOGGLE";
+
+#[derive(Component, Default)]
+struct Disabled {
+    locked: bool,
+    value: u8,
+}
+
+impl Component for Disabled {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::div()
+                    .id(DISABLED)
+                    .disabled(|ctx: R<Self>| *ctx.locked)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        *ctx.value += 1;
+                    })
+                    .text(|ctx: R<Self>| *ctx.value),
+            )
+            .child(
+                e::button()
+                    .id(TOGGLE)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        *ctx.locked = !*ctx.locked;
+                    }),
+            )
+    }
+}
+
+#[wasm_bindgen_test]
+fn disabled_sets_aria() {
+    crate::mount_test(Disabled::default());
+
+    let element = crate::get(DISABLED);
+    let toggle = crate::get(TOGGLE);
+    assert_eq!(element.get_attribute("disabled"), None);
+    assert_eq!(element.get_attribute("aria-disabled"), None);
+
+    toggle.click();
+    assert_eq!(element.get_attribute("disabled"), Some("".to_owned()));
+    assert_eq!(
+        element.get_attribute("aria-disabled"),
+        Some("true".to_owned())
+    );
+
+    toggle.click();
+    assert_eq!(element.get_attribute("disabled"), None);
+    assert_eq!(element.get_attribute("aria-disabled"), None);
+}
+
+#[wasm_bindgen_test]
+fn disabled_suppresses_click() {
+    crate::mount_test(Disabled::default());
+
+    let element = crate::get(DISABLED);
+    let toggle = crate::get(TOGGLE);
+
+    element.click();
+    assert_eq!(element.text_content(), Some("1".to_owned()));
+
+    toggle.click();
+    element.click();
+    assert_eq!(element.text_content(), Some("1".to_owned()));
+
+    toggle.click();
+    element.click();
+    assert_eq!(element.text_content(), Some("2".to_owned()));
+}
+
+const EDITABLE: &str = "EDITABLE";
+
+#[derive(Component)]
+struct Editable {
+    editing: bool,
+    #[natrix(skip)]
+    input: NodeRef,
+}
+
+impl Component for Editable {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(|ctx: R<Self>| {
+                e::div()
+                    .id(EDITABLE)
+                    .node_ref(&ctx.input)
+                    .tabindex(-1)
+                    .contenteditable(|ctx: R<Self>| *ctx.editing)
+            })
+            .child(
+                e::button()
+                    .id(TOGGLE)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        ctx.editing.toggle();
+                    }),
+            )
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        ctx.focus(&ctx.input);
+    }
+}
+
+#[wasm_bindgen_test]
+fn contenteditable_writes_bool() {
+    crate::mount_test(Editable {
+        editing: false,
+        input: NodeRef::new(),
+    });
+
+    let element = crate::get(EDITABLE);
+    assert_eq!(element.get_attribute("tabindex"), Some("-1".to_owned()));
+    assert_eq!(
+        element.get_attribute("contenteditable"),
+        Some("false".to_owned())
+    );
+
+    crate::get(TOGGLE).click();
+    assert_eq!(
+        element.get_attribute("contenteditable"),
+        Some("true".to_owned())
+    );
+}
+
+#[wasm_bindgen_test]
+async fn focus_deferred_until_mounted() {
+    crate::mount_test(Editable {
+        editing: false,
+        input: NodeRef::new(),
+    });
+
+    natrix::async_utils::sleep(std::time::Duration::from_millis(1)).await;
+
+    let element = crate::get(EDITABLE);
+    let active = element
+        .owner_document()
+        .and_then(|document| document.active_element());
+    assert_eq!(active.map(|active| active.id()), Some(element.id()));
+}
+
+const VALUE_INPUT: &str = "VALUE_INPUT";
+const VALUE_TEXT: &str = "VALUE_TEXT";
+const READ_VALUE: &str = "READ_VALUE";
+
+#[derive(Component)]
+struct Uncontrolled {
+    value: Option<String>,
+    not_input: Option<String>,
+    #[natrix(skip)]
+    input: NodeRef,
+    #[natrix(skip)]
+    wrapper: NodeRef,
+}
+
+impl Component for Uncontrolled {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(|ctx: R<Self>| {
+                e::div()
+                    .node_ref(&ctx.wrapper)
+                    .child(e::input().id(VALUE_INPUT).node_ref(&ctx.input))
+            })
+            .child(
+                e::span()
+                    .id(VALUE_TEXT)
+                    .text(|ctx: R<Self>| format!("{:?} {:?}", *ctx.value, *ctx.not_input)),
+            )
+            .child(
+                e::button()
+                    .id(READ_VALUE)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        *ctx.value = ctx.input_value(&ctx.input);
+                        *ctx.not_input = ctx.input_value(&ctx.wrapper);
+                    }),
+            )
+    }
+}
+
+#[wasm_bindgen_test]
+fn input_value_reads_uncontrolled_input() {
+    crate::mount_test(Uncontrolled {
+        value: None,
+        not_input: None,
+        input: NodeRef::new(),
+        wrapper: NodeRef::new(),
+    });
+
+    let input = crate::get(VALUE_INPUT);
+    web_sys::js_sys::Reflect::set(&input, &"value".into(), &"typed".into()).unwrap();
+
+    crate::get(READ_VALUE).click();
+    assert_eq!(
+        crate::get(VALUE_TEXT).text_content(),
+        Some("Some(\"typed\") None".to_owned())
+    );
+}
+
+const INPUT: &str = "INPUT";
+
+#[derive(Component, Default)]
+struct Property {
+    text: &'static str,
+    indeterminate: bool,
+}
+
+impl Component for Property {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::input()
+                    .id(INPUT)
+                    .attr("type", "checkbox")
+                    .prop("value", |ctx: R<Self>| *ctx.text)
+                    .prop("indeterminate", |ctx: R<Self>| *ctx.indeterminate),
+            )
+            .child(
+                e::button()
+                    .id(TOGGLE)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        *ctx.text = "changed";
+                        ctx.indeterminate.toggle();
+                    }),
+            )
+    }
+}
+
+fn get_property(element: &web_sys::HtmlElement, name: &str) -> wasm_bindgen::JsValue {
+    web_sys::js_sys::Reflect::get(element, &name.into()).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn reactive_property() {
+    crate::mount_test(Property {
+        text: "initial",
+        indeterminate: false,
+    });
+
+    let input = crate::get(INPUT);
+    assert_eq!(
+        get_property(&input, "value").as_string(),
+        Some("initial".to_owned())
+    );
+    assert_eq!(get_property(&input, "indeterminate").as_bool(), Some(false));
+    // Properties are not reflected as attributes
+    assert_eq!(input.get_attribute("indeterminate"), None);
+
+    crate::get(TOGGLE).click();
+    assert_eq!(
+        get_property(&input, "value").as_string(),
+        Some("changed".to_owned())
+    );
+    assert_eq!(get_property(&input, "indeterminate").as_bool(), Some(true));
+}
+
+const NAMESPACED: &str = "NAMESPACED";
+const TOGGLE_LINK: &str = "TOGGLE_LINK";
+
+#[derive(Component)]
+struct Namespaced {
+    link: Option<&'static str>,
+}
+
+impl Component for Namespaced {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::svg().child(
+                    e::svg::use_()
+                        .id(NAMESPACED)
+                        .attr_ns(namespaces::XLINK, "xlink:href", |ctx: R<Self>| *ctx.link)
+                        .attr_ns(namespaces::XML, "xml:lang", "en"),
+                ),
+            )
+            .child(
+                e::button()
+                    .id(TOGGLE_LINK)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        *ctx.link = match *ctx.link {
+                            Some(_) => None,
+                            None => Some("#other"),
+                        };
+                    }),
+            )
+    }
+}
+
+#[wasm_bindgen_test]
+fn namespaced_attributes() {
+    crate::mount_test(Namespaced {
+        link: Some("#icon"),
+    });
+
+    let document = web_sys::window().unwrap().document().unwrap();
+    let element = document.get_element_by_id(NAMESPACED).unwrap();
+    assert_eq!(
+        element.get_attribute_ns(Some(namespaces::XLINK), "href"),
+        Some("#icon".to_owned())
+    );
+    assert_eq!(
+        element.get_attribute_ns(Some(namespaces::XML), "lang"),
+        Some("en".to_owned())
+    );
+
+    crate::get(TOGGLE_LINK).click();
+    assert!(!element.has_attribute_ns(Some(namespaces::XLINK), "href"));
+
+    crate::get(TOGGLE_LINK).click();
+    assert_eq!(
+        element.get_attribute_ns(Some(namespaces::XLINK), "href"),
+        Some("#other".to_owned())
+    );
+}
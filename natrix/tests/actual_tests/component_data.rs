@@ -0,0 +1,203 @@
+use natrix::component::ComponentBase;
+use natrix::prelude::*;
+use natrix::state::ComponentData;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Component)]
+struct Named {
+    first: u8,
+    second: String,
+    third: bool,
+}
+
+impl Component for Named {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+    }
+}
+
+#[derive(Component)]
+struct Tuple(u8, u8);
+
+impl Component for Tuple {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+    }
+}
+
+#[derive(Component)]
+struct Unit;
+
+impl Component for Unit {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+    }
+}
+
+#[wasm_bindgen_test]
+fn named_field_names() {
+    assert_eq!(
+        <Named as ComponentBase>::Data::field_names(),
+        &["first", "second", "third"]
+    );
+}
+
+#[wasm_bindgen_test]
+fn tuple_field_names() {
+    assert_eq!(<Tuple as ComponentBase>::Data::field_names(), &["0", "1"]);
+}
+
+#[wasm_bindgen_test]
+fn unit_field_names() {
+    assert!(<Unit as ComponentBase>::Data::field_names().is_empty());
+}
+
+const BUTTON: &str = "__BUTTON";
+const SKIP_BUTTON: &str = "__SKIP_BUTTON";
+const TEXT: &str = "__TEXT";
+
+#[derive(Component)]
+struct Mixed {
+    value: u8,
+    #[natrix(skip)]
+    skipped: u8,
+}
+
+impl Component for Mixed {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON)
+                    .on::<events::Click>(|ctx: E<Self>, _| *ctx.value += 1),
+            )
+            .child(
+                e::button()
+                    .id(SKIP_BUTTON)
+                    .on::<events::Click>(|ctx: E<Self>, _| ctx.skipped += 1),
+            )
+            .child(|ctx: R<Self>| {
+                e::div()
+                    .id(TEXT)
+                    .text(format!("{} {}", *ctx.value, ctx.skipped))
+            })
+    }
+}
+
+#[derive(Component)]
+struct MixedTuple(#[natrix(skip)] u8, u8);
+
+impl Component for MixedTuple {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+    }
+}
+
+#[wasm_bindgen_test]
+fn skipped_field_names() {
+    assert_eq!(<Mixed as ComponentBase>::Data::field_names(), &["value"]);
+    assert_eq!(<MixedTuple as ComponentBase>::Data::field_names(), &["1"]);
+}
+
+#[wasm_bindgen_test]
+fn skipped_fields_are_not_reactive() {
+    crate::mount_test(Mixed {
+        value: 0,
+        skipped: 0,
+    });
+
+    let text = crate::get(TEXT);
+    assert_eq!(text.text_content(), Some("0 0".to_owned()));
+
+    crate::get(SKIP_BUTTON).click();
+    assert!(text.is_same_node(Some(&crate::get(TEXT))));
+    assert_eq!(text.text_content(), Some("0 0".to_owned()));
+
+    crate::get(BUTTON).click();
+    assert_eq!(crate::get(TEXT).text_content(), Some("1 1".to_owned()));
+}
+
+#[derive(Component, Default)]
+#[natrix(builder)]
+struct Built {
+    value: u8,
+    label: &'static str,
+    #[natrix(skip)]
+    step: u8,
+}
+
+impl Component for Built {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .id(TEXT)
+            .text(|ctx: R<Self>| format!("{} {} {}", *ctx.label, *ctx.value, ctx.step))
+    }
+}
+
+#[wasm_bindgen_test]
+fn builder_starts_from_default() {
+    let built = Built::builder().label("count").step(2).build();
+    assert_eq!(built.value, 0);
+    assert_eq!(built.label, "count");
+    assert_eq!(built.step, 2);
+
+    crate::mount_test(built);
+    assert_eq!(
+        crate::get(TEXT).text_content(),
+        Some("count 0 2".to_owned())
+    );
+}
+
+#[derive(Component)]
+struct EqSetter {
+    #[natrix(eq)]
+    value: u8,
+}
+
+impl Component for EqSetter {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON)
+                    .on::<events::Click>(|ctx: E<Self>, _| ctx.set_value(5)),
+            )
+            .child(|ctx: R<Self>| e::div().id(TEXT).text(*ctx.value))
+    }
+}
+
+#[wasm_bindgen_test]
+fn eq_setter_skips_equal_values() {
+    crate::mount_test(EqSetter { value: 5 });
+
+    let text = crate::get(TEXT);
+    crate::get(BUTTON).click();
+    assert!(text.is_same_node(Some(&crate::get(TEXT))));
+    assert_eq!(text.text_content(), Some("5".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn eq_setter_updates_different_values() {
+    crate::mount_test(EqSetter { value: 0 });
+
+    let text = crate::get(TEXT);
+    crate::get(BUTTON).click();
+    assert!(!text.is_same_node(Some(&crate::get(TEXT))));
+    assert_eq!(crate::get(TEXT).text_content(), Some("5".to_owned()));
+}
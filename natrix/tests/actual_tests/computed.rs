@@ -49,3 +49,64 @@ fn works() {
     button.click();
     assert_eq!(text.text_content(), Some("5".to_owned()));
 }
+
+const TOGGLE_ID: &str = "__TOGGLE";
+
+#[derive(Component)]
+struct Fused {
+    flag: Option<u8>,
+    value: u8,
+}
+
+impl Component for Fused {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| *ctx.value += 1),
+            )
+            .child(
+                e::button()
+                    .id(TOGGLE_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        *ctx.flag = ctx.flag.map_or(Some(0), |_| None);
+                    }),
+            )
+            .child(|ctx: R<Self>| {
+                let (has_flag, big) = ctx.watch(|ctx| (ctx.flag.is_some(), *ctx.value > 2));
+                e::div().text(format!("{has_flag} {big}")).id(TEXT)
+            })
+    }
+}
+
+#[wasm_bindgen_test]
+fn tuple_watch_fuses() {
+    crate::mount_test(Fused {
+        flag: None,
+        value: 0,
+    });
+
+    let button = crate::get(BUTTON_ID);
+    let toggle = crate::get(TOGGLE_ID);
+
+    let text = crate::get(TEXT);
+    assert_eq!(text.text_content(), Some("false false".to_owned()));
+
+    button.click();
+    button.click();
+    assert!(text.is_same_node(Some(&crate::get(TEXT))));
+
+    button.click();
+    let text = crate::get(TEXT);
+    assert_eq!(text.text_content(), Some("false true".to_owned()));
+
+    toggle.click();
+    let text = crate::get(TEXT);
+    assert_eq!(text.text_content(), Some("true true".to_owned()));
+
+    button.click();
+    assert!(text.is_same_node(Some(&crate::get(TEXT))));
+}
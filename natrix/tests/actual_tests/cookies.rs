@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use natrix::cookies::{self, CookieOptions, SameSite};
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn set_cookie_can_be_read_back() {
+    let options = CookieOptions::new().path("/").same_site(SameSite::Lax);
+    cookies::set_cookie("natrix_first", "one", &options);
+    cookies::set_cookie("natrix_second", "two", &options);
+
+    assert_eq!(cookies::get_cookie("natrix_first"), Some("one".to_owned()));
+    assert_eq!(cookies::get_cookie("natrix_second"), Some("two".to_owned()));
+    assert_eq!(cookies::get_cookie("natrix_missing"), None);
+    // Only exact names match
+    assert_eq!(cookies::get_cookie("first"), None);
+}
+
+#[wasm_bindgen_test]
+fn cookie_values_are_encoded() {
+    let options = CookieOptions::new().path("/");
+    cookies::set_cookie("natrix encoded", "a b; c=d", &options);
+
+    assert_eq!(
+        cookies::get_cookie("natrix encoded"),
+        Some("a b; c=d".to_owned())
+    );
+}
+
+#[wasm_bindgen_test]
+fn zero_max_age_removes_cookie() {
+    let options = CookieOptions::new().path("/");
+    cookies::set_cookie("natrix_removed", "value", &options);
+    assert!(cookies::get_cookie("natrix_removed").is_some());
+
+    cookies::set_cookie("natrix_removed", "", &options.max_age(Duration::ZERO));
+    assert_eq!(cookies::get_cookie("natrix_removed"), None);
+}
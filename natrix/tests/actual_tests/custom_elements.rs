@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use natrix::async_utils;
+use natrix::custom_element::{CustomElement, register_custom_element};
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const HOST_ID: &str = "__HOST";
+const GREETING_ID: &str = "__GREETING";
+const TAG: &str = "natrix-test-greeting";
+
+#[derive(Component)]
+struct Greeting {
+    name: String,
+}
+
+impl Component for Greeting {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = String;
+    fn render() -> impl Element<Self> {
+        e::h1()
+            .id(GREETING_ID)
+            .text(|ctx: R<Self>| format!("Hello {}", *ctx.name))
+    }
+
+    fn handle_message(ctx: E<Self>, msg: Self::ReceiveMessage) {
+        *ctx.name = msg;
+    }
+}
+
+impl CustomElement for Greeting {
+    const OBSERVED_ATTRIBUTES: &'static [&'static str] = &["name"];
+
+    fn create() -> Self {
+        Self {
+            name: String::from("World"),
+        }
+    }
+
+    fn attribute_changed(_name: &str, value: Option<String>) -> Option<String> {
+        Some(value.unwrap_or_else(|| String::from("World")))
+    }
+}
+
+#[derive(Component)]
+struct Host;
+
+impl Component for Host {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div().id(HOST_ID)
+    }
+}
+
+#[wasm_bindgen_test]
+async fn custom_element_mounts_and_unmounts() {
+    crate::mount_test(Host);
+    register_custom_element::<Greeting>(TAG).unwrap();
+
+    let document = web_sys::window().unwrap().document().unwrap();
+    let element = document.create_element(TAG).unwrap();
+    element.set_attribute("name", "Natrix").unwrap();
+    crate::get(HOST_ID).append_child(&element).unwrap();
+
+    async_utils::sleep(Duration::from_millis(10)).await;
+    let greeting = crate::get(GREETING_ID);
+    assert_eq!(greeting.text_content(), Some("Hello Natrix".to_owned()));
+
+    element.remove_attribute("name").unwrap();
+    async_utils::sleep(Duration::from_millis(10)).await;
+    assert_eq!(greeting.text_content(), Some("Hello World".to_owned()));
+
+    element.remove();
+    assert!(document.get_element_by_id(GREETING_ID).is_none());
+}
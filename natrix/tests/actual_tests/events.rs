@@ -39,6 +39,130 @@ fn can_use_event() {
     assert_eq!(button.text_content(), Some("3".to_owned()));
 }
 
+#[derive(Component)]
+struct MutHandler {
+    value: u8,
+}
+
+impl Component for MutHandler {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        let mut clicks = 0;
+        e::button()
+            .id(BUTTON_ID)
+            .child(|ctx: R<Self>| *ctx.value)
+            .on_mut::<events::Click>(move |ctx: E<Self>, _| {
+                clicks += 2;
+                *ctx.value = clicks;
+            })
+    }
+}
+
+#[wasm_bindgen_test]
+fn on_mut_keeps_captured_state() {
+    crate::mount_test(MutHandler { value: 0 });
+
+    let button = crate::get(BUTTON_ID);
+
+    button.click();
+    assert_eq!(button.text_content(), Some("2".to_owned()));
+
+    button.click();
+    assert_eq!(button.text_content(), Some("4".to_owned()));
+}
+
+const ERROR_ID: &str = "__ERROR";
+
+#[derive(Component)]
+struct Fallible {
+    error: Option<String>,
+}
+
+impl Component for Fallible {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::div()
+                    .id(ERROR_ID)
+                    .child(|ctx: R<Self>| ctx.error.clone()),
+            )
+            .child(e::button().id(BUTTON_ID).on_try::<events::Click, _>(
+                |_ctx: E<Self>, _| -> Result<(), std::num::ParseIntError> {
+                    "abc".parse::<u8>()?;
+                    Ok(())
+                },
+            ))
+    }
+
+    fn handle_error(ctx: E<Self>, error: String) -> Result<(), String> {
+        *ctx.error = Some(error);
+        Ok(())
+    }
+}
+
+#[wasm_bindgen_test]
+fn on_try_handles_error() {
+    crate::mount_test(Fallible { error: None });
+
+    crate::get(BUTTON_ID).click();
+    assert_eq!(
+        crate::get(ERROR_ID).text_content(),
+        Some("invalid digit found in string".to_owned())
+    );
+}
+
+#[derive(Component)]
+struct FallibleChild;
+
+impl Component for FallibleChild {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(BUTTON_ID)
+            .on_try::<events::Click, _>(|_ctx: E<Self>, _| Err("child failed"))
+    }
+}
+
+#[derive(Component)]
+struct ErrorBoundary {
+    error: Option<String>,
+}
+
+impl Component for ErrorBoundary {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::div()
+                    .id(ERROR_ID)
+                    .child(|ctx: R<Self>| ctx.error.clone()),
+            )
+            .child(C::new(FallibleChild))
+    }
+
+    fn handle_error(ctx: E<Self>, error: String) -> Result<(), String> {
+        *ctx.error = Some(error);
+        Ok(())
+    }
+}
+
+#[wasm_bindgen_test]
+async fn on_try_propagates_to_parent() {
+    crate::mount_test(ErrorBoundary { error: None });
+
+    crate::get(BUTTON_ID).click();
+    natrix::async_utils::sleep(std::time::Duration::from_millis(10)).await;
+    assert_eq!(
+        crate::get(ERROR_ID).text_content(),
+        Some("child failed".to_owned())
+    );
+}
+
 #[derive(Component)]
 struct OnMount {
     value: u8,
@@ -62,3 +186,390 @@ fn on_mount() {
     let text = crate::get(BUTTON_ID);
     assert_eq!(text.text_content(), Some("10".to_string()));
 }
+
+#[derive(Component)]
+struct Replaced {
+    value: u8,
+}
+
+impl Component for Replaced {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(BUTTON_ID)
+            .child(|ctx: R<Self>| *ctx.value)
+            .on::<events::Click>(|ctx: E<Self>, _| *ctx.value += 1)
+            .on_capture::<events::Click>(|ctx: E<Self>, _| *ctx.value += 2)
+            .on_replace::<events::Click>(|ctx: E<Self>, _| *ctx.value += 10)
+    }
+}
+
+#[wasm_bindgen_test]
+fn on_replace() {
+    crate::mount_test(Replaced { value: 0 });
+
+    let button = crate::get(BUTTON_ID);
+
+    button.click();
+    assert_eq!(button.text_content(), Some("10".to_owned()));
+}
+
+#[derive(Component)]
+struct Additive {
+    value: u8,
+}
+
+impl Component for Additive {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(BUTTON_ID)
+            .child(|ctx: R<Self>| *ctx.value)
+            .on::<events::Click>(|ctx: E<Self>, _| *ctx.value += 1)
+            .on::<events::Click>(|ctx: E<Self>, _| *ctx.value += 10)
+    }
+}
+
+#[wasm_bindgen_test]
+fn on_additive() {
+    crate::mount_test(Additive { value: 0 });
+
+    let button = crate::get(BUTTON_ID);
+
+    button.click();
+    assert_eq!(button.text_content(), Some("11".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn test_handle() {
+    let handle = crate::mount_for_test(Counter { value: 0 });
+
+    assert_eq!(handle.text(BUTTON_ID), Some("0".to_owned()));
+    handle.click(BUTTON_ID);
+    assert_eq!(handle.text(BUTTON_ID), Some("1".to_owned()));
+
+    let button = handle.get_by_id(BUTTON_ID);
+    drop(handle);
+    assert!(!button.is_connected());
+}
+
+const INPUT_ID: &str = "__INPUT";
+
+#[derive(Component)]
+struct Enter {
+    value: u8,
+}
+
+impl Component for Enter {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::textarea()
+                    .id(INPUT_ID)
+                    .on_enter(|ctx: E<Self>| *ctx.value += 1)
+                    .on::<events::KeyDown>(|ctx: E<Self>, _| *ctx.value += 10),
+            )
+            .child(e::div().id(BUTTON_ID).child(|ctx: R<Self>| *ctx.value))
+    }
+}
+
+fn press(key: &str, shift: bool) {
+    let init = web_sys::KeyboardEventInit::new();
+    init.set_key(key);
+    init.set_shift_key(shift);
+    let event = web_sys::KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init)
+        .expect("Failed to create event");
+    crate::get(INPUT_ID)
+        .dispatch_event(&event)
+        .expect("Failed to dispatch event");
+}
+
+#[wasm_bindgen_test]
+fn on_enter() {
+    crate::mount_test(Enter { value: 0 });
+
+    let text = crate::get(BUTTON_ID);
+
+    press("a", false);
+    assert_eq!(text.text_content(), Some("10".to_owned()));
+
+    press("Enter", false);
+    assert_eq!(text.text_content(), Some("21".to_owned()));
+
+    press("Enter", true);
+    assert_eq!(text.text_content(), Some("31".to_owned()));
+}
+
+const LOG_ID: &str = "__LOG";
+
+#[derive(Component)]
+struct CaptureOrder {
+    log: String,
+}
+
+impl Component for CaptureOrder {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .on::<events::Click>(|ctx: E<Self>, _| ctx.log.push('b'))
+            .on_capture::<events::Click>(|ctx: E<Self>, _| ctx.log.push('c'))
+            .child(
+                e::button()
+                    .id(BUTTON_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| ctx.log.push('t')),
+            )
+            .child(e::span().id(LOG_ID).text(|ctx: R<Self>| ctx.log.clone()))
+    }
+}
+
+#[wasm_bindgen_test]
+fn capture_runs_before_target() {
+    crate::mount_test(CaptureOrder { log: String::new() });
+
+    crate::get(BUTTON_ID).click();
+    assert_eq!(crate::get(LOG_ID).text_content(), Some("ctb".to_owned()));
+}
+
+const SELECT_ID: &str = "__SELECT";
+const THEME_ID: &str = "__THEME";
+
+#[derive(SelectOptions, Clone, Copy, PartialEq, Debug)]
+enum Theme {
+    Light,
+    Dark,
+}
+
+impl std::fmt::Display for Theme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Light => write!(f, "Light mode"),
+            Self::Dark => write!(f, "Dark mode"),
+        }
+    }
+}
+
+#[derive(Component)]
+struct ThemePicker {
+    theme: Theme,
+}
+
+impl Component for ThemePicker {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::select_options(
+                    |ctx: R<Self>| *ctx.theme,
+                    |ctx: E<Self>, theme| *ctx.theme = theme,
+                )
+                .id(SELECT_ID),
+            )
+            .child(
+                e::span()
+                    .id(THEME_ID)
+                    .text(|ctx: R<Self>| format!("{:?}", *ctx.theme)),
+            )
+    }
+}
+
+#[wasm_bindgen_test]
+fn select_options_from_enum() {
+    crate::mount_test(ThemePicker { theme: Theme::Dark });
+
+    let select = crate::get(SELECT_ID);
+    assert_eq!(select.child_element_count(), 2);
+    assert_eq!(
+        select.text_content(),
+        Some("Light modeDark mode".to_owned())
+    );
+
+    let value = wasm_bindgen::JsValue::from_str("value");
+    let current = web_sys::js_sys::Reflect::get(&select, &value).unwrap();
+    assert_eq!(current.as_string(), Some("Dark".to_owned()));
+
+    web_sys::js_sys::Reflect::set(&select, &value, &"Light".into()).unwrap();
+    let event = web_sys::Event::new("change").unwrap();
+    select.dispatch_event(&event).unwrap();
+    assert_eq!(
+        crate::get(THEME_ID).text_content(),
+        Some("Light".to_owned())
+    );
+}
+
+const OPEN_ID: &str = "__OPEN";
+const FRAME_NAME: &str = "natrix-test-frame";
+
+#[derive(Component)]
+struct OpenUrl {
+    opened: Option<bool>,
+}
+
+impl Component for OpenUrl {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div().child(e::iframe().name(FRAME_NAME)).child(
+            e::button()
+                .id(OPEN_ID)
+                .text(|ctx: R<Self>| format!("{:?}", *ctx.opened))
+                .on::<events::Click>(|ctx: E<Self>, _| {
+                    *ctx.opened = Some(ctx.open_url("about:blank", FRAME_NAME).is_some());
+                }),
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+fn open_url_returns_window() {
+    crate::mount_test(OpenUrl { opened: None });
+
+    let button = crate::get(OPEN_ID);
+    button.click();
+    assert_eq!(button.text_content(), Some("Some(true)".to_owned()));
+}
+
+const FIRST_ID: &str = "__FIRST";
+const LAST_ID: &str = "__LAST";
+
+#[derive(Component)]
+struct Modal {
+    #[natrix(skip)]
+    dialog: natrix::html_elements::NodeRef,
+}
+
+impl Component for Modal {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(e::button().text("Outside"))
+            .child(|ctx: R<Self>| {
+                e::div()
+                    .node_ref(&ctx.dialog)
+                    .child(e::button().id(FIRST_ID))
+                    .child(e::button().id(LAST_ID))
+            })
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        let dialog = ctx.dialog.clone();
+        ctx.use_focus_trap(&dialog);
+    }
+}
+
+fn press_tab(target: &web_sys::HtmlElement, shift: bool) {
+    let init = web_sys::KeyboardEventInit::new();
+    init.set_key("Tab");
+    init.set_shift_key(shift);
+    init.set_bubbles(true);
+    let event = web_sys::KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init)
+        .expect("Failed to create event");
+    target
+        .dispatch_event(&event)
+        .expect("Failed to dispatch event");
+}
+
+#[wasm_bindgen_test]
+fn focus_trap_wraps_tab() {
+    crate::mount_test(Modal {
+        dialog: natrix::html_elements::NodeRef::new(),
+    });
+
+    let first = crate::get(FIRST_ID);
+    let last = crate::get(LAST_ID);
+    let document = web_sys::window().unwrap().document().unwrap();
+    let is_focused = |element: &web_sys::HtmlElement| {
+        document
+            .active_element()
+            .is_some_and(|active| element.is_same_node(Some(&active)))
+    };
+
+    last.focus().unwrap();
+    press_tab(&last, false);
+    assert!(is_focused(&first));
+
+    press_tab(&first, true);
+    assert!(is_focused(&last));
+}
+
+const DIRTY_ID: &str = "__DIRTY";
+
+#[derive(Component)]
+struct UnsavedChanges {
+    dirty: bool,
+}
+
+impl Component for UnsavedChanges {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(DIRTY_ID)
+            .on::<events::Click>(|ctx: E<Self>, _| *ctx.dirty = true)
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        ctx.use_before_unload(|ctx| *ctx.dirty);
+    }
+}
+
+fn try_unload() -> bool {
+    let init = web_sys::EventInit::new();
+    init.set_cancelable(true);
+    let event = web_sys::Event::new_with_event_init_dict("beforeunload", &init)
+        .expect("Failed to create event");
+    web_sys::window()
+        .unwrap()
+        .dispatch_event(&event)
+        .expect("Failed to dispatch event")
+}
+
+#[wasm_bindgen_test]
+fn before_unload_checks_predicate() {
+    crate::mount_test(UnsavedChanges { dirty: false });
+    assert!(try_unload());
+
+    crate::get(DIRTY_ID).click();
+    assert!(!try_unload());
+}
+
+const TEXT_ID: &str = "__TEXT";
+
+#[derive(Component)]
+struct Login {
+    username: String,
+}
+
+impl Component for Login {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::form()
+            .child(e::input().name("username").value("alice"))
+            .child(e::button().id(BUTTON_ID).text("Login"))
+            .child(
+                e::div()
+                    .id(TEXT_ID)
+                    .text(|ctx: R<Self>| ctx.username.clone()),
+            )
+            .on_submit(|ctx: E<Self>, data: web_sys::FormData| {
+                *ctx.username = data.get("username").as_string().unwrap_or_default();
+            })
+    }
+}
+
+#[wasm_bindgen_test]
+fn on_submit_reads_form_data() {
+    crate::mount_test(Login {
+        username: String::new(),
+    });
+
+    crate::get(BUTTON_ID).click();
+    assert_eq!(crate::get(TEXT_ID).text_content(), Some("alice".to_owned()));
+}
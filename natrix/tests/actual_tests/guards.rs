@@ -287,3 +287,206 @@ fn guard_non_copy() {
     let text = crate::get(TEXT);
     assert_eq!(text.text_content(), Some("hello".to_owned()));
 }
+
+const OTHER_BUTTON: &str = "__OTHER_BUTTON";
+
+#[derive(Component)]
+struct GuardTesterTuple {
+    first: Option<u8>,
+    second: Option<u8>,
+}
+
+impl Component for GuardTesterTuple {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON)
+                    .on::<events::Click>(|ctx: E<Self>, _| match &mut *ctx.first {
+                        Some(value) => *value += 1,
+                        None => *ctx.first = Some(0),
+                    }),
+            )
+            .child(
+                e::button()
+                    .id(OTHER_BUTTON)
+                    .on::<events::Click>(|ctx: E<Self>, _| match &mut *ctx.second {
+                        Some(_) => *ctx.second = None,
+                        None => *ctx.second = Some(10),
+                    }),
+            )
+            .child(|ctx: R<Self>| {
+                if let Some((first, second)) =
+                    guard_tuple!(|ctx| ctx.first.as_ref(), ctx.second.as_ref())
+                {
+                    e::div().text(move |ctx: R<Self>| *ctx.get(&first) + *ctx.get(&second))
+                } else {
+                    e::div().text("NO VALUE")
+                }
+                .id(TEXT)
+            })
+    }
+}
+
+#[wasm_bindgen_test]
+fn guard_tuple() {
+    crate::mount_test(GuardTesterTuple {
+        first: None,
+        second: None,
+    });
+
+    let button = crate::get(BUTTON);
+    let other_button = crate::get(OTHER_BUTTON);
+
+    let text = crate::get(TEXT);
+    assert_eq!(text.text_content(), Some("NO VALUE".to_owned()));
+
+    button.click();
+    let text = crate::get(TEXT);
+    assert_eq!(text.text_content(), Some("NO VALUE".to_owned()));
+
+    other_button.click();
+    let text = crate::get(TEXT);
+    assert_eq!(text.text_content(), Some("10".to_owned()));
+
+    button.click();
+    assert_eq!(text.text_content(), Some("11".to_owned()));
+
+    other_button.click();
+    let text = crate::get(TEXT);
+    assert_eq!(text.text_content(), Some("NO VALUE".to_owned()));
+}
+
+#[derive(Component)]
+struct MatchGuardTester {
+    value: Result<u8, &'static str>,
+}
+
+impl Component for MatchGuardTester {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON)
+                    .on::<events::Click>(|ctx: E<Self>, _| match &mut *ctx.value {
+                        Ok(1) => *ctx.value = Err("ERROR"),
+                        Ok(value) => *value += 1,
+                        Err(_) => *ctx.value = Ok(0),
+                    }),
+            )
+            .child(|ctx: R<Self>| {
+                match_guard!(@owned |ctx| *ctx.value,
+                    Ok(value) => e::div()
+                        .id(TEXT)
+                        .text(move |ctx: R<Self>| ctx.get_owned(&value)),
+                    Err(error) => e::span()
+                        .id(TEXT)
+                        .text(move |ctx: R<Self>| ctx.get_owned(&error)),
+                )
+            })
+    }
+}
+
+#[wasm_bindgen_test]
+fn match_guard() {
+    crate::mount_test(MatchGuardTester {
+        value: Err("START"),
+    });
+
+    let button = crate::get(BUTTON);
+
+    let text = crate::get(TEXT);
+    assert_eq!(text.tag_name(), "SPAN");
+    assert_eq!(text.text_content(), Some("START".to_owned()));
+
+    button.click();
+    let text = crate::get(TEXT);
+    assert_eq!(text.tag_name(), "DIV");
+    assert_eq!(text.text_content(), Some("0".to_owned()));
+
+    // Staying `Ok` keeps the same element
+    button.click();
+    assert_eq!(text.text_content(), Some("1".to_owned()));
+    assert!(text.is_connected());
+
+    button.click();
+    let text = crate::get(TEXT);
+    assert_eq!(text.tag_name(), "SPAN");
+    assert_eq!(text.text_content(), Some("ERROR".to_owned()));
+}
+
+struct Profile {
+    nickname: Option<String>,
+}
+
+#[derive(Component)]
+struct ChainGuardTester {
+    profile: Option<Profile>,
+}
+
+impl Component for ChainGuardTester {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON)
+                    .on::<events::Click>(|ctx: E<Self>, _| match &mut *ctx.profile {
+                        None => *ctx.profile = Some(Profile { nickname: None }),
+                        Some(Profile { nickname: None }) => {
+                            *ctx.profile = Some(Profile {
+                                nickname: Some("vivax".to_owned()),
+                            });
+                        }
+                        Some(Profile {
+                            nickname: Some(nickname),
+                        }) => {
+                            if nickname == "vivax" {
+                                nickname.push('!');
+                            } else {
+                                *ctx.profile = None;
+                            }
+                        }
+                    }),
+            )
+            .child(|ctx: R<Self>| {
+                if let Some(nickname) =
+                    guard_option!(@chain |ctx| ctx.profile.as_ref()?.nickname.as_ref())
+                {
+                    e::div().text(move |ctx: R<Self>| ctx.get(&nickname).clone())
+                } else {
+                    e::div().text("NO VALUE")
+                }
+                .id(TEXT)
+            })
+    }
+}
+
+#[wasm_bindgen_test]
+fn guard_chain() {
+    crate::mount_test(ChainGuardTester { profile: None });
+
+    let button = crate::get(BUTTON);
+    assert_eq!(crate::get(TEXT).text_content(), Some("NO VALUE".to_owned()));
+
+    // The outer link is `Some`, but the inner link is still `None`
+    button.click();
+    assert_eq!(crate::get(TEXT).text_content(), Some("NO VALUE".to_owned()));
+
+    button.click();
+    let text = crate::get(TEXT);
+    assert_eq!(text.text_content(), Some("vivax".to_owned()));
+
+    // Staying `Some` keeps the same element
+    button.click();
+    assert_eq!(text.text_content(), Some("vivax!".to_owned()));
+    assert!(text.is_connected());
+
+    button.click();
+    assert_eq!(crate::get(TEXT).text_content(), Some("NO VALUE".to_owned()));
+}
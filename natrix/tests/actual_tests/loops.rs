@@ -150,3 +150,274 @@ fn change_only_triggers_actual_changed() {
     change_button.click();
     assert!(item.parent_node().is_some(), "unneeded re-rendered");
 }
+
+const KEYED_LIST_ID: &str = "__KEYED_LIST";
+
+#[derive(Clone, PartialEq)]
+struct Entry {
+    id: u8,
+    name: &'static str,
+}
+
+#[derive(Component)]
+struct KeyedLoop {
+    entries: Vec<Entry>,
+}
+
+impl Component for KeyedLoop {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(CHANGE_BUTTON_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        ctx.entries.reverse();
+                    }),
+            )
+            .child(
+                e::button()
+                    .id(REMOVE_BUTTON_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        ctx.entries.remove(0);
+                    }),
+            )
+            .child(
+                e::button()
+                    .id(ADD_BUTTON_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        ctx.entries[0].name = "z";
+                    }),
+            )
+            .child(e::div().id(KEYED_LIST_ID).child(for_each!(
+                |ctx| ctx.entries => |entry| e::span().id(format!("entry-{}", entry.id)).text(entry.name)
+            )))
+    }
+}
+
+#[wasm_bindgen_test]
+fn keyed_list() {
+    crate::mount_test(KeyedLoop {
+        entries: vec![
+            Entry { id: 0, name: "a" },
+            Entry { id: 1, name: "b" },
+            Entry { id: 2, name: "c" },
+        ],
+    });
+
+    let list = crate::get(KEYED_LIST_ID);
+    assert_eq!(list.text_content(), Some("abc".to_owned()));
+
+    let first = crate::get("entry-0");
+    crate::get(CHANGE_BUTTON_ID).click();
+    assert_eq!(list.text_content(), Some("cba".to_owned()));
+    assert!(first.is_same_node(Some(&crate::get("entry-0"))));
+
+    crate::get(REMOVE_BUTTON_ID).click();
+    assert_eq!(list.text_content(), Some("ba".to_owned()));
+    assert!(first.is_same_node(Some(&crate::get("entry-0"))));
+
+    crate::get(ADD_BUTTON_ID).click();
+    assert_eq!(list.text_content(), Some("za".to_owned()));
+}
+
+#[derive(Component)]
+struct KeyedFragments {
+    entries: Vec<Entry>,
+}
+
+impl Component for KeyedFragments {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(CHANGE_BUTTON_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        ctx.entries.reverse();
+                    }),
+            )
+            .child(
+                e::button()
+                    .id(REMOVE_BUTTON_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        ctx.entries.remove(0);
+                    }),
+            )
+            .child(e::div().id(KEYED_LIST_ID).child(for_each!(
+                |ctx| ctx.entries => |entry| [e::span().text(entry.name), e::span().text(entry.id)]
+            )))
+    }
+}
+
+#[wasm_bindgen_test]
+fn keyed_list_fragments() {
+    crate::mount_test(KeyedFragments {
+        entries: vec![
+            Entry { id: 0, name: "a" },
+            Entry { id: 1, name: "b" },
+            Entry { id: 2, name: "c" },
+        ],
+    });
+
+    let list = crate::get(KEYED_LIST_ID);
+    assert_eq!(list.text_content(), Some("a0b1c2".to_owned()));
+
+    crate::get(CHANGE_BUTTON_ID).click();
+    assert_eq!(list.text_content(), Some("c2b1a0".to_owned()));
+
+    crate::get(REMOVE_BUTTON_ID).click();
+    assert_eq!(list.text_content(), Some("b1a0".to_owned()));
+}
+
+#[derive(Component)]
+struct KeyedPrimitives {
+    items: Vec<u8>,
+}
+
+impl Component for KeyedPrimitives {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(ADD_BUTTON_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        ctx.items.insert(0, 0);
+                    }),
+            )
+            .child(e::div().id(KEYED_LIST_ID).child(for_each!(
+                |ctx| ctx.items, key = |item| *item => |item| e::span().text(item)
+            )))
+    }
+}
+
+#[wasm_bindgen_test]
+fn keyed_list_primitives() {
+    crate::mount_test(KeyedPrimitives {
+        items: vec![1, 2, 3],
+    });
+
+    let list = crate::get(KEYED_LIST_ID);
+    assert_eq!(list.text_content(), Some("123".to_owned()));
+
+    crate::get(ADD_BUTTON_ID).click();
+    assert_eq!(list.text_content(), Some("0123".to_owned()));
+}
+
+const TEXT_ID: &str = "__TEXT";
+
+#[derive(Component)]
+struct ItemEvents {
+    entries: Vec<Entry>,
+    clicked: Option<usize>,
+}
+
+impl Component for ItemEvents {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(CHANGE_BUTTON_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        ctx.entries.reverse();
+                    }),
+            )
+            .child(e::div().id(KEYED_LIST_ID).child(for_each!(
+                |ctx| ctx.entries => |entry| e::span()
+                    .id(format!("entry-{}", entry.id))
+                    .text(entry.name)
+                    .on_item::<events::Click, u8>(|ctx: E<Self>, _, id| {
+                        ctx.entries.retain(|entry| entry.id != *id);
+                    })
+            )))
+            .child(List::new(
+                |ctx: &State<Self>| &ctx.entries,
+                |_ctx, getter| {
+                    e::div()
+                        .id(format!("index-{}", getter.index))
+                        .on_item::<events::Click, usize>(|ctx: E<Self>, _, index| {
+                            *ctx.clicked = Some(*index);
+                        })
+                },
+            ))
+            .child(e::div().id(TEXT_ID).text(|ctx: R<Self>| *ctx.clicked))
+    }
+}
+
+#[wasm_bindgen_test]
+fn on_item_receives_key() {
+    crate::mount_test(ItemEvents {
+        entries: vec![
+            Entry { id: 0, name: "a" },
+            Entry { id: 1, name: "b" },
+            Entry { id: 2, name: "c" },
+        ],
+        clicked: None,
+    });
+
+    let list = crate::get(KEYED_LIST_ID);
+    crate::get(CHANGE_BUTTON_ID).click();
+    assert_eq!(list.text_content(), Some("cba".to_owned()));
+
+    crate::get("entry-1").click();
+    assert_eq!(list.text_content(), Some("ca".to_owned()));
+
+    crate::get("entry-2").click();
+    assert_eq!(list.text_content(), Some("a".to_owned()));
+
+    crate::get("index-0").click();
+    assert_eq!(crate::get(TEXT_ID).text_content(), Some("0".to_owned()));
+}
+
+#[derive(Component)]
+struct SharedItemEvents {
+    rows: Vec<u32>,
+    clicked: Option<usize>,
+}
+
+impl Component for SharedItemEvents {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+
+    fn render() -> impl Element<Self> {
+        let select: natrix::html_elements::SharedHandler<Self, events::Click, usize> =
+            natrix::html_elements::SharedHandler::new(|ctx: E<Self>, _, index| {
+                *ctx.clicked = Some(*index);
+            });
+
+        e::div()
+            .child(List::new(
+                |ctx: &State<Self>| &ctx.rows,
+                move |_ctx, getter| {
+                    e::div()
+                        .id(format!("row-{}", getter.index))
+                        .on_item_shared(&select)
+                },
+            ))
+            .child(e::div().id(TEXT_ID).text(|ctx: R<Self>| *ctx.clicked))
+    }
+}
+
+#[wasm_bindgen_test]
+fn on_item_shared_receives_key() {
+    crate::mount_test(SharedItemEvents {
+        rows: (0..1000).collect(),
+        clicked: None,
+    });
+
+    crate::get("row-999").click();
+    assert_eq!(crate::get(TEXT_ID).text_content(), Some("999".to_owned()));
+
+    crate::get("row-3").click();
+    assert_eq!(crate::get(TEXT_ID).text_content(), Some("3".to_owned()));
+}
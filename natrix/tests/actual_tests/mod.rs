@@ -1,11 +1,17 @@
 mod async_callbacks;
 mod attributes;
+mod component_data;
 mod computed;
+mod cookies;
+mod custom_elements;
 mod events;
 mod generic_component;
 mod guards;
 mod loops;
 mod nested_reactivity;
+#[cfg(feature = "scoped_css")]
+mod scoped_css;
 mod simple_reactivty;
 mod simple_rendering;
+mod streams;
 mod sub_components;
@@ -71,3 +71,87 @@ fn update_affects_inner_node() {
     button_2.click();
     assert_eq!(text.text_content(), Some("5".to_owned()));
 }
+
+#[derive(Component)]
+struct User {
+    name: u8,
+    age: u8,
+}
+
+#[derive(Component)]
+struct Profile {
+    #[nested]
+    user: User,
+}
+
+impl Component for Profile {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON_1)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        *ctx.user.age += 1;
+                    }),
+            )
+            .child(|ctx: R<Self>| e::div().id(TEXT).text(*ctx.user.name))
+            .child(|ctx: R<Self>| e::div().id(BUTTON_2).text(*ctx.user.age))
+    }
+}
+
+#[wasm_bindgen_test]
+fn nested_fields_tracked_individually() {
+    crate::mount_test(Profile {
+        user: User { name: 0, age: 0 },
+    });
+
+    let button = crate::get(BUTTON_1);
+    let name = crate::get(TEXT);
+
+    button.click();
+
+    assert!(name.is_same_node(Some(&crate::get(TEXT))));
+    assert_eq!(crate::get(BUTTON_2).text_content(), Some("1".to_owned()));
+}
+
+#[derive(Component)]
+struct Scores {
+    #[nested]
+    scores: natrix::map_signal::MapSignal<u8, u8>,
+}
+
+impl Component for Scores {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON_1)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        if let Some(score) = ctx.scores.get_mut(&2) {
+                            *score += 1;
+                        }
+                    }),
+            )
+            .child(|ctx: R<Self>| e::div().id(TEXT).text(ctx.scores.get(&1).copied()))
+            .child(|ctx: R<Self>| e::div().id(BUTTON_2).text(ctx.scores.get(&2).copied()))
+    }
+}
+
+#[wasm_bindgen_test]
+fn map_signal_keys_tracked_individually() {
+    crate::mount_test(Scores {
+        scores: [(1, 0), (2, 0)].into_iter().collect(),
+    });
+
+    let button = crate::get(BUTTON_1);
+    let first = crate::get(TEXT);
+
+    button.click();
+
+    assert!(first.is_same_node(Some(&crate::get(TEXT))));
+    assert_eq!(crate::get(BUTTON_2).text_content(), Some("1".to_owned()));
+}
@@ -0,0 +1,54 @@
+#![allow(dead_code)]
+
+use natrix::prelude::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+scoped_css!(
+    mod first "
+        .card {
+            color: red;
+        }
+    "
+    mod referencing "
+        .list ${first::CARD} {
+            color: green;
+        }
+    "
+);
+
+scoped_css!(mod second "
+    .card {
+        color: blue;
+    }
+");
+
+const CARD_ID: &str = "CARD_ID";
+
+#[derive(Component)]
+struct Cards;
+
+impl Component for Cards {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div().id(CARD_ID).class(first::CARD).class(second::CARD)
+    }
+}
+
+#[wasm_bindgen_test]
+fn same_class_gets_distinct_constants() {
+    assert_ne!(first::CARD, second::CARD);
+    assert!(first::CARD.ends_with("card"));
+    assert!(second::CARD.ends_with("card"));
+}
+
+#[wasm_bindgen_test]
+fn class_accepts_generated_constants() {
+    crate::mount_test(Cards);
+
+    let class_list = crate::get(CARD_ID).class_list();
+    assert!(class_list.contains(first::CARD));
+    assert!(class_list.contains(second::CARD));
+}
@@ -1,6 +1,8 @@
 #![allow(dead_code)]
 
+use natrix::html_elements::NodeRef;
 use natrix::prelude::*;
+use natrix::state::State;
 use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
 
 wasm_bindgen_test_configure!(run_in_browser);
@@ -55,3 +57,617 @@ fn updates_text() {
     button.click();
     assert_eq!(button.text_content(), Some("value: 3".to_owned()));
 }
+
+#[cfg(feature = "profiling")]
+mod profiling_test {
+    use natrix::profiling;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn profiling_counts_field_usage() {
+        profiling::reset();
+        crate::mount_test(Counter { value: 0 });
+
+        let button = crate::get(BUTTON_ID);
+        button.click();
+        button.click();
+
+        let report = profiling::report();
+        let value = report
+            .iter()
+            .find(|field| field.component.ends_with("::Counter") && field.field == "value")
+            .expect("Field not in report");
+        assert_eq!(value.reads, 3);
+        assert_eq!(value.writes, 2);
+        assert_eq!(value.hook_reruns, 2);
+    }
+}
+
+#[derive(Component)]
+struct CowLabel {
+    value: u8,
+}
+
+impl Component for CowLabel {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(BUTTON_ID)
+            .child(|ctx: R<Self>| -> std::borrow::Cow<'static, str> {
+                if *ctx.value == 0 {
+                    "none".into()
+                } else {
+                    format!("{} items", *ctx.value).into()
+                }
+            })
+            .on::<events::Click>(|ctx: E<Self>, _| *ctx.value += 1)
+    }
+}
+
+#[wasm_bindgen_test]
+fn updates_cow_text() {
+    crate::mount_test(CowLabel { value: 0 });
+
+    let button = crate::get(BUTTON_ID);
+    assert_eq!(button.text_content(), Some("none".to_owned()));
+    let text = button.first_child();
+
+    button.click();
+    assert_eq!(button.text_content(), Some("1 items".to_owned()));
+    assert!(
+        button
+            .first_child()
+            .is_some_and(|node| node.is_same_node(text.as_ref()))
+    );
+
+    button.click();
+    assert_eq!(button.text_content(), Some("2 items".to_owned()));
+}
+
+#[derive(Component)]
+struct FragmentToggle {
+    value: u8,
+}
+
+impl Component for FragmentToggle {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(BUTTON_ID)
+            .text("start ")
+            .child(|ctx: R<Self>| {
+                e::fragment()
+                    .child(e::span().text("value: "))
+                    .child_opt((*ctx.value % 2 == 1).then_some("odd "))
+                    .child(*ctx.value)
+            })
+            .text(" end")
+            .on::<events::Click>(|ctx: E<Self>, _| *ctx.value += 1)
+    }
+}
+
+#[wasm_bindgen_test]
+fn replaces_fragment() {
+    crate::mount_test(FragmentToggle { value: 0 });
+
+    let button = crate::get(BUTTON_ID);
+    assert_eq!(button.text_content(), Some("start value: 0 end".to_owned()));
+
+    button.click();
+    assert_eq!(
+        button.text_content(),
+        Some("start value: odd 1 end".to_owned())
+    );
+
+    button.click();
+    assert_eq!(button.text_content(), Some("start value: 2 end".to_owned()));
+}
+
+const INPUT_ID: &str = "__INPUT";
+
+#[derive(Component)]
+struct FocusKeeper {
+    value: u8,
+}
+
+impl Component for FocusKeeper {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div().child(e::preserve_focus(|ctx: R<Self>| {
+            e::div().child(*ctx.value).child(
+                e::input()
+                    .id(INPUT_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| *ctx.value += 1),
+            )
+        }))
+    }
+}
+
+#[wasm_bindgen_test]
+fn preserve_focus_across_rerender() {
+    crate::mount_test(FocusKeeper { value: 0 });
+
+    let input = crate::get(INPUT_ID);
+    input.focus().unwrap();
+    input.click();
+
+    let new_input = crate::get(INPUT_ID);
+    assert!(!new_input.is_same_node(Some(&input)));
+
+    let active = new_input
+        .owner_document()
+        .and_then(|document| document.active_element());
+    assert!(active.is_some_and(|active| active.is_same_node(Some(&new_input))));
+}
+
+const FORCE_ID: &str = "__FORCE";
+
+#[derive(Component)]
+struct ForceUpdate {
+    #[natrix(skip)]
+    external: std::rc::Rc<std::cell::Cell<u8>>,
+}
+
+impl Component for ForceUpdate {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON_ID)
+                    .text(|ctx: R<Self>| ctx.external.get())
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        ctx.external.set(ctx.external.get() + 1);
+                    }),
+            )
+            .child(
+                e::button()
+                    .id(FORCE_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| ctx.force_update()),
+            )
+    }
+}
+
+#[wasm_bindgen_test]
+fn force_update_reruns_callbacks() {
+    crate::mount_test(ForceUpdate {
+        external: std::rc::Rc::default(),
+    });
+
+    let button = crate::get(BUTTON_ID);
+    button.click();
+    assert_eq!(button.text_content(), Some("0".to_owned()));
+
+    crate::get(FORCE_ID).click();
+    assert_eq!(button.text_content(), Some("1".to_owned()));
+}
+
+const PANEL_ID: &str = "__PANEL";
+
+#[derive(Component)]
+struct Toggled {
+    value: u8,
+}
+
+impl Component for Toggled {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(BUTTON_ID)
+            .child_if(
+                |ctx: &State<Self>| *ctx.value % 2 == 1,
+                || e::span().id(PANEL_ID).text("panel"),
+            )
+            .on::<events::Click>(|ctx: E<Self>, _| *ctx.value += 1)
+    }
+}
+
+#[wasm_bindgen_test]
+fn child_if_toggles_child() {
+    crate::mount_test(Toggled { value: 0 });
+
+    let button = crate::get(BUTTON_ID);
+    assert_eq!(button.text_content(), Some(String::new()));
+
+    button.click();
+    assert_eq!(
+        crate::get(PANEL_ID).text_content(),
+        Some("panel".to_owned())
+    );
+
+    button.click();
+    assert_eq!(button.text_content(), Some(String::new()));
+    assert_eq!(button.child_nodes().length(), 1);
+}
+
+thread_local! {
+    static LISTENER_TRACKER: std::rc::Rc<()> = std::rc::Rc::new(());
+}
+
+#[derive(Component)]
+struct ListenerCleanup {
+    open: bool,
+}
+
+impl Component for ListenerCleanup {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| *ctx.open = !*ctx.open),
+            )
+            .child_if(
+                |ctx| *ctx.open,
+                || {
+                    let tracker = LISTENER_TRACKER.with(std::rc::Rc::clone);
+                    e::button()
+                        .id(PANEL_ID)
+                        .on::<events::Click>(move |_ctx: E<Self>, _| {
+                            let _ = &tracker;
+                        })
+                },
+            )
+    }
+}
+
+#[wasm_bindgen_test]
+fn event_listeners_dropped_on_unmount() {
+    crate::mount_test(ListenerCleanup { open: false });
+
+    let count = || LISTENER_TRACKER.with(std::rc::Rc::strong_count);
+    let baseline = count();
+    let button = crate::get(BUTTON_ID);
+
+    for _ in 0..5 {
+        button.click();
+        assert_eq!(count(), baseline + 1);
+
+        button.click();
+        assert_eq!(count(), baseline);
+    }
+}
+
+const MORPH_ID: &str = "__MORPH";
+
+#[derive(Component)]
+struct InPlace {
+    active: bool,
+}
+
+impl Component for InPlace {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| *ctx.active = !*ctx.active),
+            )
+            .child(|ctx: R<Self>| {
+                e::div()
+                    .id(MORPH_ID)
+                    .class(if *ctx.active { "on" } else { "off" })
+                    .text(if *ctx.active { "Active" } else { "Inactive" })
+            })
+    }
+}
+
+#[wasm_bindgen_test]
+fn static_element_updated_in_place() {
+    crate::mount_test(InPlace { active: false });
+
+    let element = crate::get(MORPH_ID);
+    assert_eq!(element.class_name(), "off");
+
+    crate::get(BUTTON_ID).click();
+    let updated = crate::get(MORPH_ID);
+    assert!(element.is_same_node(Some(&updated)));
+    assert_eq!(updated.class_name(), "on");
+    assert_eq!(updated.text_content(), Some("Active".to_owned()));
+}
+
+#[derive(Component)]
+struct InPlaceProperty {
+    active: bool,
+}
+
+impl Component for InPlaceProperty {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| *ctx.active = !*ctx.active),
+            )
+            .child(|ctx: R<Self>| {
+                e::div()
+                    .id(MORPH_ID)
+                    .prop("natrixState", if *ctx.active { "on" } else { "off" })
+            })
+    }
+}
+
+#[wasm_bindgen_test]
+fn element_with_property_replaced() {
+    crate::mount_test(InPlaceProperty { active: false });
+
+    let property = |element: &web_sys::Element| {
+        web_sys::js_sys::Reflect::get(element, &"natrixState".into())
+            .unwrap()
+            .as_string()
+    };
+    assert_eq!(property(&crate::get(MORPH_ID)), Some("off".to_owned()));
+
+    crate::get(BUTTON_ID).click();
+    assert_eq!(property(&crate::get(MORPH_ID)), Some("on".to_owned()));
+}
+
+#[derive(Component)]
+struct InPlaceNodeRef {
+    active: bool,
+    #[natrix(skip)]
+    node: NodeRef,
+}
+
+impl Component for InPlaceNodeRef {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| *ctx.active = !*ctx.active),
+            )
+            .child(|ctx: R<Self>| {
+                e::div()
+                    .id(MORPH_ID)
+                    .node_ref(&ctx.node)
+                    .class(if *ctx.active { "on" } else { "off" })
+            })
+    }
+}
+
+#[wasm_bindgen_test]
+fn node_ref_follows_rerender() {
+    let node = NodeRef::new();
+    crate::mount_test(InPlaceNodeRef {
+        active: false,
+        node: node.clone(),
+    });
+
+    crate::get(BUTTON_ID).click();
+    let element = crate::get(MORPH_ID);
+    assert_eq!(element.class_name(), "on");
+    assert!(
+        node.get()
+            .is_some_and(|node| node.is_same_node(Some(&element)))
+    );
+}
+
+const STYLE_SCOPE: &str = "__scope";
+
+#[derive(Component)]
+struct ReactiveRoot {
+    active: bool,
+}
+
+impl Component for ReactiveRoot {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    const STYLE_SCOPE: Option<&'static str> = Some(STYLE_SCOPE);
+    fn render() -> impl Element<Self> {
+        |ctx: R<Self>| {
+            e::button()
+                .id(BUTTON_ID)
+                .class(if *ctx.active { "on" } else { "off" })
+                .on::<events::Click>(|ctx: E<Self>, _| *ctx.active = !*ctx.active)
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn style_scope_follows_reactive_root() {
+    crate::mount_test(ReactiveRoot { active: false });
+    assert!(crate::get(BUTTON_ID).class_list().contains(STYLE_SCOPE));
+
+    crate::get(BUTTON_ID).click();
+    let button = crate::get(BUTTON_ID);
+    assert!(button.class_list().contains("on"));
+    assert!(button.class_list().contains(STYLE_SCOPE));
+}
+
+const PREVIOUS_ID: &str = "__PREVIOUS";
+
+#[derive(Component)]
+struct PreviousScore {
+    score: u8,
+    previous: u8,
+}
+
+impl Component for PreviousScore {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::button()
+                    .id(BUTTON_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| *ctx.score += 10),
+            )
+            .child(
+                e::span()
+                    .id(PREVIOUS_ID)
+                    .text(|ctx: R<Self>| format!("{} -> {}", *ctx.previous, *ctx.score)),
+            )
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        ctx.use_previous(|ctx| *ctx.score, |ctx| &mut *ctx.previous);
+    }
+}
+
+#[wasm_bindgen_test]
+fn use_previous_tracks_prior_value() {
+    crate::mount_test(PreviousScore {
+        score: 5,
+        previous: 0,
+    });
+
+    let text = crate::get(PREVIOUS_ID);
+    assert_eq!(text.text_content(), Some("5 -> 5".to_owned()));
+
+    crate::get(BUTTON_ID).click();
+    assert_eq!(text.text_content(), Some("5 -> 15".to_owned()));
+
+    crate::get(BUTTON_ID).click();
+    assert_eq!(text.text_content(), Some("15 -> 25".to_owned()));
+}
+
+const LEFT_ID: &str = "__LEFT";
+const RIGHT_ID: &str = "__RIGHT";
+
+#[derive(Component)]
+struct Branching {
+    left: bool,
+}
+
+impl Component for Branching {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(BUTTON_ID)
+            .child(e::either(
+                |ctx: &State<Self>| *ctx.left,
+                || e::span().id(LEFT_ID).text("left"),
+                || e::div().id(RIGHT_ID).text("right"),
+            ))
+            .on::<events::Click>(|ctx: E<Self>, _| *ctx.left = !*ctx.left)
+    }
+}
+
+#[wasm_bindgen_test]
+fn either_switches_branch() {
+    crate::mount_test(Branching { left: true });
+
+    let button = crate::get(BUTTON_ID);
+    assert_eq!(crate::get(LEFT_ID).text_content(), Some("left".to_owned()));
+
+    button.click();
+    assert_eq!(
+        crate::get(RIGHT_ID).text_content(),
+        Some("right".to_owned())
+    );
+    assert_eq!(button.text_content(), Some("right".to_owned()));
+
+    button.click();
+    assert_eq!(button.text_content(), Some("left".to_owned()));
+}
+
+#[derive(Component)]
+struct ClassToggle {
+    active: bool,
+}
+
+impl Component for ClassToggle {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(BUTTON_ID)
+            .class("tab")
+            .class_toggle("active", |ctx: R<Self>| *ctx.active)
+            .on::<events::Click>(|ctx: E<Self>, _| *ctx.active = !*ctx.active)
+    }
+}
+
+#[wasm_bindgen_test]
+fn class_toggle_keeps_other_classes() {
+    crate::mount_test(ClassToggle { active: false });
+
+    let button = crate::get(BUTTON_ID);
+    assert_eq!(button.class_name(), "tab");
+
+    button.click();
+    assert!(button.class_list().contains("active"));
+    assert!(button.class_list().contains("tab"));
+
+    button.class_list().add_1("external").unwrap();
+    button.click();
+    assert!(!button.class_list().contains("active"));
+    assert!(button.class_list().contains("tab"));
+    assert!(button.class_list().contains("external"));
+}
+
+const KEYED_ID: &str = "__KEYED";
+const SWITCH_ID: &str = "__SWITCH";
+
+#[derive(Component)]
+struct KeyedInput {
+    clicks: u8,
+    mode: u8,
+}
+
+impl Component for KeyedInput {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(|ctx: R<Self>| {
+                let _ = *ctx.clicks;
+                e::input()
+                    .id(KEYED_ID)
+                    .attr("data-clicks", |ctx: R<Self>| ctx.clicks.to_string())
+                    .with_key(*ctx.mode)
+            })
+            .child(
+                e::button()
+                    .id(BUTTON_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        *ctx.clicks += 1;
+                    }),
+            )
+            .child(
+                e::button()
+                    .id(SWITCH_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        *ctx.mode += 1;
+                    }),
+            )
+    }
+}
+
+#[wasm_bindgen_test]
+fn with_key_reuses_node() {
+    crate::mount_test(KeyedInput { clicks: 0, mode: 0 });
+
+    let input = crate::get(KEYED_ID);
+    crate::get(BUTTON_ID).click();
+    let after_click = crate::get(KEYED_ID);
+    assert!(input.is_same_node(Some(&after_click)));
+    assert_eq!(
+        after_click.get_attribute("data-clicks"),
+        Some("1".to_owned())
+    );
+
+    crate::get(SWITCH_ID).click();
+    let after_switch = crate::get(KEYED_ID);
+    assert!(!input.is_same_node(Some(&after_switch)));
+    assert_eq!(
+        after_switch.get_attribute("data-clicks"),
+        Some("1".to_owned())
+    );
+}
@@ -73,6 +73,30 @@ fn render_result_err() {
     assert_eq!(element.text_content(), Some("hey".to_owned()));
 }
 
+#[wasm_bindgen_test]
+fn render_unit() {
+    crate::mount_test(Render(()));
+
+    let element = crate::get(HELLO_ID);
+    assert_eq!(element.text_content(), Some("".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn render_nonzero() {
+    crate::mount_test(Render(std::num::NonZeroU32::new(42).unwrap()));
+
+    let element = crate::get(HELLO_ID);
+    assert_eq!(element.text_content(), Some("42".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn render_nonzero_negative() {
+    crate::mount_test(Render(std::num::NonZeroI64::new(-7).unwrap()));
+
+    let element = crate::get(HELLO_ID);
+    assert_eq!(element.text_content(), Some("-7".to_owned()));
+}
+
 #[cfg(feature = "either")]
 mod either_test {
     use either::Either;
@@ -96,6 +120,42 @@ mod either_test {
     }
 }
 
+#[cfg(feature = "time")]
+mod time_test {
+    use std::time::Duration;
+
+    use natrix::time::Formatted;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn render_duration() {
+        crate::mount_test(Render(Duration::from_secs(3725)));
+
+        let element = crate::get(HELLO_ID);
+        assert_eq!(element.text_content(), Some("1h 2m 5s".to_owned()));
+    }
+
+    #[wasm_bindgen_test]
+    fn render_short_duration() {
+        crate::mount_test(Render(Duration::from_millis(250)));
+
+        let element = crate::get(HELLO_ID);
+        assert_eq!(element.text_content(), Some("250ms".to_owned()));
+    }
+
+    #[wasm_bindgen_test]
+    fn render_formatted_duration() {
+        crate::mount_test(Render(Formatted::new(
+            Duration::from_millis(3_725_042),
+            "%H:%M:%S.%L",
+        )));
+
+        let element = crate::get(HELLO_ID);
+        assert_eq!(element.text_content(), Some("01:02:05.042".to_owned()));
+    }
+}
+
 proptest! {
     #[wasm_bindgen_test]
     fn render_int(x: u32) {
@@ -113,3 +173,420 @@ proptest! {
         crate::mount_test(Render(x));
     }
 }
+
+#[derive(Component)]
+struct OptionalChildren;
+
+impl Component for OptionalChildren {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .id(HELLO_ID)
+            .child_opt(true.then(|| e::span().text("a")))
+            .child_opt(false.then(|| e::span().text("b")))
+    }
+}
+
+#[wasm_bindgen_test]
+fn child_opt() {
+    crate::mount_test(OptionalChildren);
+
+    let element = crate::get(HELLO_ID);
+    assert_eq!(element.text_content(), Some("a".to_owned()));
+    assert_eq!(element.child_element_count(), 1);
+}
+
+#[derive(Component)]
+struct Fragments;
+
+impl Component for Fragments {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .id(HELLO_ID)
+            .child([e::span().text("a"), e::span().text("b")])
+            .child(vec!["c", "d"])
+    }
+}
+
+#[wasm_bindgen_test]
+fn render_array_and_vec() {
+    crate::mount_test(Fragments);
+
+    let element = crate::get(HELLO_ID);
+    assert_eq!(element.text_content(), Some("abcd".to_owned()));
+    assert_eq!(element.child_element_count(), 2);
+}
+
+#[derive(Component)]
+struct IterChildren;
+
+impl Component for IterChildren {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .id(HELLO_ID)
+            .child("a")
+            .children((1..=3).map(|x| e::span().text(x)))
+            .child("b")
+            .children(["c", "d"])
+    }
+}
+
+#[wasm_bindgen_test]
+fn children_keeps_order() {
+    crate::mount_test(IterChildren);
+
+    let element = crate::get(HELLO_ID);
+    assert_eq!(element.text_content(), Some("a123bcd".to_owned()));
+    assert_eq!(element.child_element_count(), 3);
+}
+
+#[derive(Component)]
+struct BoxedChildren;
+
+impl Component for BoxedChildren {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        let children: Vec<Box<dyn Element<Self>>> =
+            vec![e::span().text("a").boxed(), "b".boxed(), 10_u8.boxed()];
+        e::div().id(HELLO_ID).child(children)
+    }
+}
+
+#[wasm_bindgen_test]
+fn render_boxed_vec() {
+    crate::mount_test(BoxedChildren);
+
+    let element = crate::get(HELLO_ID);
+    assert_eq!(element.text_content(), Some("ab10".to_owned()));
+    assert_eq!(element.child_element_count(), 1);
+}
+
+#[derive(Component)]
+struct InnerText {
+    value: u8,
+}
+
+impl Component for InnerText {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(HELLO_ID)
+            .inner_text(|ctx: R<Self>| *ctx.value)
+            .on::<events::Click>(|ctx: E<Self>, _| *ctx.value += 1)
+    }
+}
+
+#[wasm_bindgen_test]
+fn inner_text() {
+    crate::mount_test(InnerText { value: 0 });
+
+    let element = crate::get(HELLO_ID);
+    assert_eq!(element.text_content(), Some("0".to_owned()));
+    assert_eq!(element.first_child(), element.last_child());
+
+    element.click();
+    assert_eq!(element.text_content(), Some("1".to_owned()));
+    assert_eq!(element.first_child(), element.last_child());
+}
+
+struct Version(u8);
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "v{}.0", self.0)
+    }
+}
+
+#[derive(Component)]
+struct DisplayedText {
+    value: u8,
+}
+
+impl Component for DisplayedText {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(HELLO_ID)
+            .text_display(|ctx: R<Self>| Version(*ctx.value))
+            .on::<events::Click>(|ctx: E<Self>, _| *ctx.value += 1)
+    }
+}
+
+#[wasm_bindgen_test]
+fn text_display() {
+    crate::mount_test(DisplayedText { value: 1 });
+
+    let element = crate::get(HELLO_ID);
+    assert_eq!(element.text_content(), Some("v1.0".to_owned()));
+
+    element.click();
+    assert_eq!(element.text_content(), Some("v2.0".to_owned()));
+    assert_eq!(element.first_child(), element.last_child());
+}
+
+#[derive(Component)]
+struct RootNode {
+    tag: Option<String>,
+}
+
+impl Component for RootNode {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(HELLO_ID)
+            .text(|ctx: R<Self>| ctx.tag.clone())
+            .on::<events::Click>(|ctx: E<Self>, _| {
+                *ctx.tag = ctx.root_node().map(|node| node.tag_name());
+            })
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        assert!(ctx.root_node().is_none());
+    }
+}
+
+#[wasm_bindgen_test]
+fn root_node() {
+    crate::mount_test(RootNode { tag: None });
+
+    let element = crate::get(HELLO_ID);
+    element.click();
+    assert_eq!(element.text_content(), Some("BUTTON".to_owned()));
+}
+
+const TEMPLATE_ID: &str = "__TEMPLATE";
+
+#[derive(Component)]
+struct Template;
+
+impl Component for Template {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .id(HELLO_ID)
+            .child(e::template(e::span().text("hello")).id(TEMPLATE_ID))
+    }
+}
+
+#[wasm_bindgen_test]
+fn template_clone() {
+    crate::mount_test(Template);
+
+    let element = crate::get(HELLO_ID);
+    assert_eq!(element.text_content(), Some(String::new()));
+
+    let clone = e::clone_content(TEMPLATE_ID).expect("Template not found");
+    assert_eq!(clone.text_content(), Some("hello".to_owned()));
+
+    element
+        .append_child(&clone)
+        .expect("Failed to append clone");
+    assert_eq!(element.text_content(), Some("hello".to_owned()));
+}
+
+const CLASSES_ID: &str = "__CLASSES";
+const MERGED_ID: &str = "__MERGED";
+const DUPLICATE_ID: &str = "__DUPLICATE";
+const MERGED_DUPLICATE_ID: &str = "__MERGED_DUPLICATE";
+
+#[derive(Component)]
+struct Classes;
+
+impl Component for Classes {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(e::div().id(CLASSES_ID).class("a").classes(["b", "c"]))
+            .child(e::div().id(MERGED_ID).attr("class", "a").class("b"))
+            .child(
+                e::div()
+                    .id(DUPLICATE_ID)
+                    .class("a b")
+                    .class("b")
+                    .classes(["c", " a  c "]),
+            )
+            .child(
+                e::div()
+                    .id(MERGED_DUPLICATE_ID)
+                    .attr("class", "a")
+                    .class("a b")
+                    .class("b"),
+            )
+            .id(HELLO_ID)
+    }
+}
+
+#[wasm_bindgen_test]
+fn classes() {
+    crate::mount_test(Classes);
+
+    let element = crate::get(CLASSES_ID);
+    assert_eq!(element.class_name(), "a b c");
+
+    let element = crate::get(MERGED_ID);
+    assert_eq!(element.class_name(), "a b");
+
+    let element = crate::get(DUPLICATE_ID);
+    assert_eq!(element.class_name(), "a b c");
+
+    let element = crate::get(MERGED_DUPLICATE_ID);
+    assert_eq!(element.class_name(), "a b");
+
+    let element = crate::get(HELLO_ID);
+    assert_eq!(element.get_attribute("class"), None);
+}
+
+const STATIC_ID: &str = "__STATIC";
+const MIXED_ID: &str = "__MIXED";
+
+#[derive(Component)]
+struct StaticText {
+    value: u8,
+}
+
+impl Component for StaticText {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(
+                e::div()
+                    .id(STATIC_ID)
+                    .text("Hello")
+                    .text(String::from(" World")),
+            )
+            .child(
+                e::div()
+                    .id(MIXED_ID)
+                    .text("Value: ")
+                    .text(|ctx: R<Self>| *ctx.value)
+                    .on::<events::Click>(|ctx: E<Self>, _| *ctx.value += 1),
+            )
+    }
+}
+
+#[wasm_bindgen_test]
+fn static_text_children() {
+    crate::mount_test(StaticText { value: 0 });
+
+    let element = crate::get(STATIC_ID);
+    assert_eq!(element.text_content(), Some("Hello World".to_owned()));
+    assert_eq!(element.child_nodes().length(), 1);
+
+    let element = crate::get(MIXED_ID);
+    assert_eq!(element.text_content(), Some("Value: 0".to_owned()));
+    element.click();
+    assert_eq!(element.text_content(), Some("Value: 1".to_owned()));
+}
+
+#[derive(Component)]
+struct SvgIcon;
+
+impl Component for SvgIcon {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .id(HELLO_ID)
+            .child(e::svg::icon("#icon-home").class("icon").width(24))
+    }
+}
+
+#[wasm_bindgen_test]
+fn svg_icon_uses_svg_namespace() {
+    crate::mount_test(SvgIcon);
+
+    let svg = crate::get(HELLO_ID).first_element_child().unwrap();
+    assert_eq!(svg.tag_name(), "svg");
+    assert_eq!(svg.namespace_uri(), Some(e::svg::NAMESPACE.to_owned()));
+    assert_eq!(svg.get_attribute("class"), Some("icon".to_owned()));
+    assert_eq!(svg.get_attribute("width"), Some("24".to_owned()));
+
+    let use_ = svg.first_element_child().unwrap();
+    assert_eq!(use_.tag_name(), "use");
+    assert_eq!(use_.namespace_uri(), Some(e::svg::NAMESPACE.to_owned()));
+    assert_eq!(use_.get_attribute("href"), Some("#icon-home".to_owned()));
+}
+
+const FALLBACK_ID: &str = "__FALLBACK";
+const PARENT_ID: &str = "__PARENT";
+
+#[derive(Component)]
+struct WithFallback;
+
+impl Component for WithFallback {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div().id(HELLO_ID).text("rendered")
+    }
+
+    fn render_fallback() -> impl Element<Self> {
+        e::div().id(FALLBACK_ID).text("fallback")
+    }
+}
+
+#[wasm_bindgen_test]
+fn fallback_not_rendered_on_success() {
+    crate::mount_test(WithFallback);
+
+    let element = crate::get(HELLO_ID);
+    assert_eq!(element.text_content(), Some("rendered".to_owned()));
+    let document = web_sys::window().unwrap().document().unwrap();
+    assert!(document.get_element_by_id(FALLBACK_ID).is_none());
+}
+
+#[derive(Component)]
+struct FailingRender;
+
+impl Component for FailingRender {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .id(HELLO_ID)
+            .child(e::HtmlElement::<Self, ()>::new("not a tag"))
+    }
+
+    fn render_fallback() -> impl Element<Self> {
+        e::div().id(FALLBACK_ID).text("fallback")
+    }
+}
+
+#[derive(Component)]
+struct FailingParent;
+
+impl Component for FailingParent {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div().id(PARENT_ID).child(C::new(FailingRender))
+    }
+
+    fn render_fallback() -> impl Element<Self> {
+        e::div().text("parent fallback")
+    }
+}
+
+#[wasm_bindgen_test]
+fn fallback_rendered_only_for_failing_component() {
+    crate::mount_test(FailingParent);
+
+    let fallback = crate::get(FALLBACK_ID);
+    assert_eq!(fallback.text_content(), Some("fallback".to_owned()));
+    let parent = crate::get(PARENT_ID);
+    assert!(parent.contains(Some(&fallback)));
+    let document = web_sys::window().unwrap().document().unwrap();
+    assert!(document.get_element_by_id(HELLO_ID).is_none());
+}
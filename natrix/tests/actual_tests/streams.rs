@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded};
+use natrix::async_utils;
+use natrix::prelude::*;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const ROOT: &str = "__ROOT";
+
+thread_local! {
+    static CHANNEL: std::cell::RefCell<Option<UnboundedReceiver<u8>>> = const { std::cell::RefCell::new(None) };
+}
+
+fn setup_channel() -> UnboundedSender<u8> {
+    let (tx, rx) = unbounded();
+    CHANNEL.with(|channel| *channel.borrow_mut() = Some(rx));
+    tx
+}
+
+#[derive(Component)]
+struct Streaming;
+
+impl Component for Streaming {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        let rx = CHANNEL
+            .with(|channel| channel.borrow_mut().take())
+            .expect("Channel not setup");
+        e::div()
+            .id(ROOT)
+            .child(e::stream(rx, |value| e::span().text(value)))
+    }
+}
+
+#[wasm_bindgen_test]
+async fn renders_items() {
+    let tx = setup_channel();
+    crate::mount_test(Streaming);
+
+    let root = crate::get(ROOT);
+    assert_eq!(root.text_content(), Some(String::new()));
+
+    tx.unbounded_send(1).unwrap();
+    async_utils::sleep(Duration::from_millis(10)).await;
+    assert_eq!(root.text_content(), Some("1".to_owned()));
+
+    tx.unbounded_send(2).unwrap();
+    async_utils::sleep(Duration::from_millis(10)).await;
+    assert_eq!(root.text_content(), Some("2".to_owned()));
+}
+
+#[wasm_bindgen_test]
+async fn keeps_last_item() {
+    let tx = setup_channel();
+    crate::mount_test(Streaming);
+
+    let root = crate::get(ROOT);
+
+    tx.unbounded_send(5).unwrap();
+    drop(tx);
+    async_utils::sleep(Duration::from_millis(10)).await;
+    assert_eq!(root.text_content(), Some("5".to_owned()));
+}
@@ -1,6 +1,10 @@
 #![allow(dead_code)]
 
+use std::cell::Cell;
+use std::rc::Rc;
+
 use natrix::prelude::*;
+use natrix::state::State;
 use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
 
 wasm_bindgen_test_configure!(run_in_browser);
@@ -134,3 +138,382 @@ async fn parent_to_child() {
     assert_eq!(button.text_content(), Some("30".to_owned()));
     assert_eq!(double.text_content(), Some("60".to_owned()));
 }
+
+const SYNC_BUTTON_ID: &str = "SYNC_BUTTON";
+const SYNC_TOTAL_ID: &str = "SYNC_TOTAL";
+
+#[derive(Component)]
+struct SyncChild;
+
+impl Component for SyncChild {
+    type EmitMessage = u8;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(SYNC_BUTTON_ID)
+            .on::<events::Click>(|ctx: E<Self>, _| {
+                ctx.emit_sync(5);
+            })
+    }
+}
+
+#[derive(Component)]
+struct SyncParent {
+    total: u8,
+}
+
+impl Component for SyncParent {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(C::new(SyncChild).on(|ctx: E<Self>, amount| {
+                *ctx.total += amount;
+            }))
+            .child(e::div().id(SYNC_TOTAL_ID).text(|ctx: R<Self>| *ctx.total))
+    }
+}
+
+#[wasm_bindgen_test]
+fn emit_sync_delivers_immediately() {
+    crate::mount_test(SyncParent { total: 0 });
+
+    let button = crate::get(SYNC_BUTTON_ID);
+    let total = crate::get(SYNC_TOTAL_ID);
+
+    assert_eq!(total.text_content(), Some("0".to_owned()));
+
+    button.click();
+    assert_eq!(total.text_content(), Some("5".to_owned()));
+
+    button.click();
+    assert_eq!(total.text_content(), Some("10".to_owned()));
+}
+
+const SHARED_FIRST_ID: &str = "SHARED_FIRST";
+const SHARED_SECOND_ID: &str = "SHARED_SECOND";
+
+#[derive(Component)]
+struct SharedCounter {
+    #[natrix(skip)]
+    id: &'static str,
+    #[natrix(skip)]
+    count: natrix::shared::SharedSignal<u8>,
+}
+
+impl Component for SharedCounter {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .attr("id", |ctx: R<Self>| ctx.id)
+            .text(|ctx: R<Self>| ctx.count.get(ctx))
+            .on::<events::Click>(|ctx: E<Self>, _| ctx.count.update(|count| *count += 1))
+    }
+}
+
+#[derive(Component)]
+struct SharedParent {
+    #[natrix(skip)]
+    count: natrix::shared::SharedSignal<u8>,
+}
+
+impl Component for SharedParent {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div().child(|ctx: R<Self>| {
+            e::div()
+                .child(C::new(SharedCounter {
+                    id: SHARED_FIRST_ID,
+                    count: ctx.count.clone(),
+                }))
+                .child(C::new(SharedCounter {
+                    id: SHARED_SECOND_ID,
+                    count: ctx.count.clone(),
+                }))
+        })
+    }
+}
+
+#[wasm_bindgen_test]
+async fn shared_signal_between_siblings() {
+    use natrix::async_utils;
+    crate::mount_test(SharedParent {
+        count: natrix::shared::SharedSignal::new(0),
+    });
+
+    let first = crate::get(SHARED_FIRST_ID);
+    let second = crate::get(SHARED_SECOND_ID);
+    assert_eq!(first.text_content(), Some("0".to_owned()));
+    assert_eq!(second.text_content(), Some("0".to_owned()));
+
+    first.click();
+    async_utils::next_animation_frame().await;
+    assert_eq!(first.text_content(), Some("1".to_owned()));
+    assert_eq!(second.text_content(), Some("1".to_owned()));
+
+    second.click();
+    second.click();
+    async_utils::next_animation_frame().await;
+    assert_eq!(first.text_content(), Some("3".to_owned()));
+    assert_eq!(second.text_content(), Some("3".to_owned()));
+}
+
+const QUERY_FIRST_ID: &str = "QUERY_FIRST";
+const QUERY_SECOND_ID: &str = "QUERY_SECOND";
+
+#[derive(Component)]
+struct QueryChild {
+    #[natrix(skip)]
+    id: &'static str,
+    found: Option<String>,
+}
+
+impl Component for QueryChild {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div().child(|ctx: R<Self>| {
+            e::button()
+                .id(ctx.id)
+                .class("target")
+                .text(|ctx: R<Self>| ctx.found.clone())
+                .on::<events::Click>(|ctx: E<Self>, _| {
+                    *ctx.found = ctx.query_selector(".target").map(|element| element.id());
+                })
+        })
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        assert!(ctx.query_selector(".target").is_none());
+    }
+}
+
+#[derive(Component)]
+struct QueryParent;
+
+impl Component for QueryParent {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(C::new(QueryChild {
+                id: QUERY_FIRST_ID,
+                found: None,
+            }))
+            .child(C::new(QueryChild {
+                id: QUERY_SECOND_ID,
+                found: None,
+            }))
+    }
+}
+
+#[wasm_bindgen_test]
+fn query_selector_scoped_to_component() {
+    crate::mount_test(QueryParent);
+
+    let first = crate::get(QUERY_FIRST_ID);
+    let second = crate::get(QUERY_SECOND_ID);
+
+    second.click();
+    assert_eq!(second.text_content(), Some(QUERY_SECOND_ID.to_owned()));
+
+    first.click();
+    assert_eq!(first.text_content(), Some(QUERY_FIRST_ID.to_owned()));
+}
+
+const RENAME_ID: &str = "RENAME_ID";
+const CLOSE_PAGE_ID: &str = "CLOSE_PAGE_ID";
+
+#[derive(Component)]
+struct TitledPage {
+    name: &'static str,
+}
+
+impl Component for TitledPage {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::button()
+            .id(RENAME_ID)
+            .on::<events::Click>(|ctx: E<Self>, _| {
+                *ctx.name = "Settings";
+            })
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        ctx.set_title_part(|ctx| ctx.name.to_string());
+    }
+}
+
+#[derive(Component)]
+struct TitledApp {
+    show_page: bool,
+}
+
+impl Component for TitledApp {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child_if(
+                |ctx: &State<Self>| *ctx.show_page,
+                || C::new(TitledPage { name: "Profile" }),
+            )
+            .child(
+                e::button()
+                    .id(CLOSE_PAGE_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        *ctx.show_page = false;
+                    }),
+            )
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        ctx.set_title_template("%s | MyApp");
+    }
+}
+
+#[wasm_bindgen_test]
+fn title_template_composes_nested_part() {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let original = document.title();
+
+    let handle = crate::mount_for_test(TitledApp { show_page: true });
+    assert_eq!(document.title(), "Profile | MyApp");
+
+    crate::get(RENAME_ID).click();
+    assert_eq!(document.title(), "Settings | MyApp");
+
+    crate::get(CLOSE_PAGE_ID).click();
+    assert_eq!(document.title(), original);
+
+    drop(handle);
+    assert_eq!(document.title(), original);
+}
+
+const CONTEXT_ID: &str = "CONTEXT_ID";
+
+struct AppConfig {
+    name: &'static str,
+}
+
+#[derive(Component)]
+struct ContextChild;
+
+impl Component for ContextChild {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div().id(CONTEXT_ID).child(|ctx: R<Self>| {
+            ctx.context::<AppConfig>()
+                .map_or("missing", |config| config.name)
+        })
+    }
+}
+
+#[derive(Component)]
+struct ContextParent;
+
+impl Component for ContextParent {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div().child(C::new(ContextChild))
+    }
+}
+
+#[wasm_bindgen_test]
+fn context_reaches_sub_components() {
+    crate::mount_test_with_context(ContextParent, AppConfig { name: "natrix" });
+
+    assert_eq!(
+        crate::get(CONTEXT_ID).text_content(),
+        Some("natrix".to_owned())
+    );
+}
+
+const TOGGLE_CHILD_ID: &str = "TOGGLE_CHILD_ID";
+
+#[derive(Default)]
+struct LifecycleLog {
+    mounts: Cell<u8>,
+    unmounts: Cell<u8>,
+}
+
+#[derive(Component)]
+struct LifecycleChild {
+    #[natrix(skip)]
+    log: Rc<LifecycleLog>,
+}
+
+impl Component for LifecycleChild {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::span().text("child")
+    }
+
+    fn on_mount(ctx: E<Self>) {
+        ctx.log.mounts.set(ctx.log.mounts.get() + 1);
+    }
+
+    fn on_unmount(ctx: E<Self>) {
+        ctx.log.unmounts.set(ctx.log.unmounts.get() + 1);
+    }
+}
+
+#[derive(Component)]
+struct LifecycleParent {
+    show: bool,
+    #[natrix(skip)]
+    log: Rc<LifecycleLog>,
+}
+
+impl Component for LifecycleParent {
+    type EmitMessage = NoMessages;
+    type ReceiveMessage = NoMessages;
+    fn render() -> impl Element<Self> {
+        e::div()
+            .child(|ctx: R<Self>| {
+                if *ctx.show {
+                    let log = Rc::clone(&ctx.log);
+                    Some(C::new(LifecycleChild { log }))
+                } else {
+                    None
+                }
+            })
+            .child(
+                e::button()
+                    .id(TOGGLE_CHILD_ID)
+                    .on::<events::Click>(|ctx: E<Self>, _| {
+                        *ctx.show = !*ctx.show;
+                    }),
+            )
+    }
+}
+
+#[wasm_bindgen_test]
+fn child_lifecycle_follows_conditional_branch() {
+    let log = Rc::new(LifecycleLog::default());
+    crate::mount_test(LifecycleParent {
+        show: true,
+        log: Rc::clone(&log),
+    });
+    assert_eq!((log.mounts.get(), log.unmounts.get()), (1, 0));
+
+    let button = crate::get(TOGGLE_CHILD_ID);
+    button.click();
+    assert_eq!((log.mounts.get(), log.unmounts.get()), (1, 1));
+    // Only the test and the parent hold the log, so the child state was dropped
+    assert_eq!(Rc::strong_count(&log), 2);
+
+    button.click();
+    assert_eq!((log.mounts.get(), log.unmounts.get()), (2, 1));
+    assert_eq!(Rc::strong_count(&log), 3);
+
+    button.click();
+    assert_eq!((log.mounts.get(), log.unmounts.get()), (2, 2));
+    assert_eq!(Rc::strong_count(&log), 2);
+}
@@ -0,0 +1,55 @@
+//! Javascript shims for natrix that cant be expressed with `web-sys`.
+//!
+//! These live in their own crate as `#[wasm_bindgen]` imports allow `clippy::restriction` on the
+//! generated code, which is incompatible with the lints `natrix` forbids.
+
+#![forbid(unsafe_code)]
+#![deny(
+    clippy::todo,
+    clippy::unreachable,
+    clippy::unwrap_used,
+    clippy::indexing_slicing,
+    clippy::dbg_macro,
+    clippy::expect_used,
+    clippy::allow_attributes,
+    clippy::allow_attributes_without_reason,
+    clippy::arithmetic_side_effects
+)]
+#![warn(
+    missing_docs,
+    clippy::missing_docs_in_private_items,
+    clippy::pedantic,
+    unfulfilled_lint_expectations
+)]
+
+use wasm_bindgen::JsValue;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen(inline_js = r"
+export function define_natrix_element(name, observed, connect, disconnect, changed) {
+    customElements.define(name, class extends HTMLElement {
+        static get observedAttributes() { return observed; }
+        connectedCallback() { connect(this); }
+        disconnectedCallback() { disconnect(this); }
+        attributeChangedCallback(attribute, _old, value) { changed(this, attribute, value); }
+    });
+}
+")]
+extern "C" {
+    /// Define a custom element named `name` calling the given callbacks.
+    ///
+    /// `observed` is the array of attribute names passed to `changed`, which is called with the
+    /// element, the attribute name and the new value. `connect` and `disconnect` are called with
+    /// the element.
+    ///
+    /// # Errors
+    /// The exception thrown by `customElements.define`.
+    #[wasm_bindgen(catch, js_name = define_natrix_element)]
+    pub fn define_custom_element(
+        name: &str,
+        observed: JsValue,
+        connect: JsValue,
+        disconnect: JsValue,
+        changed: JsValue,
+    ) -> Result<(), JsValue>;
+}
@@ -4,7 +4,6 @@
     clippy::todo,
     clippy::unreachable,
     clippy::unwrap_used,
-    clippy::unreachable,
     clippy::indexing_slicing
 )]
 #![deny(
@@ -23,8 +22,12 @@
 
 extern crate proc_macro;
 
+#[cfg(feature = "inline_css")]
+mod css_properties;
+
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::{fs, io};
 
 use proc_macro2::TokenStream;
@@ -44,7 +47,32 @@ use template_quote::{ToTokens, quote};
 ///     }
 /// }
 /// ```
-#[proc_macro_derive(Component)]
+///
+/// Fields marked `#[nested]` have their own fields tracked individually, the type of the field
+/// also needs `#[derive(Component)]` (but does not need to implement `Component`).
+///
+/// Fields marked `#[natrix(skip)]` are stored as plain values, reading or writing them will never
+/// trigger reactive updates.
+///
+/// Fields marked `#[natrix(eq)]` get a `set_<field>` method on the reactive data, which only marks
+/// the field as changed if the new value is different (see `Signal::set_if_changed`).
+///
+/// Structs marked `#[natrix(builder)]` get a `builder()` function, which starts from the `Default`
+/// value of the struct and has a setter for every field. So only the fields that differ from the
+/// default have to be set.
+///
+/// Multiple flags can be given as a comma separated list, and unknown flags are a compile error.
+/// ```ignore
+/// #[derive(Component, Default)]
+/// #[natrix(builder)]
+/// struct Counter {
+///     value: u8,
+///     step: u8,
+/// }
+///
+/// mount(Counter::builder().step(5).build());
+/// ```
+#[proc_macro_derive(Component, attributes(nested, natrix))]
 pub fn component_derive(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let item = syn::parse_macro_input!(item as ItemStruct);
     let result = component_derive_implementation(item);
@@ -54,11 +82,19 @@ pub fn component_derive(item: proc_macro::TokenStream) -> proc_macro::TokenStrea
 /// Actual implementation of the macro, split out to make dealing with the different `TokenStream`
 /// types easier
 fn component_derive_implementation(item: ItemStruct) -> TokenStream {
+    if let Err(err) = check_natrix_flags(&item) {
+        return err.into_compile_error();
+    }
     let name = item.ident.clone();
     let vis = item.vis;
+    let builder = has_natrix_flag(&item.attrs, "builder");
     let (fields, is_named) = get_fields(item.fields);
+    if let Err(err) = validate_flags(builder, is_named, &fields) {
+        return quote!(compile_error!(#err););
+    }
+    let signal_fields: Vec<&Field> = fields.iter().filter(|field| !field.skip).collect();
 
-    let field_count = proc_macro2::Literal::usize_unsuffixed(fields.len());
+    let field_count = proc_macro2::Literal::usize_unsuffixed(signal_fields.len());
     let data_name = format_ident!("_{name}Data");
     let signal_state_name = format_ident!("_{name}SignalState");
 
@@ -69,30 +105,7 @@ fn component_derive_implementation(item: ItemStruct) -> TokenStream {
     let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
 
     quote! {
-        #[doc(hidden)]
-        #(if is_named) {
-            #vis struct #data_name #generics {
-                #(for field in &fields) {
-                    #{field.access.clone()}: ::natrix::macro_ref::Signal<#{field.type_.clone()}>,
-                }
-            }
-            #vis struct #signal_state_name {
-                #(for field in &fields) {
-                    #{field.access.clone()}: ::natrix::macro_ref::SignalState,
-                }
-            }
-        } #(else) {
-            #vis struct #data_name #generics (
-                #(for field in &fields) {
-                    ::natrix::macro_ref::Signal<#{field.type_.clone()}>,
-                }
-            );
-            #vis struct #signal_state_name (
-                #(for _ in &fields) {
-                    ::natrix::macro_ref::SignalState,
-                }
-            );
-        }
+        #{data_structs(&vis, &data_name, &signal_state_name, &generics, &fields, is_named)}
 
         #[automatically_derived]
         impl #impl_generics ::natrix::macro_ref::ComponentData for #data_name #type_generics #where_clause {
@@ -101,54 +114,189 @@ fn component_derive_implementation(item: ItemStruct) -> TokenStream {
 
             fn signals_mut(&mut self) -> Self::FieldRef<'_> {
                 [
-                    #(for field in &fields) {
+                    #(for field in &signal_fields) {
                         &mut self.#{field.access.clone()},
                     }
                 ]
             }
 
+            fn field_names() -> &'static [&'static str] {
+                &[
+                    #(for field in &signal_fields) {
+                        #{field.name()},
+                    }
+                ]
+            }
+
             fn pop_signals(&mut self) -> Self::SignalState {
                 #(if is_named) {
                     #signal_state_name {
-                        #(for field in &fields) {
-                            #{field.access.clone()}: self.#{field.access.clone()}.pop_state(),
+                        #(for field in &signal_fields) {
+                            #{field.access.clone()}: #{field.pop_state()},
                         }
                     }
                 } #(else) {
                     #signal_state_name (
-                        #(for field in &fields) {
-                            self.#{field.access.clone()}.pop_state(),
+                        #(for field in &signal_fields) {
+                            #{field.pop_state()},
                         }
                     )
                 }
             }
 
             fn set_signals(&mut self, state: Self::SignalState) {
-                #(for field in &fields) {
-                    self.#{field.access.clone()}.set_state(state.#{field.access.clone()});
+                #(for field in &signal_fields) {
+                    #{field.set_state()};
                 }
             }
         }
 
+        #{eq_setters(&data_name, &generics, &fields)}
+
         #[automatically_derived]
         impl #impl_generics ::natrix::macro_ref::ComponentBase for #name #type_generics #where_clause {
             type Data = #data_name #type_generics;
              fn into_data(self) -> Self::Data {
                 #(if is_named) {
                     #data_name {
-                        #(for field in fields) {
-                            #{field.access.clone()}: ::natrix::macro_ref::Signal::new(self.#{field.access}),
+                        #(for field in &fields) {
+                            #{field.access.clone()}: #{field.convert_to_data()},
                         }
                     }
                 } #(else) {
                     #data_name(
-                        #(for field in fields) {
-                            ::natrix::macro_ref::Signal::new(self.#{field.access}),
+                        #(for field in &fields) {
+                            #{field.convert_to_data()},
                         }
                     )
                 }
             }
         }
+
+        #(if builder) {
+            #{builder_implementation(&name, &vis, &generics, &fields)}
+        }
+    }
+}
+
+/// Generate the declarations of the data and signal state structs
+fn data_structs(
+    vis: &syn::Visibility,
+    data_name: &syn::Ident,
+    signal_state_name: &syn::Ident,
+    generics: &syn::Generics,
+    fields: &[Field],
+    is_named: bool,
+) -> TokenStream {
+    let signal_fields: Vec<&Field> = fields.iter().filter(|field| !field.skip).collect();
+
+    quote! {
+    #[doc(hidden)]
+    #(if is_named) {
+        #vis struct #data_name #generics {
+            #(for field in fields) {
+                #{field.access.clone()}: #{field.data_type()},
+            }
+        }
+        #vis struct #signal_state_name {
+            #(for field in &signal_fields) {
+                #{field.access.clone()}: #{field.state_type()},
+            }
+        }
+    } #(else) {
+        #vis struct #data_name #generics (
+            #(for field in fields) {
+                #{field.data_type()},
+            }
+        );
+        #vis struct #signal_state_name (
+            #(for field in &signal_fields) {
+                #{field.state_type()},
+            }
+        );
+    }
+    }
+}
+
+/// Check the `#[natrix(...)]` flags are only used where they are supported
+fn validate_flags(builder: bool, is_named: bool, fields: &[Field]) -> Result<(), &'static str> {
+    if builder && !is_named {
+        return Err("`#[natrix(builder)]` is only supported on structs with named fields");
+    }
+    if fields
+        .iter()
+        .any(|field| field.eq && (field.skip || field.nested))
+    {
+        return Err("`#[natrix(eq)]` is not supported on skipped or nested fields");
+    }
+    Ok(())
+}
+
+/// Generate the change checking setters of the `#[natrix(eq)]` fields
+fn eq_setters(data_name: &syn::Ident, generics: &syn::Generics, fields: &[Field]) -> TokenStream {
+    let eq_fields: Vec<&Field> = fields.iter().filter(|field| field.eq).collect();
+    if eq_fields.is_empty() {
+        return TokenStream::new();
+    }
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #data_name #type_generics #where_clause {
+            #(for field in &eq_fields) {
+                #[doc = #{format!("Set the `{}` field, only marking it as changed if the value is different", field.name())}]
+                #{&field.vis} fn #{field.setter()}(&mut self, value: #{&field.type_}) {
+                    self.#{&field.access}.set_if_changed(value);
+                }
+            }
+        }
+    }
+}
+
+/// Generate the `#[natrix(builder)]` builder struct and its setters
+fn builder_implementation(
+    name: &syn::Ident,
+    vis: &syn::Visibility,
+    generics: &syn::Generics,
+    fields: &[Field],
+) -> TokenStream {
+    let builder_name = format_ident!("{name}Builder");
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[doc = #{format!("Builder for [`{name}`], starting from its `Default` value")}]
+        #[must_use = "Call `.build()` to get the component"]
+        #vis struct #builder_name #generics (#name #type_generics) #where_clause;
+
+        #[automatically_derived]
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Start building this component from its `Default` value, only setting the
+            /// fields that differ.
+            #vis fn builder() -> #builder_name #type_generics
+            where
+                Self: ::std::default::Default,
+            {
+                #builder_name(::std::default::Default::default())
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #builder_name #type_generics #where_clause {
+            #(for field in fields) {
+                #[doc = #{format!("Set the `{}` field", field.name())}]
+                #[must_use]
+                #{&field.vis} fn #{&field.access}(mut self, value: #{&field.type_}) -> Self {
+                    self.0.#{&field.access} = value;
+                    self
+                }
+            }
+
+            /// Finish building the component
+            #[must_use]
+            #vis fn build(self) -> #name #type_generics {
+                self.0
+            }
+        }
     }
 }
 
@@ -161,42 +309,256 @@ fn get_fields(fields: syn::Fields) -> (Vec<Field>, bool) {
             fields
                 .named
                 .into_iter()
-                .map(|field| Field {
-                    type_: field.ty.into_token_stream(),
-                    access: field.ident.into_token_stream(),
+                .map(|field| {
+                    let access = field.ident.into_token_stream();
+                    Field {
+                        vis: field.vis,
+                        nested: is_nested(&field.attrs),
+                        skip: is_skipped(&field.attrs),
+                        eq: has_natrix_flag(&field.attrs, "eq"),
+                        type_: field.ty.into_token_stream(),
+                        state_access: access.clone(),
+                        access,
+                    }
                 })
                 .collect(),
             true,
         ),
-        syn::Fields::Unnamed(fields) => (
-            fields
-                .unnamed
-                .into_iter()
-                .enumerate()
-                .map(|(index, field)| Field {
-                    type_: field.ty.to_token_stream(),
-                    access: proc_macro2::Literal::usize_unsuffixed(index).to_token_stream(),
-                })
-                .collect(),
-            false,
-        ),
+        syn::Fields::Unnamed(fields) => {
+            // The signal state struct does not contain skipped fields, so its indexes are offset
+            let mut state_index = 0_usize;
+            (
+                fields
+                    .unnamed
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, field)| {
+                        let skip = is_skipped(&field.attrs);
+                        let state_access =
+                            proc_macro2::Literal::usize_unsuffixed(state_index).to_token_stream();
+                        if !skip {
+                            state_index = state_index.saturating_add(1);
+                        }
+                        Field {
+                            vis: field.vis,
+                            nested: is_nested(&field.attrs),
+                            skip,
+                            eq: has_natrix_flag(&field.attrs, "eq"),
+                            type_: field.ty.to_token_stream(),
+                            access: proc_macro2::Literal::usize_unsuffixed(index).to_token_stream(),
+                            state_access,
+                        }
+                    })
+                    .collect(),
+                false,
+            )
+        }
     }
 }
 
 /// A abstract representation of a struct field
 struct Field {
+    /// The visibility of the field
+    vis: syn::Visibility,
+    /// Is this field marked `#[nested]`
+    nested: bool,
+    /// Is this field marked `#[natrix(skip)]`
+    skip: bool,
+    /// Is this field marked `#[natrix(eq)]`
+    eq: bool,
     /// The type of the field
     type_: TokenStream,
     /// How one would access the field (identifiers for named structs, a number for tuple)
     access: TokenStream,
+    /// How one would access the field in the signal state struct
+    state_access: TokenStream,
+}
+
+impl Field {
+    /// The name of the field as written by the user
+    fn name(&self) -> String {
+        self.access.to_string().trim_start_matches("r#").to_owned()
+    }
+
+    /// The name of the `#[natrix(eq)]` setter of this field
+    fn setter(&self) -> syn::Ident {
+        format_ident!("set_{}", self.name())
+    }
+
+    /// The type of this field in the reactive struct
+    fn data_type(&self) -> TokenStream {
+        let type_ = &self.type_;
+        if self.skip {
+            type_.clone()
+        } else if self.nested {
+            quote!(<#type_ as ::natrix::macro_ref::ComponentBase>::Data)
+        } else {
+            quote!(::natrix::macro_ref::Signal<#type_>)
+        }
+    }
+
+    /// The type of this field in the signal state struct
+    fn state_type(&self) -> TokenStream {
+        if self.nested {
+            let data_type = self.data_type();
+            quote!(<#data_type as ::natrix::macro_ref::ComponentData>::SignalState)
+        } else {
+            quote!(::natrix::macro_ref::SignalState)
+        }
+    }
+
+    /// Expression to pop the signal state of this field
+    fn pop_state(&self) -> TokenStream {
+        let access = &self.access;
+        if self.nested {
+            quote!(::natrix::macro_ref::ComponentData::pop_signals(&mut self.#access))
+        } else {
+            quote!(self.#access.pop_state())
+        }
+    }
+
+    /// Statement to restore the signal state of this field from `state`
+    fn set_state(&self) -> TokenStream {
+        let access = &self.access;
+        let state_access = &self.state_access;
+        if self.nested {
+            quote!(::natrix::macro_ref::ComponentData::set_signals(&mut self.#access, state.#state_access))
+        } else {
+            quote!(self.#access.set_state(state.#state_access))
+        }
+    }
+
+    /// Expression to convert the field on `self` to its reactive version
+    fn convert_to_data(&self) -> TokenStream {
+        let access = &self.access;
+        if self.skip {
+            quote!(self.#access)
+        } else if self.nested {
+            quote!(::natrix::macro_ref::ComponentBase::into_data(self.#access))
+        } else {
+            quote!(::natrix::macro_ref::Signal::new(self.#access))
+        }
+    }
+}
+
+/// Is the `#[nested]` attribute present
+fn is_nested(attributes: &[syn::Attribute]) -> bool {
+    attributes
+        .iter()
+        .any(|attribute| attribute.path().is_ident("nested"))
+}
+
+/// Is the `#[natrix(skip)]` attribute present
+fn is_skipped(attributes: &[syn::Attribute]) -> bool {
+    has_natrix_flag(attributes, "skip")
+}
+
+/// Is `flag` present in a `#[natrix(...)]` attribute
+fn has_natrix_flag(attributes: &[syn::Attribute], flag: &str) -> bool {
+    natrix_flags(attributes).is_ok_and(|flags| flags.iter().any(|ident| ident == flag))
+}
+
+/// The flags of all the `#[natrix(...)]` attributes, which take a comma separated list such as
+/// `#[natrix(skip, eq)]`
+fn natrix_flags(attributes: &[syn::Attribute]) -> syn::Result<Vec<syn::Ident>> {
+    let mut flags = Vec::new();
+    for attribute in attributes
+        .iter()
+        .filter(|attribute| attribute.path().is_ident("natrix"))
+    {
+        flags.extend(attribute.parse_args_with(
+            syn::punctuated::Punctuated::<syn::Ident, syn::Token![,]>::parse_terminated,
+        )?);
+    }
+    Ok(flags)
+}
+
+/// Check the struct and its fields only use known `#[natrix(...)]` flags
+fn check_natrix_flags(item: &ItemStruct) -> syn::Result<()> {
+    check_flags_allowed(&item.attrs, &["builder"])?;
+    for field in &item.fields {
+        check_flags_allowed(&field.attrs, &["skip", "eq"])?;
+    }
+    Ok(())
+}
+
+/// Error on the first `#[natrix(...)]` flag not in `allowed`
+fn check_flags_allowed(attributes: &[syn::Attribute], allowed: &[&str]) -> syn::Result<()> {
+    for flag in natrix_flags(attributes)? {
+        if !allowed.iter().any(|allowed| flag == allowed) {
+            let expected = allowed
+                .iter()
+                .map(|allowed| format!("`{allowed}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(syn::Error::new(
+                flag.span(),
+                format!("unknown natrix flag `{flag}`, expected one of {expected}"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Derive the `SelectOptions` trait for a enum, for use with `e::select_options`
+///
+/// ```ignore
+/// #[derive(SelectOptions)]
+/// enum Theme {
+///     Light,
+///     Dark,
+/// }
+/// ```
+///
+/// The value of each option is the name of the variant, only enums without fields are supported.
+#[proc_macro_derive(SelectOptions)]
+pub fn select_options_derive(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let item = syn::parse_macro_input!(item as syn::ItemEnum);
+    select_options_implementation(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Actual implementation of the `SelectOptions` derive
+fn select_options_implementation(item: &syn::ItemEnum) -> syn::Result<TokenStream> {
+    let name = &item.ident;
+    let (impl_generics, type_generics, where_clause) = item.generics.split_for_impl();
+
+    let mut variants = Vec::with_capacity(item.variants.len());
+    for variant in &item.variants {
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "`SelectOptions` can only be derived for variants without fields",
+            ));
+        }
+        variants.push(&variant.ident);
+    }
+    let values: Vec<String> = variants.iter().map(ToString::to_string).collect();
+
+    Ok(quote! {
+        impl #impl_generics ::natrix::html_elements::SelectOptions for #name #type_generics #where_clause {
+            const VARIANTS: &'static [Self] = &[#(Self::#variants),*];
+
+            fn value(&self) -> &'static str {
+                match self {
+                    #(Self::#variants => #values,)*
+                }
+            }
+
+            fn from_value(value: &str) -> ::std::option::Option<Self> {
+                match value {
+                    #(#values => ::std::option::Option::Some(Self::#variants),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+    })
 }
 
 /// If this is the first time a macro is used in this crate we should clear out the target folder
 static FIRST_USE_IN_CRATE: AtomicBool = AtomicBool::new(true);
 
-/// Counter to generate unique file names
-static FILE_COUNTER: AtomicU32 = AtomicU32::new(0);
-
 /// Register global css to be included in the final bundle.
 ///
 /// For most usecases prefer scoped css machinery.
@@ -205,11 +567,14 @@ pub fn global_css(css_input: proc_macro::TokenStream) -> proc_macro::TokenStream
     let css = syn::parse_macro_input!(css_input as syn::LitStr);
     let css = css.value();
 
-    emit_css(css).into()
+    emit_css(&css).into()
 }
 
 /// Emit the css to the target directory
-fn emit_css(css: String) -> TokenStream {
+///
+/// The file is named by the hash of the css, so identical stylesheets are only bundled once.
+fn emit_css(css: &str) -> TokenStream {
+    let css = minify_css(css);
     let first_use = FIRST_USE_IN_CRATE.fetch_and(false, Ordering::AcqRel);
 
     let caller_name =
@@ -226,20 +591,20 @@ fn emit_css(css: String) -> TokenStream {
         reason = "This should be valid because the natrix build tool should have made sure of that"
     )]
     {
-        if first_use {
-            if let Err(err) = std::fs::remove_dir_all(&output_directory) {
-                assert!(
-                    err.kind() == io::ErrorKind::NotFound,
-                    "Deleting folder failed {err}"
-                );
-            }
+        if first_use && let Err(err) = std::fs::remove_dir_all(&output_directory) {
+            assert!(
+                err.kind() == io::ErrorKind::NotFound,
+                "Deleting folder failed {err}"
+            );
         }
         std::fs::create_dir_all(&output_directory)
             .expect("Could not create target output directory for crate");
     }
 
-    let name = FILE_COUNTER.fetch_add(1, Ordering::AcqRel);
-    let output_file = output_directory.join(format!("{name}.css"));
+    let mut hasher = DefaultHasher::new();
+    css.hash(&mut hasher);
+    let name = hasher.finish();
+    let output_file = output_directory.join(format!("{name:016x}.css"));
 
     if let Err(err) = fs::write(output_file, css) {
         let err = err.to_string();
@@ -249,6 +614,83 @@ fn emit_css(css: String) -> TokenStream {
     }
 }
 
+/// Strip comments and collapse whitespace in the css.
+///
+/// String literals and escapes are kept as is, and whitespace is only removed entirely around
+/// `{`, `}`, `;` and `,` where it is never significant.
+fn minify_css(css: &str) -> String {
+    /// Characters whitespace can be removed around
+    const SEPARATORS: [char; 4] = ['{', '}', ';', ','];
+
+    /// Can the character be part of a identifier or number, which would merge with a neighbouring
+    /// one if the comment between them is removed
+    fn is_token_char(character: char) -> bool {
+        character.is_alphanumeric() || matches!(character, '-' | '_') || !character.is_ascii()
+    }
+
+    let mut output = String::with_capacity(css.len());
+    let mut pending_space = false;
+    let mut after_comment = false;
+    let mut chars = css.chars().peekable();
+
+    while let Some(character) = chars.next() {
+        if character.is_whitespace() {
+            pending_space = true;
+            continue;
+        }
+        if character == '/' && chars.peek() == Some(&'*') {
+            chars.next();
+            let mut previous = None;
+            for inner in chars.by_ref() {
+                if previous == Some('*') && inner == '/' {
+                    break;
+                }
+                previous = Some(inner);
+            }
+            after_comment = true;
+            continue;
+        }
+
+        if SEPARATORS.contains(&character) {
+            // The last declaration in a block does not need a semicolon
+            if character == '}' && output.ends_with(';') {
+                output.pop();
+            }
+        } else if pending_space && !output.is_empty() && !output.ends_with(SEPARATORS) {
+            output.push(' ');
+        } else if after_comment && is_token_char(character) && output.ends_with(is_token_char) {
+            // The comment separated two tokens, `0/**/auto` is not `0auto`
+            output.push(' ');
+        }
+        pending_space = false;
+        after_comment = false;
+        output.push(character);
+
+        match character {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    output.push(escaped);
+                }
+            }
+            '"' | '\'' => {
+                while let Some(inner) = chars.next() {
+                    output.push(inner);
+                    if inner == '\\' {
+                        if let Some(escaped) = chars.next() {
+                            output.push(escaped);
+                        }
+                    } else if inner == character {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    output
+}
+
 /// Create scoped css for a component.
 ///
 /// This generates a set of constants for every class and id in the css.
@@ -296,20 +738,207 @@ fn emit_css(css: String) -> TokenStream {
 /// e::div().class(css::HELLO);
 /// ```
 ///
+/// # Avoiding collisions
+/// The generated class names include a hash of the css, so two `scoped_css!` invocations using the
+/// same class name still get distinct classes.
+/// But the constants are named after the class, so two invocations in the same module would both
+/// define the same constant.
+/// Prefixing the css with `mod name` puts the constants in a module of that name instead.
+/// ```ignore
+/// scoped_css!(mod card "
+///     .title {
+///         font-size: 2em;
+///     }
+/// ");
+/// scoped_css!(mod dialog "
+///     .title {
+///         font-weight: bold;
+///     }
+/// ");
+///
+/// e::div()
+///     .child(e::h2().class(card::TITLE))
+///     .child(e::h2().class(dialog::TITLE))
+/// ```
+///
+/// # Referencing other classes
+/// A `scoped_css!` can take multiple stylesheets, each optionally prefixed with `mod name` and
+/// `@component`. A stylesheet can reference the classes of the stylesheets before it using
+/// `${CONST_NAME}`, which is replaced by a class selector for the generated class.
+/// ```ignore
+/// scoped_css!(
+///     mod button "
+///     .button {
+///         color: red;
+///     }
+///     "
+///     mod card "
+///     .card ${button::BUTTON} {
+///         color: blue;
+///     }
+///     "
+/// );
+/// ```
+/// Constants of a stylesheet without a module are referenced as `${CONST_NAME}`.
+/// Every macro invocation is expanded on its own, so classes of other `scoped_css!` invocations
+/// can not be referenced.
+/// The reference counts as a regular class selector for specificity, so `.card ${button::BUTTON}`
+/// overrides `.button`. Do not rely on the order of the stylesheets in the bundle, as each is
+/// emitted to its own file.
+///
+/// # Nesting
+/// Rules can be nested using `&` to refer to the parent selector, the same as in SCSS.
+/// Nested rules are expanded to flat selectors, and at-rules such as `@media` can be nested
+/// in a rule as well.
+/// ```ignore
+/// scoped_css!("
+///     .card {
+///         padding: 1em;
+///         &:hover {
+///             background: gray;
+///         }
+///         & .title {
+///             font-weight: bold;
+///         }
+///         @media (max-width: 600px) {
+///             padding: 0;
+///         }
+///     }
+/// ");
+/// ```
+/// This is emitted as `.card`, `.card:hover` and `.card .title` rules (using the generated names).
+///
+/// # Component scope
+/// Prefixing the css with `@component` (after the `mod name` if given) nests every rule under a unique class, exposed as the
+/// `NATRIX_SCOPE` constant, which is added to the root element of the component when set as its
+/// `STYLE_SCOPE`. This means the rules can not leak to elements of other components, even if they
+/// end up using the same classes.
+/// ```ignore
+/// scoped_css!(@component "
+///     .hello {
+///         color: red;
+///     }
+///     & {
+///         display: flex;
+///     }
+///     @unscoped {
+///         @keyframes spin {
+///             from { rotate: 0deg; }
+///             to { rotate: 360deg; }
+///         }
+///     }
+/// ");
+///
+/// impl Component for HelloWorld {
+///     const STYLE_SCOPE: Option<&'static str> = Some(NATRIX_SCOPE);
+///     // ...
+/// }
+/// ```
+/// Rules only match descendants of the root, use `&` to style the root itself.
+/// Rules in a `@unscoped { ... }` block are emitted without the prefix,
+/// this is also required for at-rules that can not be nested, such as `@keyframes`.
+///
+/// # Media queries
+/// Classes inside at-rules such as `@media` are rewritten to the same generated names as the top
+/// level rules, so theme specific styles can be written next to the default ones.
+/// ```ignore
+/// scoped_css!("
+///     .card {
+///         background: white;
+///     }
+///     @media (prefers-color-scheme: dark) {
+///         .card {
+///             background: black;
+///         }
+///     }
+/// ");
+/// ```
+///
 /// # Consistency
 /// The generated string literals are not guaranteed to be the same between builds.
 /// Their exact format is not covered by the public API and may change in the future.
 #[proc_macro]
-#[expect(
-    clippy::missing_panics_doc,
-    reason = "This can only panic if its not called from cargo"
-)]
 #[cfg(feature = "scoped_css")]
 pub fn scoped_css(css_input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(css_input as ScopedCssInput);
+
+    let mut known_classes = std::collections::HashMap::new();
+    let mut result = TokenStream::new();
+    for sheet in input.sheets {
+        match expand_scoped_sheet(sheet, &mut known_classes) {
+            Ok(tokens) => result.extend(tokens),
+            Err(err) => return quote!(compile_error!(#err)).into(),
+        }
+    }
+    result.into()
+}
+
+/// Expand a single stylesheet of a `scoped_css!`, adding its classes to `known_classes` for the
+/// following stylesheets to reference.
+#[cfg(feature = "scoped_css")]
+fn expand_scoped_sheet(
+    sheet: ScopedCssSheet,
+    known_classes: &mut std::collections::HashMap<String, String>,
+) -> Result<TokenStream, String> {
     use convert_case::{Case, Casing};
 
-    let css = syn::parse_macro_input!(css_input as syn::LitStr);
-    let css = css.value();
+    let css = resolve_class_references(&sheet.css.value(), known_classes)?;
+    let (code, classes) = compile_scoped_css(&css, sheet.component)?;
+
+    let mut consts = Vec::with_capacity(classes.len());
+    for (name, new_name) in classes {
+        let const_name = name.to_case(Case::Constant);
+        let const_name = format_ident!("{const_name}");
+
+        let reference_name = match &sheet.module {
+            Some(module) => format!("{module}::{const_name}"),
+            None => const_name.to_string(),
+        };
+        known_classes.insert(reference_name, new_name.clone());
+        consts.push(quote! {
+            #[doc = #name]
+            pub(crate) const #const_name: &str = #new_name;
+        });
+    }
+
+    let emit_css_result = emit_css(&code);
+    let consts = if let Some(module) = sheet.module {
+        quote! {
+            #[doc = "Class names generated by `scoped_css!`"]
+            pub(crate) mod #module {
+                #(for const_ in consts) {
+                    #const_
+                }
+            }
+        }
+    } else {
+        quote! {
+            #(for const_ in consts) {
+                #const_
+            }
+        }
+    };
+    Ok(quote! {
+        #consts
+        #emit_css_result
+    })
+}
+
+/// Compile the css of a `scoped_css!`, rewriting the classes to their generated names.
+///
+/// Returns the compiled css, and the original and generated name of every class.
+/// Rules inside at-rules such as `@media (prefers-color-scheme: dark)` use the same generated
+/// names as the top level rules.
+#[cfg(feature = "scoped_css")]
+fn compile_scoped_css(
+    css: &str,
+    component: bool,
+) -> Result<(String, Vec<(String, String)>), String> {
+    let css = if component {
+        wrap_in_scope(css)?
+    } else {
+        css.to_owned()
+    };
 
     let caller_name =
         std::env::var("CARGO_PKG_NAME").unwrap_or_else(|_| String::from("unknown-caller"));
@@ -335,13 +964,7 @@ pub fn scoped_css(css_input: proc_macro::TokenStream) -> proc_macro::TokenStream
             flags: lightningcss::stylesheet::ParserFlags::empty(),
         },
     );
-    let styles = match styles {
-        Ok(styles) => styles,
-        Err(err) => {
-            let err = err.to_string();
-            return quote!(compile_error!(#err)).into();
-        }
-    };
+    let styles = styles.map_err(|err| err.to_string())?;
 
     #[expect(
         clippy::expect_used,
@@ -353,7 +976,11 @@ pub fn scoped_css(css_input: proc_macro::TokenStream) -> proc_macro::TokenStream
             project_root: None,
             analyze_dependencies: None,
             pseudo_classes: None,
-            targets: lightningcss::targets::Targets::default(),
+            // Lower `&` nesting (written by the user or introduced by the scope) to flat selectors
+            targets: lightningcss::targets::Targets {
+                include: lightningcss::targets::Features::Nesting,
+                ..lightningcss::targets::Targets::default()
+            },
         })
         .expect("Failed to convert css to string");
 
@@ -361,27 +988,152 @@ pub fn scoped_css(css_input: proc_macro::TokenStream) -> proc_macro::TokenStream
         clippy::expect_used,
         reason = "We set the css_modules value to true, so this field should be present"
     )]
-    let expand = css_result.exports.expect("Exports not found");
-    let mut consts = Vec::with_capacity(expand.len());
-    for (name, export) in expand {
-        let new_name = export.name;
-        let const_name = name.to_case(Case::Constant);
-        let const_name = format_ident!("{const_name}");
+    let classes = css_result
+        .exports
+        .expect("Exports not found")
+        .into_iter()
+        .map(|(name, export)| (name, export.name))
+        .collect();
 
-        consts.push(quote! {
-            #[doc = #name]
-            pub(crate) const #const_name: &str = #new_name;
-        });
+    Ok((css_result.code, classes))
+}
+
+/// Input to `scoped_css!`
+#[cfg(feature = "scoped_css")]
+struct ScopedCssInput {
+    /// The stylesheets, in the order they were given
+    sheets: Vec<ScopedCssSheet>,
+}
+
+#[cfg(feature = "scoped_css")]
+impl syn::parse::Parse for ScopedCssInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut sheets = vec![input.parse()?];
+        while !input.is_empty() {
+            sheets.push(input.parse()?);
+        }
+        Ok(Self { sheets })
     }
+}
 
-    let emit_css_result = emit_css(css_result.code);
-    quote! {
-        #(for const_ in consts) {
-            #const_
+/// A single stylesheet of a `scoped_css!`
+#[cfg(feature = "scoped_css")]
+struct ScopedCssSheet {
+    /// The module to put the constants in, given as `mod name`
+    module: Option<syn::Ident>,
+    /// Was `@component` given
+    component: bool,
+    /// The css
+    css: syn::LitStr,
+}
+
+#[cfg(feature = "scoped_css")]
+impl syn::parse::Parse for ScopedCssSheet {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let module = if input.peek(syn::Token![mod]) {
+            input.parse::<syn::Token![mod]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let component = if input.peek(syn::Token![@]) {
+            input.parse::<syn::Token![@]>()?;
+            let mode: syn::Ident = input.parse()?;
+            if mode != "component" {
+                return Err(syn::Error::new(mode.span(), "Expected `@component`"));
+            }
+            true
+        } else {
+            false
+        };
+
+        Ok(Self {
+            module,
+            component,
+            css: input.parse()?,
+        })
+    }
+}
+
+/// Name of the class the rules of a `@component` `scoped_css!` are nested under
+#[cfg(feature = "scoped_css")]
+const SCOPE_CLASS: &str = "natrix-scope";
+
+/// Nest all rules under the scope class, except for those in `@unscoped { ... }` blocks which are
+/// kept at the top level.
+#[cfg(feature = "scoped_css")]
+fn wrap_in_scope(css: &str) -> Result<String, String> {
+    let mut scoped = String::with_capacity(css.len());
+    let mut unscoped = String::new();
+
+    let mut rest = css;
+    while let Some((before, after)) = rest.split_once("@unscoped") {
+        scoped.push_str(before);
+
+        let Some(body) = after.trim_start().strip_prefix('{') else {
+            return Err(String::from("Expected `{` after `@unscoped`"));
+        };
+
+        let mut depth = 1_usize;
+        let mut end = None;
+        for (index, char) in body.char_indices() {
+            match char {
+                '{' => depth = depth.saturating_add(1),
+                '}' => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        end = Some(index);
+                        break;
+                    }
+                }
+                _ => {}
+            }
         }
-        #emit_css_result
+        let Some(end) = end else {
+            return Err(String::from("Unclosed `@unscoped` block"));
+        };
+
+        let (inner, after) = body.split_at(end);
+        unscoped.push_str(inner);
+        unscoped.push('\n');
+        rest = after.strip_prefix('}').unwrap_or(after);
     }
-    .into()
+    scoped.push_str(rest);
+
+    Ok(format!("{unscoped}\n.{SCOPE_CLASS} {{\n{scoped}\n}}"))
+}
+
+/// Replace `${NAME}` references with a selector for the class generated for `NAME` by a previous
+/// stylesheet of the same `scoped_css!`.
+#[cfg(feature = "scoped_css")]
+fn resolve_class_references(
+    css: &str,
+    known_classes: &std::collections::HashMap<String, String>,
+) -> Result<String, String> {
+    let mut result = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some((before, after)) = rest.split_once("${") {
+        result.push_str(before);
+
+        let Some((name, after)) = after.split_once('}') else {
+            return Err(String::from("Unclosed `${` in css"));
+        };
+        let name = name.trim();
+
+        let Some(class) = known_classes.get(name) else {
+            return Err(format!(
+                "`{name}` is not a class of a earlier stylesheet in this `scoped_css!`"
+            ));
+        };
+        result.push_str(":global(.");
+        result.push_str(class);
+        result.push(')');
+
+        rest = after;
+    }
+    result.push_str(rest);
+
+    Ok(result)
 }
 
 /// Generate a ad-hoc class with the specific style
@@ -392,12 +1144,23 @@ pub fn scoped_css(css_input: proc_macro::TokenStream) -> proc_macro::TokenStream
 ///
 /// If a element requires many of these classes, consider using a scoped css macro instead of
 /// generate one common class for all properties
+///
+/// The property names are checked against the known css properties, custom properties (`--name`)
+/// and vendor prefixed properties (`-webkit-name`) are always allowed.
+/// Prefix the string with `unchecked` to skip this for experimental properties.
+/// ```ignore
+/// style!("margin: 0 auto")
+/// style!(unchecked "anchor-name: --tooltip")
+/// ```
 #[proc_macro]
 #[cfg(feature = "inline_css")]
 pub fn style(css: proc_macro::TokenStream) -> proc_macro::TokenStream {
     use std::hash::{DefaultHasher, Hash, Hasher};
 
-    let css = syn::parse_macro_input!(css as syn::LitStr);
+    let StyleInput { unchecked, css } = syn::parse_macro_input!(css as StyleInput);
+    if !unchecked && let Err(err) = check_properties(&css.value()) {
+        return syn::Error::new(css.span(), err).into_compile_error().into();
+    }
     let css = css.value();
 
     let mut hasher = DefaultHasher::default();
@@ -408,7 +1171,330 @@ pub fn style(css: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let class_name = format!("inline-{hash}");
 
     let css = format!(".{class_name} {{ {css} }}");
-    emit_css(css);
+    emit_css(&css);
 
     quote!(#class_name).into()
 }
+
+/// The input of the `style!` macro, the css with a optional `unchecked` marker
+#[cfg(feature = "inline_css")]
+struct StyleInput {
+    /// Should the property names be left unchecked
+    unchecked: bool,
+    /// The css declarations
+    css: syn::LitStr,
+}
+
+#[cfg(feature = "inline_css")]
+impl syn::parse::Parse for StyleInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let unchecked = if input.peek(syn::Ident) {
+            let marker: syn::Ident = input.parse()?;
+            if marker != "unchecked" {
+                return Err(syn::Error::new(
+                    marker.span(),
+                    "Expected `unchecked` or a string literal",
+                ));
+            }
+            true
+        } else {
+            false
+        };
+        let css = input.parse()?;
+        Ok(Self { unchecked, css })
+    }
+}
+
+/// Check that every declaration in the css uses a known property name.
+///
+/// Declarations are split on top level `;`, so semicolons in strings and functions (such as
+/// `url(...)`) are fine. Nested rules are not checked.
+#[cfg(feature = "inline_css")]
+fn check_properties(css: &str) -> Result<(), String> {
+    let css = minify_css(css);
+    for declaration in split_declarations(&css) {
+        let declaration = declaration.trim();
+        if declaration.is_empty() || declaration.contains('{') {
+            continue;
+        }
+        let Some((property, _)) = declaration.split_once(':') else {
+            return Err(format!("Expected `property: value`, found `{declaration}`"));
+        };
+        let property = property.trim();
+        let known = property.starts_with('-')
+            || css_properties::PROPERTIES.contains(&property.to_ascii_lowercase().as_str());
+        if !known {
+            return Err(format!(
+                "Unknown css property `{property}`, use `unchecked` for experimental properties"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Split the css on `;` outside of strings, parentheses and blocks
+#[cfg(feature = "inline_css")]
+fn split_declarations(css: &str) -> Vec<&str> {
+    let mut declarations = Vec::new();
+    let mut depth = 0_usize;
+    let mut quote = None;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (index, character) in css.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match (character, quote) {
+            ('\\', _) => escaped = true,
+            (character, Some(open)) if character == open => quote = None,
+            ('"' | '\'', None) => quote = Some(character),
+            ('(' | '[' | '{', None) => depth = depth.saturating_add(1),
+            (')' | ']' | '}', None) => depth = depth.saturating_sub(1),
+            (';', None) if depth == 0 => {
+                declarations.extend(css.get(start..index));
+                start = index.saturating_add(1);
+            }
+            _ => {}
+        }
+    }
+    declarations.extend(css.get(start..));
+    declarations
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::{check_natrix_flags, has_natrix_flag, minify_css};
+
+    #[test]
+    fn natrix_flags_accept_lists() {
+        let item: syn::ItemStruct = parse_quote! {
+            struct Form {
+                #[natrix(skip, eq)]
+                value: u8,
+            }
+        };
+        assert!(check_natrix_flags(&item).is_ok());
+
+        let attributes: Vec<syn::Attribute> = vec![parse_quote!(#[natrix(skip, eq)])];
+        assert!(has_natrix_flag(&attributes, "skip"));
+        assert!(has_natrix_flag(&attributes, "eq"));
+        assert!(!has_natrix_flag(&attributes, "builder"));
+    }
+
+    #[test]
+    fn unknown_natrix_flag_is_an_error() {
+        let item: syn::ItemStruct = parse_quote! {
+            struct Form {
+                #[natrix(skipp)]
+                value: u8,
+            }
+        };
+        assert!(check_natrix_flags(&item).is_err());
+
+        let item: syn::ItemStruct = parse_quote! {
+            #[natrix(skip)]
+            struct Form;
+        };
+        assert!(check_natrix_flags(&item).is_err());
+    }
+
+    #[test]
+    fn minify_compacts_stylesheet() {
+        let css = "
+            /* The main container */
+            .container   .item > a,
+            .other {
+                color: red;
+                margin: 0 auto  ;
+            }
+
+            @media (width > 100px) {
+                .item { padding: calc(1px + 2px); }
+            }
+        ";
+        assert_eq!(
+            minify_css(css),
+            ".container .item > a,.other{color: red;margin: 0 auto}@media (width > 100px){.item{padding: calc(1px + 2px)}}"
+        );
+    }
+
+    #[test]
+    fn minify_keeps_strings() {
+        let css =
+            r#".a::before { content: "  /* not a comment */  "; } .b::after { content: ' \'  '; }"#;
+        assert_eq!(
+            minify_css(css),
+            r#".a::before{content: "  /* not a comment */  "}.b::after{content: ' \'  '}"#
+        );
+    }
+
+    #[test]
+    fn minify_comment_separates_tokens() {
+        assert_eq!(
+            minify_css(".a/**/.b { margin: 0/* top */auto; }"),
+            ".a.b{margin: 0 auto}"
+        );
+        assert_eq!(
+            minify_css(".a { /* x */ color: red /* y */; }"),
+            ".a{color: red}"
+        );
+    }
+
+    #[test]
+    fn minify_keeps_escapes() {
+        assert_eq!(minify_css(".a\\  .b {}"), ".a\\  .b{}");
+    }
+
+    #[cfg(feature = "inline_css")]
+    mod inline_css {
+        use crate::check_properties;
+
+        #[test]
+        fn known_properties_pass() {
+            assert_eq!(
+                check_properties(
+                    "margin: 0 auto; FONT-SIZE: 4rem; --gap: 1px; -webkit-appearance: none;"
+                ),
+                Ok(())
+            );
+        }
+
+        #[test]
+        fn typos_are_rejected() -> Result<(), String> {
+            let Err(err) = check_properties("color: red; margni: 0") else {
+                return Err(String::from("Typo was accepted"));
+            };
+            assert!(err.contains("`margni`"));
+            Ok(())
+        }
+
+        #[test]
+        fn semicolons_in_values_are_ignored() {
+            assert_eq!(
+                check_properties(
+                    r#"background: url("data:image/png;base64,AA"); content: ";x: y""#
+                ),
+                Ok(())
+            );
+        }
+
+        #[test]
+        fn missing_colon_is_rejected() {
+            assert!(check_properties("margin 0").is_err());
+        }
+    }
+
+    #[cfg(feature = "scoped_css")]
+    mod scoped_css {
+        use std::collections::HashMap;
+
+        use crate::{SCOPE_CLASS, compile_scoped_css, resolve_class_references};
+
+        /// Css with a light and a dark rule for the same class
+        const THEMED: &str = "
+            .card { color: black; }
+            @media (prefers-color-scheme: dark) {
+                .card { color: white; }
+            }
+        ";
+
+        /// Split the compiled css into the part before and inside the dark mode media query
+        fn split_dark(code: &str) -> Result<(&str, &str), String> {
+            code.split_once("@media (prefers-color-scheme: dark)")
+                .ok_or_else(|| format!("Dark mode block missing in {code}"))
+        }
+
+        #[test]
+        fn dark_mode_rules_use_generated_class() -> Result<(), String> {
+            let (code, classes) = compile_scoped_css(THEMED, false)?;
+            let [(name, class)] = classes.as_slice() else {
+                return Err(format!("Expected a single class, got {classes:?}"));
+            };
+            assert_eq!(name, "card");
+
+            let (light, dark) = split_dark(&code)?;
+            let selector = format!(".{class}");
+            assert!(light.contains(&selector));
+            assert!(dark.contains(&selector));
+            assert!(dark.contains("color: #fff") || dark.contains("color: white"));
+            Ok(())
+        }
+
+        #[test]
+        fn nested_rules_are_flattened() -> Result<(), String> {
+            let (code, classes) = compile_scoped_css(
+                "
+                .card {
+                    color: black;
+                    &:hover { color: red; }
+                    & .title { font-weight: bold; }
+                    @media (max-width: 600px) {
+                        & .title { font-weight: normal; }
+                    }
+                }
+                ",
+                false,
+            )?;
+            let Some((_, card)) = classes.iter().find(|(name, _)| name == "card") else {
+                return Err(format!("Card class missing in {classes:?}"));
+            };
+            let Some((_, title)) = classes.iter().find(|(name, _)| name == "title") else {
+                return Err(format!("Title class missing in {classes:?}"));
+            };
+
+            assert!(!code.contains('&'), "Nesting not lowered in {code}");
+            assert!(code.contains(&format!(".{card}:hover")));
+            assert!(code.contains(&format!(".{card} .{title}")));
+
+            // lightningcss prints the query in range syntax
+            let (_, narrow) = code
+                .split_once("@media (width <= 600px)")
+                .ok_or_else(|| format!("Media query missing in {code}"))?;
+            assert!(narrow.contains(&format!(".{card} .{title}")));
+            Ok(())
+        }
+
+        #[test]
+        fn dark_mode_rules_are_scoped_in_component() -> Result<(), String> {
+            let (code, classes) = compile_scoped_css(THEMED, true)?;
+            let Some((_, scope)) = classes.iter().find(|(name, _)| name == SCOPE_CLASS) else {
+                return Err(format!("Scope class missing in {classes:?}"));
+            };
+            let Some((_, class)) = classes.iter().find(|(name, _)| name == "card") else {
+                return Err(format!("Card class missing in {classes:?}"));
+            };
+
+            let (_, dark) = split_dark(&code)?;
+            assert!(dark.contains(&format!(".{scope} .{class}")));
+            Ok(())
+        }
+
+        #[test]
+        fn class_references_resolve_to_earlier_sheets() -> Result<(), String> {
+            let known_classes =
+                HashMap::from([(String::from("button::BUTTON"), String::from("abc-button"))]);
+
+            let css = resolve_class_references(
+                ".card ${ button::BUTTON } { color: blue; }",
+                &known_classes,
+            )?;
+            assert_eq!(css, ".card :global(.abc-button) { color: blue; }");
+
+            let (code, classes) = compile_scoped_css(&css, false)?;
+            assert!(code.contains(".abc-button"));
+            assert!(classes.iter().all(|(name, _)| name != "abc-button"));
+            Ok(())
+        }
+
+        #[test]
+        fn unknown_class_reference_is_an_error() {
+            let known_classes = HashMap::new();
+            assert!(resolve_class_references(".card ${BUTTON} {}", &known_classes).is_err());
+            assert!(resolve_class_references(".card ${BUTTON {}", &known_classes).is_err());
+        }
+    }
+}
@@ -5,7 +5,6 @@
     clippy::todo,
     clippy::unreachable,
     clippy::unwrap_used,
-    clippy::unreachable,
     clippy::indexing_slicing
 )]
 #![deny(